@@ -0,0 +1,112 @@
+use std::{fs, path::Path};
+
+use reclaimx::{Asset, AssetType};
+use reclaimx_client::instruction::VaultConfig;
+use serde::Deserialize;
+
+use crate::{
+    error::{Error, Result},
+    parse_pubkey,
+};
+
+/// Vault configuration as written by users, in TOML or JSON.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VaultConfigFile {
+    /// Tells apart vaults created by the same key
+    #[serde(default)]
+    pub vault_seed: u64,
+    pub guardians: Vec<String>,
+    pub threshold: u8,
+    pub timelock: u64,
+    pub inactivity_period: u64,
+    pub backup_wallet: String,
+    #[serde(default)]
+    pub stake_amount: u64,
+    #[serde(default)]
+    pub assets: Vec<AssetConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AssetConfig {
+    #[serde(rename = "type")]
+    pub kind: AssetKind,
+    pub mint: String,
+    pub token_account: String,
+    #[serde(default = "one")]
+    pub amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssetKind {
+    Token,
+    Nft,
+}
+
+fn one() -> u64 {
+    1
+}
+
+impl VaultConfigFile {
+    /// Reads `path` as JSON if it ends in `.json`, TOML otherwise.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let data = fs::read_to_string(path)?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json(&data)
+        } else {
+            Self::from_toml(&data)
+        }
+    }
+
+    pub fn from_toml(data: &str) -> Result<Self> {
+        toml::from_str(data).map_err(|err| Error::Config(err.to_string()))
+    }
+
+    pub fn from_json(data: &str) -> Result<Self> {
+        serde_json::from_str(data).map_err(|err| Error::Config(err.to_string()))
+    }
+
+    pub fn into_vault_config(self) -> Result<VaultConfig> {
+        let guardians = self
+            .guardians
+            .iter()
+            .map(|guardian| parse_pubkey(guardian))
+            .collect::<Result<Vec<_>>>()?;
+        if self.threshold == 0 || self.threshold as usize > guardians.len() {
+            return Err(Error::Config(format!(
+                "threshold {} must be between 1 and the number of guardians ({})",
+                self.threshold,
+                guardians.len()
+            )));
+        }
+        let assets = self
+            .assets
+            .iter()
+            .map(|asset| {
+                Ok(Asset {
+                    asset_type: match asset.kind {
+                        AssetKind::Token => AssetType::Token,
+                        AssetKind::Nft => AssetType::NFT,
+                    },
+                    amount: asset.amount,
+                    token_account: parse_pubkey(&asset.token_account)?,
+                    mint: parse_pubkey(&asset.mint)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(VaultConfig {
+            vault_seed: self.vault_seed,
+            guardians,
+            threshold: self.threshold,
+            timelock: self.timelock,
+            inactivity_period: self.inactivity_period,
+            backup_wallet: parse_pubkey(&self.backup_wallet)?,
+            stake_amount: self.stake_amount,
+            assets,
+        })
+    }
+}
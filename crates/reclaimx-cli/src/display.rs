@@ -0,0 +1,284 @@
+use std::fmt::Write;
+
+use anchor_lang::prelude::Pubkey;
+use reclaimx::{
+    freeze, ownership, recovery, timing, AssetType, PauseScope, ProtocolConfig, Recovery, Vault,
+};
+use reclaimx_indexer::VaultEvent;
+
+/// Multi-line, human-readable summary of a vault and its active recovery at `now`.
+pub fn vault_summary(
+    address: &Pubkey,
+    vault: &Vault,
+    active: Option<&Recovery>,
+    now: i64,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Vault:             {}", address);
+    let _ = writeln!(out, "Owner:             {}", vault.owner);
+    let _ = writeln!(out, "Backup wallet:     {}", vault.backup_wallet);
+    let _ = writeln!(
+        out,
+        "Guardians:         {} of {} required",
+        vault.threshold,
+        vault.guardians.len() + vault.evm_guardians.len()
+    );
+    for guardian in &vault.guardians {
+        let _ = writeln!(out, "  {}", guardian);
+    }
+    for address in &vault.evm_guardians {
+        let _ = writeln!(out, "  0x{} (EVM)", hex::encode(address));
+    }
+    if vault.creator != vault.owner || vault.vault_seed != 0 {
+        let _ = writeln!(
+            out,
+            "Created by:        {} (vault_seed {})",
+            vault.creator, vault.vault_seed
+        );
+    }
+    if let Some(pending) = &vault.pending_ownership {
+        let accept_at = pending
+            .proposed_at
+            .saturating_add(ownership::OWNERSHIP_TRANSFER_DELAY as i64);
+        let _ = writeln!(
+            out,
+            "Proposed owner:    {} ({})",
+            pending.new_owner,
+            if now >= accept_at {
+                "can accept".to_string()
+            } else {
+                format!("can accept in {}", duration((accept_at - now) as u64))
+            }
+        );
+    }
+    let _ = writeln!(out, "Timelock:          {}", duration(vault.timelock));
+    let _ = writeln!(
+        out,
+        "Recovery expiry:   {} (approvals valid for {})",
+        duration(vault.recovery_expiry),
+        duration(vault.approval_window)
+    );
+    let inactive_at = vault
+        .last_active_timestamp
+        .saturating_add(i64::try_from(vault.inactivity_period).unwrap_or(i64::MAX));
+    let _ = writeln!(
+        out,
+        "Inactivity period: {} (last active {}, {})",
+        duration(vault.inactivity_period),
+        vault.last_active_timestamp,
+        if recovery::inactivity_elapsed(vault, now) {
+            "elapsed".to_string()
+        } else {
+            format!("elapses in {}", duration((inactive_at - now) as u64))
+        }
+    );
+    if let Some(pending) = &vault.pending_timing {
+        let applies_at = pending
+            .queued_at
+            .saturating_add(i64::try_from(vault.timelock).unwrap_or(i64::MAX));
+        let _ = writeln!(
+            out,
+            "Queued timing:     timelock {}, inactivity period {} ({})",
+            duration(pending.timelock),
+            duration(pending.inactivity_period),
+            if timing::ready_to_apply(vault, now) {
+                "ready to apply".to_string()
+            } else {
+                format!("applies in {}", duration((applies_at - now) as u64))
+            }
+        );
+    }
+    let _ = writeln!(out, "Guardian stake:    {}", vault.stake_amount);
+    if !vault.spending_limits.is_empty() {
+        let _ = writeln!(out, "Spending limits:");
+        for limit in &vault.spending_limits {
+            let _ = writeln!(
+                out,
+                "  {} {} per {} ({} left){}",
+                limit.mint,
+                limit.max_amount,
+                duration(limit.period),
+                limit.remaining(now),
+                match limit.queue_above {
+                    Some(queue_above) => format!(", queued above {}", queue_above),
+                    None => String::new(),
+                }
+            );
+        }
+    }
+    if freeze::is_frozen(vault, now) {
+        let _ = writeln!(
+            out,
+            "Frozen:            until {}{}",
+            vault.frozen_until,
+            if vault.frozen_by_guardians {
+                " (by guardians)"
+            } else {
+                ""
+            }
+        );
+    }
+
+    match active {
+        None if vault.recovery_id == 0 => {
+            let _ = writeln!(out, "Recovery:          none");
+        }
+        None => {
+            let _ = writeln!(
+                out,
+                "Recovery:          none pending (last #{})",
+                vault.recovery_id
+            );
+        }
+        Some(active) => {
+            let _ = writeln!(
+                out,
+                "Recovery:          #{} pending{}",
+                active.recovery_id,
+                if active.inactivity_triggered {
+                    " (inactivity)"
+                } else {
+                    ""
+                }
+            );
+            let _ = writeln!(out, "  Proposer:        {}", active.proposer);
+            let _ = writeln!(out, "  New owner:       {}", active.new_owner);
+            let _ = writeln!(out, "  Started:         {}", active.start_timestamp);
+            let _ = writeln!(
+                out,
+                "  Approvals:       {}/{}",
+                recovery::guardian_approvals(vault, active, now),
+                vault.threshold
+            );
+            for approval in &active.approvals {
+                let _ = writeln!(out, "    {} at {}", approval.guardian, approval.timestamp);
+            }
+            if !active.malicious_approvals.is_empty() {
+                let _ = writeln!(
+                    out,
+                    "  Malicious votes: {}",
+                    active.malicious_approvals.len()
+                );
+            }
+            let ready = if recovery::ready_to_execute(vault, active, now) {
+                "ready to execute"
+            } else if recovery::expired(vault, active, now) {
+                "expired"
+            } else if freeze::is_frozen(vault, now) {
+                "frozen"
+            } else {
+                "not ready"
+            };
+            let _ = writeln!(out, "  Status:          {}", ready);
+        }
+    }
+
+    if !vault.heartbeat_keys.is_empty() {
+        let _ = writeln!(out, "Heartbeat keys:");
+        for key in &vault.heartbeat_keys {
+            let _ = writeln!(out, "  {}", key);
+        }
+    }
+    if !vault.assets.is_empty() {
+        let _ = writeln!(out, "Assets:");
+        for asset in &vault.assets {
+            let kind = match asset.asset_type {
+                AssetType::Token => "token",
+                AssetType::NFT => "nft",
+            };
+            let _ = writeln!(
+                out,
+                "  {} {} x{} in {}",
+                kind, asset.mint, asset.amount, asset.token_account
+            );
+        }
+    }
+    out
+}
+
+/// Multi-line summary of the protocol config.
+pub fn config_summary(address: &Pubkey, config: &ProtocolConfig) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Config:            {}", address);
+    let _ = writeln!(out, "Admin:             {}", config.admin);
+    if let Some(pending) = config.pending_admin {
+        let _ = writeln!(out, "Pending admin:     {}", pending);
+    }
+    if let Some(council) = config.security_council {
+        let _ = writeln!(out, "Security council:  {}", council);
+    }
+    let _ = writeln!(out, "Endpoint program:  {}", config.endpoint_program);
+    let _ = writeln!(out, "Local EID:         {}", config.local_eid);
+    let _ = writeln!(out, "Allowed EIDs:      {:?}", config.allowed_eids);
+    let _ = writeln!(
+        out,
+        "Timelock range:    {} to {}",
+        duration(config.min_timelock),
+        duration(config.max_timelock)
+    );
+    let _ = writeln!(
+        out,
+        "Inactivity range:  {} to {}",
+        duration(config.min_inactivity_period),
+        duration(config.max_inactivity_period)
+    );
+    let _ = writeln!(out, "Max message fee:   {} lamports", config.max_native_fee);
+    let _ = writeln!(
+        out,
+        "Stake message fee: {} lamports",
+        config.stake_native_fee
+    );
+    // Every scope reads as paused under `All`, so list them only without it
+    let scopes = if config.is_paused(PauseScope::All) {
+        vec![PauseScope::All]
+    } else {
+        vec![
+            PauseScope::InboundLayerZero,
+            PauseScope::OutboundLayerZero,
+            PauseScope::RecoveryExecution,
+            PauseScope::Staking,
+        ]
+    };
+    let paused: Vec<String> = scopes
+        .into_iter()
+        .filter(|scope| config.is_paused(*scope))
+        .map(|scope| format!("{:?}", scope))
+        .collect();
+    let _ = writeln!(
+        out,
+        "Paused:            {}",
+        if paused.is_empty() {
+            "no".to_string()
+        } else {
+            paused.join(", ")
+        }
+    );
+    out
+}
+
+/// One timeline line: timestamp, event kind, actor and event-specific fields.
+pub fn event_line(event: &VaultEvent) -> String {
+    format!(
+        "{:>12}  #{:<4} {:<22} {}  {}",
+        event.timestamp(),
+        event.recovery_id(),
+        event.kind(),
+        event.actor(),
+        event.details()
+    )
+}
+
+/// Formats seconds as e.g. `7d 2h 30m 0s`, dropping leading zero units.
+pub fn duration(seconds: u64) -> String {
+    let units = [(86_400, "d"), (3_600, "h"), (60, "m"), (1, "s")];
+    let mut rest = seconds;
+    let mut parts = vec![];
+    for (size, suffix) in units {
+        let value = rest / size;
+        rest %= size;
+        if value > 0 || !parts.is_empty() || size == 1 {
+            parts.push(format!("{}{}", value, suffix));
+        }
+    }
+    parts.join(" ")
+}
@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Client(#[from] reclaimx_client::Error),
+    #[error(transparent)]
+    Indexer(#[from] reclaimx_indexer::Error),
+    #[error("rpc error: {0}")]
+    Rpc(#[from] solana_client::client_error::ClientError),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid config: {0}")]
+    Config(String),
+    #[error("invalid pubkey: {0}")]
+    InvalidPubkey(String),
+    #[error("invalid peer address: {0}")]
+    InvalidPeer(String),
+    #[error("cannot read keypair {0}: {1}")]
+    Keypair(String, String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
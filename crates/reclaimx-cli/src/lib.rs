@@ -0,0 +1,42 @@
+//! Library half of the `reclaimx` CLI: config parsing, argument parsing and
+//! human-readable formatting, kept separate from I/O so it can be tested.
+
+pub mod config;
+pub mod display;
+pub mod error;
+
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+
+pub use error::{Error, Result};
+
+pub fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|_| Error::InvalidPubkey(value.to_string()))
+}
+
+/// Parses a LayerZero peer: a base58 Solana address, or a `0x` hex EVM
+/// address or bytes32, left-padded to 32 bytes.
+pub fn parse_peer(value: &str) -> Result<[u8; 32]> {
+    let Some(hex) = value.strip_prefix("0x") else {
+        return parse_pubkey(value)
+            .map(|pubkey| pubkey.to_bytes())
+            .map_err(|_| Error::InvalidPeer(value.to_string()));
+    };
+    let bytes = hex::decode(hex).map_err(|_| Error::InvalidPeer(value.to_string()))?;
+    if bytes.len() > 32 {
+        return Err(Error::InvalidPeer(value.to_string()));
+    }
+    let mut peer = [0u8; 32];
+    peer[32 - bytes.len()..].copy_from_slice(&bytes);
+    Ok(peer)
+}
+
+/// Parses a `0x`-prefixed 20-byte Ethereum address.
+pub fn parse_evm_address(value: &str) -> Result<[u8; 20]> {
+    value
+        .strip_prefix("0x")
+        .and_then(|hex| hex::decode(hex).ok())
+        .and_then(|bytes| <[u8; 20]>::try_from(bytes).ok())
+        .ok_or_else(|| Error::InvalidPeer(value.to_string()))
+}
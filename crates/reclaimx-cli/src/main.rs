@@ -0,0 +1,920 @@
+use std::{
+    io::Read,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use reclaimx_cli::{
+    config::VaultConfigFile,
+    display::{config_summary, event_line, vault_summary},
+    parse_evm_address, parse_peer, parse_pubkey, Error, Result,
+};
+use reclaimx_client::{
+    attestation::{self, ApprovalMessage, EvmSignedApproval, SignedApproval},
+    fees, fetch, instruction,
+    offline::{self, DurableNonce},
+    pda, ConfigParams, Endpoint, PauseScope,
+};
+use reclaimx_indexer::{transaction_events, RpcSource, TransactionSource};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    account::from_account,
+    clock::Clock,
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    sysvar,
+    transaction::Transaction,
+};
+
+#[derive(Parser)]
+#[command(name = "reclaimx", about = "Administer ReclaimX vaults")]
+struct Cli {
+    #[arg(long, global = true, default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Keypair that signs and pays for transactions
+    #[arg(long, global = true, default_value = "~/.config/solana/id.json")]
+    keypair: String,
+    /// Build transactions for this pubkey and print them unsigned (base64)
+    /// instead of sending, e.g. for signing on a hardware wallet
+    #[arg(long, global = true)]
+    authority: Option<String>,
+    /// Durable nonce account to use instead of a recent blockhash, so
+    /// unsigned transactions do not expire before they are signed
+    #[arg(long, global = true)]
+    nonce: Option<String>,
+    /// Vault the owner commands act on; defaults to the first one the
+    /// authority created (vault_seed 0), which differs after an ownership
+    /// transfer
+    #[arg(long, global = true)]
+    owner_vault: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a vault owned by the authority from a TOML or JSON config
+    Create {
+        #[arg(long)]
+        config: PathBuf,
+    },
+    /// Print vault state; defaults to the authority's vault
+    Inspect { vault: Option<String> },
+    /// Start a recovery as the owner or a guardian
+    Initiate {
+        vault: String,
+        #[arg(long)]
+        new_owner: String,
+        /// Remote peer the recovery message is sent to
+        #[arg(long)]
+        peer: String,
+        #[arg(long, default_value_t = reclaimx::ETHEREUM_EID)]
+        dst_eid: u32,
+        /// LayerZero executor options, hex encoded
+        #[arg(long, default_value = "")]
+        options: String,
+    },
+    /// Approve the pending recovery as a guardian
+    Approve {
+        vault: String,
+        /// Approve for these guardians in one transaction, each signing
+        /// offline; defaults to the authority
+        #[arg(long = "guardian")]
+        guardians: Vec<String>,
+    },
+    /// Sign an approval off-chain as a guardian; works offline
+    Attest {
+        vault: String,
+        #[arg(long)]
+        recovery_id: u64,
+        #[arg(long)]
+        new_owner: String,
+        /// Seconds from now until the approval expires
+        #[arg(long, default_value_t = 86_400)]
+        expires_in: i64,
+    },
+    /// Relay guardian attestations in a single transaction
+    SubmitApprovals {
+        vault: String,
+        attestations: Vec<String>,
+    },
+    /// Relay EIP-712 approvals from EVM guardians in a single transaction
+    SubmitEvmApprovals {
+        vault: String,
+        /// `0xADDRESS:EXPIRY:0xSIGNATURE`, signature as returned by
+        /// eth_signTypedData_v4
+        #[arg(long = "approval", required = true)]
+        approvals: Vec<String>,
+    },
+    /// Let an Ethereum address act as a guardian of the authority's vault
+    AddEvmGuardian { address: String },
+    /// Remove an Ethereum guardian from the authority's vault
+    RemoveEvmGuardian { address: String },
+    /// Cancel the pending recovery: an inactivity-triggered one as the owner or a
+    /// heartbeat key, one the owner started as the owner, any other by casting a
+    /// guardian vote
+    Cancel { vault: String },
+    /// Withdraw tokens from the authority's vault; amounts over the spending
+    /// limit need a guardian to co-sign
+    Withdraw {
+        #[arg(long)]
+        vault_token_account: String,
+        #[arg(long)]
+        destination: String,
+        #[arg(long)]
+        amount: u64,
+        /// Guardian co-signing the withdrawal offline
+        #[arg(long)]
+        guardian: Option<String>,
+    },
+    /// Cap what the owner may withdraw of a mint per period, in seconds;
+    /// loosening an existing limit needs a guardian to co-sign
+    SetSpendingLimit {
+        mint: String,
+        #[arg(long)]
+        max_amount: u64,
+        #[arg(long)]
+        period: u64,
+        /// Withdrawals above this amount must be queued behind the timelock
+        #[arg(long)]
+        queue_above: Option<u64>,
+        #[arg(long)]
+        guardian: Option<String>,
+    },
+    /// Queue a large withdrawal from the authority's vault; it can be executed
+    /// once the timelock has passed unless a guardian vetoes it
+    QueueWithdrawal {
+        #[arg(long)]
+        vault_token_account: String,
+        #[arg(long)]
+        destination: String,
+        #[arg(long)]
+        amount: u64,
+        /// Guardian co-signing a withdrawal over the spending limit
+        #[arg(long)]
+        guardian: Option<String>,
+    },
+    /// Execute a queued withdrawal whose delay has passed
+    ExecuteWithdrawal { vault: String, withdrawal_id: u64 },
+    /// Veto a queued withdrawal as a guardian
+    VetoWithdrawal { vault: String, withdrawal_id: u64 },
+    /// Cancel a queued withdrawal from the authority's vault
+    CancelWithdrawal { withdrawal_id: u64 },
+    /// Drop the spending limit for a mint, co-signed by a guardian
+    RemoveSpendingLimit {
+        mint: String,
+        #[arg(long)]
+        guardian: String,
+    },
+    /// Freeze a vault as its owner, or vote to freeze it as a guardian
+    Freeze { vault: String },
+    /// Lift a freeze as the owner, or vote to lift it as a guardian
+    Unfreeze { vault: String },
+    /// Set how long recoveries and guardian approvals stay valid, in seconds
+    SetDeadlines {
+        #[arg(long)]
+        recovery_expiry: u64,
+        #[arg(long)]
+        approval_window: u64,
+    },
+    /// Queue a new timelock and inactivity period, in seconds; they apply
+    /// once the current timelock has passed
+    UpdateTiming {
+        #[arg(long)]
+        timelock: u64,
+        #[arg(long)]
+        inactivity_period: u64,
+    },
+    /// Propose a new owner for the authority's vault; they accept once the
+    /// transfer delay has passed
+    TransferOwnership { new_owner: String },
+    /// Take over a vault as its proposed owner
+    AcceptOwnership { vault: String },
+    /// Cancel the pending ownership transfer of the authority's vault
+    CancelOwnershipTransfer,
+    /// Apply a queued timing change whose delay has passed
+    ApplyTiming { vault: String },
+    /// Cancel the authority's queued timing change
+    CancelTimingUpdate,
+    /// Trust a remote OApp for an endpoint ID
+    SetPeer {
+        #[arg(long)]
+        eid: u32,
+        /// Base58 Solana address or 0x-prefixed EVM address
+        #[arg(long)]
+        peer: String,
+    },
+    /// Print the event history of a vault
+    Timeline { vault: String },
+    /// Print the protocol config
+    ShowConfig,
+    /// Create the protocol config with default settings; run once after
+    /// deploying, signed by the program's upgrade authority
+    InitConfig {
+        #[arg(long)]
+        admin: String,
+    },
+    /// Change protocol settings as the admin; omitted flags keep their value
+    UpdateConfig {
+        #[arg(long)]
+        endpoint_program: Option<String>,
+        /// Replaces the allowed EIDs; repeat for each EID
+        #[arg(long = "allowed-eid")]
+        allowed_eids: Vec<u32>,
+        #[arg(long)]
+        local_eid: Option<u32>,
+        #[arg(long)]
+        min_timelock: Option<u64>,
+        #[arg(long)]
+        max_timelock: Option<u64>,
+        #[arg(long)]
+        min_inactivity_period: Option<u64>,
+        #[arg(long)]
+        max_inactivity_period: Option<u64>,
+        #[arg(long)]
+        max_native_fee: Option<u64>,
+        #[arg(long)]
+        stake_native_fee: Option<u64>,
+        /// Key allowed to pause and unpause alongside the admin
+        #[arg(long)]
+        security_council: Option<String>,
+        /// Remove the security council
+        #[arg(long, conflicts_with = "security_council")]
+        no_security_council: bool,
+    },
+    /// Pause part or all of the protocol as the admin or security council
+    Pause {
+        #[arg(long, value_enum, default_value_t = Scope::All)]
+        scope: Scope,
+    },
+    /// Lift a pause as the admin or security council
+    Unpause {
+        #[arg(long, value_enum, default_value_t = Scope::All)]
+        scope: Scope,
+    },
+    /// Propose a new protocol admin, or withdraw a proposal if omitted
+    ProposeAdmin { admin: Option<String> },
+    /// Become the protocol admin after being proposed
+    AcceptAdmin,
+    /// Add the keypair's signature to an encoded transaction; works offline
+    Sign {
+        /// Base64 transaction, or `-` to read stdin
+        transaction: String,
+    },
+    /// Merge signatures from copies of the same transaction
+    Combine { transactions: Vec<String> },
+    /// Send a fully signed transaction
+    Broadcast { transaction: String },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Scope {
+    All,
+    Inbound,
+    Outbound,
+    RecoveryExecution,
+    Staking,
+}
+
+impl From<Scope> for PauseScope {
+    fn from(scope: Scope) -> Self {
+        match scope {
+            Scope::All => PauseScope::All,
+            Scope::Inbound => PauseScope::InboundLayerZero,
+            Scope::Outbound => PauseScope::OutboundLayerZero,
+            Scope::RecoveryExecution => PauseScope::RecoveryExecution,
+            Scope::Staking => PauseScope::Staking,
+        }
+    }
+}
+
+enum Authority {
+    Keypair(Keypair),
+    Offline(Pubkey),
+}
+
+impl Authority {
+    fn load(cli: &Cli) -> Result<Self> {
+        if let Some(pubkey) = &cli.authority {
+            return Ok(Authority::Offline(parse_pubkey(pubkey)?));
+        }
+        let path = expand_home(&cli.keypair);
+        read_keypair_file(&path)
+            .map(Authority::Keypair)
+            .map_err(|err| Error::Keypair(path.display().to_string(), err.to_string()))
+    }
+
+    fn pubkey(&self) -> Pubkey {
+        match self {
+            Authority::Keypair(keypair) => keypair.pubkey(),
+            Authority::Offline(pubkey) => *pubkey,
+        }
+    }
+
+    /// Builds a transaction paid for by the authority. It is signed and sent
+    /// when the keypair is its only signer; otherwise it is printed, signed as
+    /// far as possible, for the remaining signers.
+    fn submit(
+        &self,
+        client: &RpcClient,
+        nonce: Option<&Pubkey>,
+        instructions: &[Instruction],
+    ) -> Result<()> {
+        let fee_payer = self.pubkey();
+        let mut tx = match nonce {
+            Some(nonce) => {
+                let nonce = DurableNonce::fetch(client, nonce)?;
+                offline::unsigned_transaction(instructions, &fee_payer, &nonce)
+            }
+            None => {
+                let mut tx = Transaction::new_with_payer(instructions, Some(&fee_payer));
+                tx.message.recent_blockhash = client.get_latest_blockhash()?;
+                tx
+            }
+        };
+        if let Authority::Keypair(keypair) = self {
+            offline::sign(&mut tx, &[keypair])?;
+        }
+        print_or_send(client, &tx)
+    }
+}
+
+fn print_or_send(client: &RpcClient, tx: &Transaction) -> Result<()> {
+    let missing = offline::missing_signers(tx);
+    if missing.is_empty() {
+        let signature = client.send_and_confirm_transaction(tx)?;
+        println!("Signature: {}", signature);
+        return Ok(());
+    }
+    println!("{}", offline::encode(tx)?);
+    for signer in missing {
+        eprintln!("Missing signature: {}", signer);
+    }
+    Ok(())
+}
+
+fn read_transaction(value: &str) -> Result<Transaction> {
+    if value != "-" {
+        return Ok(offline::decode(value)?);
+    }
+    let mut data = String::new();
+    std::io::stdin().read_to_string(&mut data)?;
+    Ok(offline::decode(&data)?)
+}
+
+fn parse_evm_approval(value: &str) -> Result<EvmSignedApproval> {
+    let invalid = || Error::Config(format!("invalid EVM approval: {}", value));
+    let mut parts = value.split(':');
+    let (Some(address), Some(expiry), Some(signature), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+    let signature = signature
+        .strip_prefix("0x")
+        .and_then(|hex| hex::decode(hex).ok())
+        .and_then(|bytes| <[u8; 65]>::try_from(bytes).ok())
+        .ok_or_else(invalid)?;
+    Ok(EvmSignedApproval {
+        address: parse_evm_address(address)?,
+        signature,
+        expiry: expiry.parse().map_err(|_| invalid())?,
+    })
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn owned_vault(cli: &Cli, owner: &Pubkey) -> Result<Pubkey> {
+    match &cli.owner_vault {
+        Some(vault) => parse_pubkey(vault),
+        None => Ok(pda::vault(owner, 0)),
+    }
+}
+
+fn chain_time(client: &RpcClient) -> Result<i64> {
+    let account = client.get_account(&sysvar::clock::id())?;
+    let clock: Clock = from_account(&account)
+        .ok_or_else(|| Error::Config("clock sysvar unavailable".to_string()))?;
+    Ok(clock.unix_timestamp)
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let client = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+    let nonce = cli.nonce.as_deref().map(parse_pubkey).transpose()?;
+    let nonce = nonce.as_ref();
+
+    match &cli.command {
+        Command::Create { config } => {
+            let authority = Authority::load(&cli)?;
+            let owner = authority.pubkey();
+            let config = VaultConfigFile::load(config)?.into_vault_config()?;
+            let endpoint = Endpoint::from_config(&fetch::config(&client)?);
+            println!("Vault: {}", pda::vault(&owner, config.vault_seed));
+            let ix = instruction::initialize_vault(&owner, &owner, &endpoint, config);
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::Inspect { vault } => {
+            let vault = match vault {
+                Some(vault) => parse_pubkey(vault)?,
+                None => owned_vault(&cli, &Authority::load(&cli)?.pubkey())?,
+            };
+            let state = fetch::vault(&client, &vault)?;
+            let active = fetch::active_recovery(&client, &state)?.map(|(_, recovery)| recovery);
+            print!(
+                "{}",
+                vault_summary(&vault, &state, active.as_ref(), chain_time(&client)?)
+            );
+        }
+        Command::Initiate {
+            vault,
+            new_owner,
+            peer,
+            dst_eid,
+            options,
+        } => {
+            let authority = Authority::load(&cli)?;
+            let vault = parse_pubkey(vault)?;
+            let state = fetch::vault(&client, &vault)?;
+            let args = instruction::InitiateRecovery {
+                vault,
+                recovery: pda::recovery(&vault, state.recovery_id + 1),
+                initiator: authority.pubkey(),
+                payer: authority.pubkey(),
+                peer: Pubkey::new_from_array(parse_peer(peer)?),
+                new_owner: parse_pubkey(new_owner)?,
+                dst_eid: *dst_eid,
+                options: hex::decode(options).map_err(|err| Error::Config(err.to_string()))?,
+            };
+            let endpoint = Endpoint::from_config(&fetch::config(&client)?);
+            let fee = fees::quote_initiate_recovery(&client, &endpoint, &state, &args)?;
+            println!("LayerZero fee: {} lamports", fee.native_fee);
+            authority.submit(
+                &client,
+                nonce,
+                &[instruction::initiate_recovery(&endpoint, args)],
+            )?;
+        }
+        Command::Approve { vault, guardians } => {
+            let authority = Authority::load(&cli)?;
+            let vault = parse_pubkey(vault)?;
+            let state = fetch::vault(&client, &vault)?;
+            let (recovery, pending) = fetch::active_recovery(&client, &state)?
+                .ok_or_else(|| Error::Config("vault has no pending recovery".to_string()))?;
+            // Assets move to the new owner's associated token accounts
+            let asset_accounts =
+                instruction::recovery_asset_accounts(&state.assets, &pending.new_owner);
+            let guardians = if guardians.is_empty() {
+                vec![authority.pubkey()]
+            } else {
+                guardians
+                    .iter()
+                    .map(|guardian| parse_pubkey(guardian))
+                    .collect::<Result<Vec<_>>>()?
+            };
+            // A guardian whose approval is on-chain and still inside the approval
+            // window would be rejected as a duplicate, so leave them out
+            let now = chain_time(&client)?;
+            let approved: Vec<Pubkey> = pending
+                .approvals
+                .iter()
+                .filter(|approval| {
+                    state.is_guardian(&approval.guardian)
+                        && !reclaimx::recovery::timelock_elapsed(
+                            approval.timestamp,
+                            state.approval_window,
+                            now,
+                        )
+                })
+                .map(|approval| approval.guardian)
+                .collect();
+            // The approval that meets the threshold may execute the recovery, and
+            // any approval after it would fail and revert the whole transaction
+            let missing = (state.threshold as usize).saturating_sub(approved.len());
+            let approvals: Vec<Instruction> = guardians
+                .iter()
+                .filter(|guardian| !approved.contains(guardian))
+                .take(missing)
+                .map(|guardian| {
+                    instruction::approve_recovery(&vault, &recovery, guardian, &asset_accounts)
+                })
+                .collect();
+            if approvals.is_empty() {
+                return Err(Error::Config(
+                    "the recovery needs no further approvals from these guardians".to_string(),
+                ));
+            }
+            let mut ixs = instruction::create_recovery_asset_accounts(
+                &authority.pubkey(),
+                &state.assets,
+                &pending.new_owner,
+            );
+            ixs.extend(approvals);
+            authority.submit(&client, nonce, &ixs)?;
+        }
+        Command::Attest {
+            vault,
+            recovery_id,
+            new_owner,
+            expires_in,
+        } => {
+            let Authority::Keypair(keypair) = Authority::load(&cli)? else {
+                return Err(Error::Config("attesting requires --keypair".to_string()));
+            };
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|err| Error::Config(err.to_string()))?
+                .as_secs() as i64;
+            let message = ApprovalMessage {
+                vault: parse_pubkey(vault)?,
+                recovery_id: *recovery_id,
+                new_owner: parse_pubkey(new_owner)?,
+                expiry: now + expires_in,
+            };
+            println!("{}", SignedApproval::sign(&keypair, message).encode());
+        }
+        Command::SubmitApprovals {
+            vault,
+            attestations,
+        } => {
+            let authority = Authority::load(&cli)?;
+            let approvals = attestations
+                .iter()
+                .map(|attestation| SignedApproval::decode(attestation))
+                .collect::<reclaimx_client::Result<Vec<_>>>()?;
+            let ixs = attestation::submit_approvals_instructions(
+                &parse_pubkey(vault)?,
+                &authority.pubkey(),
+                &approvals,
+            );
+            authority.submit(&client, nonce, &ixs)?;
+        }
+        Command::SubmitEvmApprovals { vault, approvals } => {
+            let authority = Authority::load(&cli)?;
+            let vault = parse_pubkey(vault)?;
+            let (_, recovery) =
+                fetch::active_recovery(&client, &fetch::vault(&client, &vault)?)?
+                    .ok_or_else(|| Error::Config("vault has no pending recovery".to_string()))?;
+            let approvals = approvals
+                .iter()
+                .map(|approval| parse_evm_approval(approval))
+                .collect::<Result<Vec<_>>>()?;
+            let ixs = attestation::submit_evm_approvals_instructions(
+                &vault,
+                &authority.pubkey(),
+                recovery.recovery_id,
+                &recovery.new_owner,
+                &approvals,
+            )?;
+            authority.submit(&client, nonce, &ixs)?;
+        }
+        Command::AddEvmGuardian { address } => {
+            let authority = Authority::load(&cli)?;
+            let owner = authority.pubkey();
+            let ix = instruction::add_evm_guardian(
+                &owned_vault(&cli, &owner)?,
+                &owner,
+                parse_evm_address(address)?,
+            );
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::RemoveEvmGuardian { address } => {
+            let authority = Authority::load(&cli)?;
+            let owner = authority.pubkey();
+            let ix = instruction::remove_evm_guardian(
+                &owned_vault(&cli, &owner)?,
+                &owner,
+                parse_evm_address(address)?,
+            );
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::Cancel { vault } => {
+            let authority = Authority::load(&cli)?;
+            let vault = parse_pubkey(vault)?;
+            let (recovery, pending) =
+                fetch::active_recovery(&client, &fetch::vault(&client, &vault)?)?
+                    .ok_or_else(|| Error::Config("vault has no pending recovery".to_string()))?;
+            let ix = if pending.inactivity_triggered {
+                instruction::cancel_inactivity_recovery(&vault, &recovery, &authority.pubkey())
+            } else {
+                instruction::cancel_recovery(&vault, &recovery, &authority.pubkey())
+            };
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::Withdraw {
+            vault_token_account,
+            destination,
+            amount,
+            guardian,
+        } => {
+            let authority = Authority::load(&cli)?;
+            let owner = authority.pubkey();
+            let guardian = guardian.as_deref().map(parse_pubkey).transpose()?;
+            let ix = instruction::withdraw_asset(
+                &owned_vault(&cli, &owner)?,
+                &owner,
+                guardian,
+                &parse_pubkey(vault_token_account)?,
+                &parse_pubkey(destination)?,
+                *amount,
+            );
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::SetSpendingLimit {
+            mint,
+            max_amount,
+            period,
+            queue_above,
+            guardian,
+        } => {
+            let authority = Authority::load(&cli)?;
+            let owner = authority.pubkey();
+            let guardian = guardian.as_deref().map(parse_pubkey).transpose()?;
+            let ix = instruction::set_spending_limit(
+                &owned_vault(&cli, &owner)?,
+                &owner,
+                guardian,
+                parse_pubkey(mint)?,
+                *max_amount,
+                *period,
+                *queue_above,
+            );
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::QueueWithdrawal {
+            vault_token_account,
+            destination,
+            amount,
+            guardian,
+        } => {
+            let authority = Authority::load(&cli)?;
+            let owner = authority.pubkey();
+            let vault = owned_vault(&cli, &owner)?;
+            let state = fetch::vault(&client, &vault)?;
+            let guardian = guardian.as_deref().map(parse_pubkey).transpose()?;
+            println!("Withdrawal id: {}", state.withdrawal_id + 1);
+            let ix = instruction::queue_withdrawal(
+                &vault,
+                state.withdrawal_id + 1,
+                &owner,
+                guardian,
+                &parse_pubkey(vault_token_account)?,
+                &parse_pubkey(destination)?,
+                *amount,
+            );
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::ExecuteWithdrawal {
+            vault,
+            withdrawal_id,
+        } => {
+            let authority = Authority::load(&cli)?;
+            let vault = parse_pubkey(vault)?;
+            let withdrawal = fetch::withdrawal(&client, &vault, *withdrawal_id)?;
+            let ix = instruction::execute_withdrawal(&vault, &withdrawal, &authority.pubkey());
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::VetoWithdrawal {
+            vault,
+            withdrawal_id,
+        } => {
+            let authority = Authority::load(&cli)?;
+            let vault = parse_pubkey(vault)?;
+            let ix = instruction::veto_withdrawal(
+                &vault,
+                &pda::withdrawal(&vault, *withdrawal_id),
+                &authority.pubkey(),
+            );
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::CancelWithdrawal { withdrawal_id } => {
+            let authority = Authority::load(&cli)?;
+            let owner = authority.pubkey();
+            let vault = owned_vault(&cli, &owner)?;
+            let ix = instruction::cancel_withdrawal(
+                &vault,
+                &pda::withdrawal(&vault, *withdrawal_id),
+                &owner,
+            );
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::RemoveSpendingLimit { mint, guardian } => {
+            let authority = Authority::load(&cli)?;
+            let owner = authority.pubkey();
+            let ix = instruction::remove_spending_limit(
+                &owned_vault(&cli, &owner)?,
+                &owner,
+                &parse_pubkey(guardian)?,
+                parse_pubkey(mint)?,
+            );
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::Freeze { vault } => {
+            let authority = Authority::load(&cli)?;
+            let ix = instruction::freeze_vault(&parse_pubkey(vault)?, &authority.pubkey());
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::Unfreeze { vault } => {
+            let authority = Authority::load(&cli)?;
+            let ix = instruction::unfreeze_vault(&parse_pubkey(vault)?, &authority.pubkey());
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::SetDeadlines {
+            recovery_expiry,
+            approval_window,
+        } => {
+            let authority = Authority::load(&cli)?;
+            let owner = authority.pubkey();
+            let ix = instruction::set_recovery_deadlines(
+                &owned_vault(&cli, &owner)?,
+                &owner,
+                *recovery_expiry,
+                *approval_window,
+            );
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::UpdateTiming {
+            timelock,
+            inactivity_period,
+        } => {
+            let authority = Authority::load(&cli)?;
+            let owner = authority.pubkey();
+            let ix = instruction::update_timing(
+                &owned_vault(&cli, &owner)?,
+                &owner,
+                *timelock,
+                *inactivity_period,
+            );
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::TransferOwnership { new_owner } => {
+            let authority = Authority::load(&cli)?;
+            let owner = authority.pubkey();
+            let ix = instruction::transfer_ownership(
+                &owned_vault(&cli, &owner)?,
+                &owner,
+                parse_pubkey(new_owner)?,
+            );
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::AcceptOwnership { vault } => {
+            let authority = Authority::load(&cli)?;
+            let ix = instruction::accept_ownership(&parse_pubkey(vault)?, &authority.pubkey());
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::CancelOwnershipTransfer => {
+            let authority = Authority::load(&cli)?;
+            let owner = authority.pubkey();
+            let ix = instruction::cancel_ownership_transfer(&owned_vault(&cli, &owner)?, &owner);
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::ApplyTiming { vault } => {
+            let authority = Authority::load(&cli)?;
+            let ix = instruction::apply_timing(&parse_pubkey(vault)?, &authority.pubkey());
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::CancelTimingUpdate => {
+            let authority = Authority::load(&cli)?;
+            let owner = authority.pubkey();
+            let ix = instruction::cancel_timing_update(&owned_vault(&cli, &owner)?, &owner);
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::SetPeer { eid, peer } => {
+            let authority = Authority::load(&cli)?;
+            let owner = authority.pubkey();
+            let ix = instruction::set_peer(
+                &owned_vault(&cli, &owner)?,
+                &owner,
+                &owner,
+                *eid,
+                parse_peer(peer)?,
+            );
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::Timeline { vault } => {
+            let vault = parse_pubkey(vault)?;
+            let source = RpcSource::new(cli.url.clone());
+            for signature in source.signatures(&vault, None)? {
+                let Some(tx) = source.transaction(&signature)? else {
+                    continue;
+                };
+                for event in transaction_events(&tx, &reclaimx::ID)? {
+                    if event.vault_id() == vault {
+                        println!("{}", event_line(&event));
+                    }
+                }
+            }
+        }
+        Command::ShowConfig => {
+            print!(
+                "{}",
+                config_summary(&pda::config(), &fetch::config(&client)?)
+            );
+        }
+        Command::InitConfig { admin } => {
+            let authority = Authority::load(&cli)?;
+            let ix = instruction::initialize_config(
+                &authority.pubkey(),
+                &authority.pubkey(),
+                parse_pubkey(admin)?,
+                ConfigParams::default(),
+            );
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::UpdateConfig {
+            endpoint_program,
+            allowed_eids,
+            local_eid,
+            min_timelock,
+            max_timelock,
+            min_inactivity_period,
+            max_inactivity_period,
+            max_native_fee,
+            stake_native_fee,
+            security_council,
+            no_security_council,
+        } => {
+            let authority = Authority::load(&cli)?;
+            let current = fetch::config(&client)?;
+            let params = ConfigParams {
+                endpoint_program: match endpoint_program {
+                    Some(program) => parse_pubkey(program)?,
+                    None => current.endpoint_program,
+                },
+                allowed_eids: if allowed_eids.is_empty() {
+                    current.allowed_eids
+                } else {
+                    allowed_eids.clone()
+                },
+                local_eid: local_eid.unwrap_or(current.local_eid),
+                min_timelock: min_timelock.unwrap_or(current.min_timelock),
+                max_timelock: max_timelock.unwrap_or(current.max_timelock),
+                min_inactivity_period: min_inactivity_period
+                    .unwrap_or(current.min_inactivity_period),
+                max_inactivity_period: max_inactivity_period
+                    .unwrap_or(current.max_inactivity_period),
+                max_native_fee: max_native_fee.unwrap_or(current.max_native_fee),
+                stake_native_fee: stake_native_fee.unwrap_or(current.stake_native_fee),
+                security_council: match security_council {
+                    Some(council) => Some(parse_pubkey(council)?),
+                    None if *no_security_council => None,
+                    None => current.security_council,
+                },
+            };
+            let ix = instruction::update_config(&authority.pubkey(), params);
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::Pause { scope } => {
+            let authority = Authority::load(&cli)?;
+            let ix = instruction::set_paused(&authority.pubkey(), (*scope).into(), true);
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::Unpause { scope } => {
+            let authority = Authority::load(&cli)?;
+            let ix = instruction::set_paused(&authority.pubkey(), (*scope).into(), false);
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::ProposeAdmin { admin } => {
+            let authority = Authority::load(&cli)?;
+            let admin = admin.as_deref().map(parse_pubkey).transpose()?;
+            let ix = instruction::propose_admin(&authority.pubkey(), admin);
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::AcceptAdmin => {
+            let authority = Authority::load(&cli)?;
+            let ix = instruction::accept_admin(&authority.pubkey());
+            authority.submit(&client, nonce, &[ix])?;
+        }
+        Command::Sign { transaction } => {
+            let Authority::Keypair(keypair) = Authority::load(&cli)? else {
+                return Err(Error::Config("signing requires --keypair".to_string()));
+            };
+            let mut tx = read_transaction(transaction)?;
+            offline::sign(&mut tx, &[&keypair])?;
+            println!("{}", offline::encode(&tx)?);
+        }
+        Command::Combine { transactions } => {
+            let parts = transactions
+                .iter()
+                .map(|tx| read_transaction(tx))
+                .collect::<Result<Vec<_>>>()?;
+            let tx = offline::combine(&parts)?;
+            println!("{}", offline::encode(&tx)?);
+            for signer in offline::missing_signers(&tx) {
+                eprintln!("Missing signature: {}", signer);
+            }
+        }
+        Command::Broadcast { transaction } => {
+            print_or_send(&client, &read_transaction(transaction)?)?
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use reclaimx::AssetType;
+use reclaimx_cli::{config::VaultConfigFile, display::duration, parse_peer};
+
+const GUARDIAN_A: &str = "11111111111111111111111111111112";
+const GUARDIAN_B: &str = "SysvarC1ock11111111111111111111111111111111";
+const BACKUP: &str = "SysvarRent111111111111111111111111111111111";
+
+#[test]
+fn toml_and_json_configs_are_equivalent() {
+    let toml = format!(
+        r#"
+        guardians = ["{GUARDIAN_A}", "{GUARDIAN_B}"]
+        threshold = 2
+        timelock = 3600
+        inactivity_period = 86400
+        backup_wallet = "{BACKUP}"
+
+        [[assets]]
+        type = "nft"
+        mint = "{GUARDIAN_A}"
+        token_account = "{GUARDIAN_B}"
+        "#
+    );
+    let json = format!(
+        r#"{{
+            "guardians": ["{GUARDIAN_A}", "{GUARDIAN_B}"],
+            "threshold": 2,
+            "timelock": 3600,
+            "inactivity_period": 86400,
+            "backup_wallet": "{BACKUP}",
+            "assets": [{{ "type": "nft", "mint": "{GUARDIAN_A}", "token_account": "{GUARDIAN_B}" }}]
+        }}"#
+    );
+
+    let from_toml = VaultConfigFile::from_toml(&toml)
+        .unwrap()
+        .into_vault_config()
+        .unwrap();
+    let from_json = VaultConfigFile::from_json(&json)
+        .unwrap()
+        .into_vault_config()
+        .unwrap();
+
+    assert_eq!(from_toml.guardians, from_json.guardians);
+    assert_eq!(from_toml.backup_wallet, from_json.backup_wallet);
+    assert_eq!(from_toml.assets.len(), 1);
+    assert!(matches!(from_toml.assets[0].asset_type, AssetType::NFT));
+    assert_eq!(from_toml.assets[0].amount, 1);
+}
+
+#[test]
+fn threshold_above_guardian_count_is_rejected() {
+    let toml = format!(
+        r#"
+        guardians = ["{GUARDIAN_A}"]
+        threshold = 2
+        timelock = 0
+        inactivity_period = 0
+        backup_wallet = "{BACKUP}"
+        "#
+    );
+    let config = VaultConfigFile::from_toml(&toml).unwrap();
+    assert!(config.into_vault_config().is_err());
+}
+
+#[test]
+fn evm_peers_are_left_padded() {
+    let peer = parse_peer("0x1a44076050125825900e736c501f859c50fe728c").unwrap();
+    assert_eq!(&peer[..12], &[0u8; 12]);
+    assert_eq!(peer[12], 0x1a);
+    assert!(parse_peer("0x").is_ok());
+    assert!(parse_peer("not-a-peer").is_err());
+}
+
+#[test]
+fn durations_drop_leading_zero_units() {
+    assert_eq!(duration(0), "0s");
+    assert_eq!(duration(3_600), "1h 0m 0s");
+    assert_eq!(duration(90_061), "1d 1h 1m 1s");
+}
@@ -0,0 +1,196 @@
+//! Off-chain guardian approvals. Solana guardians sign an [`ApprovalMessage`]
+//! anywhere, even offline; a relayer packs the signatures into one Ed25519
+//! precompile instruction followed by `submit_approvals`. EVM guardians sign
+//! the same approval as EIP-712 typed data, relayed through the Secp256k1
+//! precompile and `submit_evm_approvals`.
+
+use anchor_lang::{
+    prelude::Pubkey, solana_program::instruction::Instruction, AnchorDeserialize, AnchorSerialize,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_sdk::{ed25519_program, secp256k1_program, signature::Signer};
+
+pub use reclaimx::attestation::{
+    eip712_domain_separator, eip712_preimage, evm_guardian_key, ApprovalMessage, APPROVAL_DOMAIN,
+};
+
+use crate::{
+    error::{Error, Result},
+    instruction, pda,
+};
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+const SECP_OFFSETS_START: usize = 1;
+const SECP_OFFSETS_SIZE: usize = 11;
+
+/// A guardian's signature over an approval message.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SignedApproval {
+    pub guardian: Pubkey,
+    pub signature: [u8; 64],
+    pub message: ApprovalMessage,
+}
+
+impl SignedApproval {
+    pub fn sign(guardian: &dyn Signer, message: ApprovalMessage) -> Self {
+        Self {
+            guardian: guardian.pubkey(),
+            signature: guardian.sign_message(&message.to_bytes()).into(),
+            message,
+        }
+    }
+
+    /// Base64 form for carrying between machines.
+    pub fn encode(&self) -> String {
+        STANDARD.encode(self.try_to_vec().expect("signed approval serializes"))
+    }
+
+    pub fn decode(data: &str) -> Result<Self> {
+        let bytes = STANDARD
+            .decode(data.trim())
+            .map_err(|err| Error::InvalidAttestation(err.to_string()))?;
+        Self::try_from_slice(&bytes).map_err(|err| Error::InvalidAttestation(err.to_string()))
+    }
+}
+
+/// One Ed25519 precompile instruction verifying every approval. All offsets
+/// point into the instruction's own data, as the program requires.
+pub fn ed25519_instruction(approvals: &[SignedApproval]) -> Instruction {
+    let messages: Vec<Vec<u8>> = approvals.iter().map(|a| a.message.to_bytes()).collect();
+    let mut offsets = Vec::with_capacity(approvals.len() * SIGNATURE_OFFSETS_SIZE);
+    let mut payload = vec![];
+    let data_start = SIGNATURE_OFFSETS_START + approvals.len() * SIGNATURE_OFFSETS_SIZE;
+
+    for (approval, message) in approvals.iter().zip(&messages) {
+        let pubkey_offset = data_start + payload.len();
+        payload.extend_from_slice(approval.guardian.as_ref());
+        let signature_offset = data_start + payload.len();
+        payload.extend_from_slice(&approval.signature);
+        let message_offset = data_start + payload.len();
+        payload.extend_from_slice(message);
+
+        for value in [
+            signature_offset as u16,
+            CURRENT_INSTRUCTION,
+            pubkey_offset as u16,
+            CURRENT_INSTRUCTION,
+            message_offset as u16,
+            message.len() as u16,
+            CURRENT_INSTRUCTION,
+        ] {
+            offsets.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    let mut data = vec![approvals.len() as u8, 0];
+    data.extend(offsets);
+    data.extend(payload);
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// The precompile instruction followed by `submit_approvals`, ready to be
+/// sent by `relayer` in a single transaction. The recovery account is the
+/// one the first approval is for.
+pub fn submit_approvals_instructions(
+    vault: &Pubkey,
+    relayer: &Pubkey,
+    approvals: &[SignedApproval],
+) -> Vec<Instruction> {
+    let recovery_id = approvals
+        .first()
+        .map_or(0, |approval| approval.message.recovery_id);
+    vec![
+        ed25519_instruction(approvals),
+        instruction::submit_approvals(vault, &pda::recovery(vault, recovery_id), relayer),
+    ]
+}
+
+/// An EVM guardian's EIP-712 signature, as returned by `eth_signTypedData_v4`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EvmSignedApproval {
+    pub address: [u8; 20],
+    /// r || s || v, with v either 0/1 or 27/28
+    pub signature: [u8; 65],
+    pub expiry: i64,
+}
+
+/// One Secp256k1 precompile instruction verifying every EVM approval against
+/// its EIP-712 preimage. `instruction_index` is the position the instruction
+/// will take in the transaction; the program requires entries to point at it.
+pub fn secp256k1_instruction(
+    vault: &Pubkey,
+    recovery_id: u64,
+    new_owner: &Pubkey,
+    approvals: &[EvmSignedApproval],
+    instruction_index: u8,
+) -> Result<Instruction> {
+    let mut offsets = Vec::with_capacity(approvals.len() * SECP_OFFSETS_SIZE);
+    let mut payload = vec![];
+    let data_start = SECP_OFFSETS_START + approvals.len() * SECP_OFFSETS_SIZE;
+
+    for approval in approvals {
+        let preimage = eip712_preimage(&ApprovalMessage {
+            vault: *vault,
+            recovery_id,
+            new_owner: *new_owner,
+            expiry: approval.expiry,
+        })
+        .map_err(|err| Error::InvalidAttestation(err.to_string()))?;
+        let recovery = match approval.signature[64] {
+            v @ (0 | 1) => v,
+            v @ (27 | 28) => v - 27,
+            v => return Err(Error::InvalidAttestation(format!("bad recovery id {}", v))),
+        };
+
+        let address_offset = data_start + payload.len();
+        payload.extend_from_slice(&approval.address);
+        let signature_offset = data_start + payload.len();
+        payload.extend_from_slice(&approval.signature[..64]);
+        payload.push(recovery);
+        let message_offset = data_start + payload.len();
+        payload.extend_from_slice(&preimage);
+
+        offsets.extend_from_slice(&(signature_offset as u16).to_le_bytes());
+        offsets.push(instruction_index);
+        offsets.extend_from_slice(&(address_offset as u16).to_le_bytes());
+        offsets.push(instruction_index);
+        offsets.extend_from_slice(&(message_offset as u16).to_le_bytes());
+        offsets.extend_from_slice(&(preimage.len() as u16).to_le_bytes());
+        offsets.push(instruction_index);
+    }
+
+    let mut data = vec![approvals.len() as u8];
+    data.extend(offsets);
+    data.extend(payload);
+    Ok(Instruction {
+        program_id: secp256k1_program::ID,
+        accounts: vec![],
+        data,
+    })
+}
+
+/// The precompile instruction followed by `submit_evm_approvals`, for a
+/// transaction in which they are the first two instructions.
+pub fn submit_evm_approvals_instructions(
+    vault: &Pubkey,
+    relayer: &Pubkey,
+    recovery_id: u64,
+    new_owner: &Pubkey,
+    approvals: &[EvmSignedApproval],
+) -> Result<Vec<Instruction>> {
+    Ok(vec![
+        secp256k1_instruction(vault, recovery_id, new_owner, approvals, 0)?,
+        instruction::submit_evm_approvals(
+            vault,
+            &pda::recovery(vault, recovery_id),
+            relayer,
+            approvals.iter().map(|approval| approval.expiry).collect(),
+        ),
+    ])
+}
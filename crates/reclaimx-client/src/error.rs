@@ -0,0 +1,25 @@
+use anchor_lang::prelude::Pubkey;
+use solana_client::client_error::ClientError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("rpc error: {0}")]
+    Rpc(#[from] ClientError),
+    #[error("account not found: {0}")]
+    AccountNotFound(Pubkey),
+    #[error("account {0} is not a valid {1}")]
+    InvalidAccount(Pubkey, &'static str),
+    #[error("simulation failed: {0}")]
+    Simulation(String),
+    #[error("endpoint returned no fee quote")]
+    MissingReturnData,
+    #[error("invalid nonce account {0}: {1}")]
+    InvalidNonce(Pubkey, String),
+    #[error("invalid transaction: {0}")]
+    InvalidTransaction(String),
+    #[error("invalid attestation: {0}")]
+    InvalidAttestation(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,110 @@
+//! LayerZero fee quotes for messages the program sends, so callers can fund
+//! the payer before submitting.
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{
+        hash::hash,
+        instruction::{AccountMeta, Instruction},
+    },
+    AnchorDeserialize, AnchorSerialize,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_client::{rpc_client::RpcClient, rpc_config::RpcSimulateTransactionConfig};
+use solana_sdk::transaction::Transaction;
+
+use crate::{
+    error::{Error, Result},
+    instruction::InitiateRecovery,
+    Endpoint, RecoveryAction, RecoveryMessage, Vault,
+};
+
+#[derive(AnchorSerialize)]
+struct QuoteParams {
+    dst_eid: u32,
+    to: Vec<u8>,
+    message: Vec<u8>,
+    options: Vec<u8>,
+    pay_in_lz_token: bool,
+}
+
+#[derive(AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MessagingFee {
+    pub native_fee: u64,
+    pub lz_token_fee: u64,
+}
+
+/// The payload `initiate_recovery` sends for the vault's next recovery.
+pub fn initiate_recovery_message(vault: &Pubkey, state: &Vault, new_owner: Pubkey) -> Vec<u8> {
+    RecoveryMessage {
+        vault_id: *vault,
+        new_owner,
+        recovery_id: state.recovery_id + 1,
+        action: RecoveryAction::Initiate,
+    }
+    .try_to_vec()
+    .expect("recovery message serializes")
+}
+
+/// The endpoint `quote` instruction, as the program invokes it.
+pub fn quote_instruction(
+    endpoint: &Endpoint,
+    dst_eid: u32,
+    peer: &Pubkey,
+    message: Vec<u8>,
+    options: Vec<u8>,
+) -> Instruction {
+    let mut data = hash(b"global:quote").to_bytes()[..8].to_vec();
+    QuoteParams {
+        dst_eid,
+        to: peer.to_bytes().to_vec(),
+        message,
+        options,
+        pay_in_lz_token: false,
+    }
+    .serialize(&mut data)
+    .expect("quote params serialize");
+    Instruction {
+        program_id: endpoint.program,
+        accounts: vec![AccountMeta::new_readonly(endpoint.settings, false)],
+        data,
+    }
+}
+
+/// Simulates the endpoint `quote` and decodes its return data. `payer` only
+/// needs to exist; nothing is signed or sent.
+pub fn quote(client: &RpcClient, payer: &Pubkey, instruction: Instruction) -> Result<MessagingFee> {
+    let tx = Transaction::new_with_payer(&[instruction], Some(payer));
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = client.simulate_transaction_with_config(&tx, config)?.value;
+    if let Some(err) = result.err {
+        return Err(Error::Simulation(err.to_string()));
+    }
+    let return_data = result.return_data.ok_or(Error::MissingReturnData)?;
+    let data = STANDARD
+        .decode(&return_data.data.0)
+        .map_err(|_| Error::MissingReturnData)?;
+    MessagingFee::try_from_slice(&data).map_err(|_| Error::MissingReturnData)
+}
+
+/// Messaging fee `args.payer` will be charged by `initiate_recovery`.
+pub fn quote_initiate_recovery(
+    client: &RpcClient,
+    endpoint: &Endpoint,
+    state: &Vault,
+    args: &InitiateRecovery,
+) -> Result<MessagingFee> {
+    let message = initiate_recovery_message(&args.vault, state, args.new_owner);
+    let instruction = quote_instruction(
+        endpoint,
+        args.dst_eid,
+        &args.peer,
+        message,
+        args.options.clone(),
+    );
+    quote(client, &args.payer, instruction)
+}
@@ -0,0 +1,74 @@
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use solana_client::rpc_client::RpcClient;
+
+use crate::{
+    error::{Error, Result},
+    pda, CrankReserve, PeerInfo, ProtocolConfig, Recovery, Vault, Withdrawal,
+};
+
+/// Deserializes an Anchor account, checking its discriminator.
+pub fn decode<T: AccountDeserialize>(
+    address: &Pubkey,
+    data: &[u8],
+    name: &'static str,
+) -> Result<T> {
+    T::try_deserialize(&mut &data[..]).map_err(|_| Error::InvalidAccount(*address, name))
+}
+
+fn account<T: AccountDeserialize>(
+    client: &RpcClient,
+    address: &Pubkey,
+    name: &'static str,
+) -> Result<T> {
+    let account = client
+        .get_account_with_commitment(address, client.commitment())?
+        .value
+        .ok_or(Error::AccountNotFound(*address))?;
+    if account.owner != reclaimx::ID {
+        return Err(Error::InvalidAccount(*address, name));
+    }
+    decode(address, &account.data, name)
+}
+
+pub fn config(client: &RpcClient) -> Result<ProtocolConfig> {
+    account(client, &pda::config(), "ProtocolConfig")
+}
+
+pub fn vault(client: &RpcClient, address: &Pubkey) -> Result<Vault> {
+    account(client, address, "Vault")
+}
+
+/// The vault created by `creator` with `vault_seed`.
+pub fn vault_of(client: &RpcClient, creator: &Pubkey, vault_seed: u64) -> Result<(Pubkey, Vault)> {
+    let address = pda::vault(creator, vault_seed);
+    Ok((address, vault(client, &address)?))
+}
+
+pub fn recovery(client: &RpcClient, vault: &Pubkey, recovery_id: u64) -> Result<Recovery> {
+    account(client, &pda::recovery(vault, recovery_id), "Recovery")
+}
+
+/// The vault's pending recovery, or `None` if no recovery is in progress.
+pub fn active_recovery(client: &RpcClient, vault: &Vault) -> Result<Option<(Pubkey, Recovery)>> {
+    match vault.active_recovery {
+        Some(address) => Ok(Some((address, account(client, &address, "Recovery")?))),
+        None => Ok(None),
+    }
+}
+
+pub fn withdrawal(client: &RpcClient, vault: &Pubkey, withdrawal_id: u64) -> Result<Withdrawal> {
+    account(client, &pda::withdrawal(vault, withdrawal_id), "Withdrawal")
+}
+
+pub fn peer_info(client: &RpcClient, vault: &Pubkey, eid: u32) -> Result<PeerInfo> {
+    account(client, &pda::peer(vault, eid), "PeerInfo")
+}
+
+/// The vault's crank reserve, or `None` if it has not been created.
+pub fn crank_reserve(client: &RpcClient, vault: &Pubkey) -> Result<Option<CrankReserve>> {
+    match account(client, &pda::crank_reserve(vault), "CrankReserve") {
+        Ok(reserve) => Ok(Some(reserve)),
+        Err(Error::AccountNotFound(_)) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,866 @@
+//! One builder per program instruction. `lz_receive` is omitted because the
+//! LayerZero executor submits it.
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        sysvar,
+    },
+    system_program, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{associated_token::get_associated_token_address, token::spl_token};
+use reclaimx::{accounts, config::ConfigParams, instruction as ix, Asset, PauseScope, Withdrawal};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+use crate::{pda, Endpoint};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Arguments to `initialize_vault`. `vault_seed` must differ between vaults
+/// created by the same owner.
+#[derive(Clone, Debug)]
+pub struct VaultConfig {
+    pub vault_seed: u64,
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timelock: u64,
+    pub inactivity_period: u64,
+    pub backup_wallet: Pubkey,
+    pub stake_amount: u64,
+    pub assets: Vec<Asset>,
+}
+
+/// Passes each asset's token account, which must already belong to the vault
+/// (`pda::vault(owner, config.vault_seed)`) and hold the asset's amount.
+pub fn initialize_vault(
+    owner: &Pubkey,
+    payer: &Pubkey,
+    endpoint: &Endpoint,
+    config: VaultConfig,
+) -> Instruction {
+    let token_accounts: Vec<AccountMeta> = config
+        .assets
+        .iter()
+        .map(|asset| AccountMeta::new_readonly(asset.token_account, false))
+        .collect();
+    let mut instruction = build(
+        accounts::InitializeVault {
+            vault: pda::vault(owner, config.vault_seed),
+            owner: *owner,
+            payer: *payer,
+            config: pda::config(),
+            endpoint: endpoint.settings,
+            endpoint_program: endpoint.program,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::InitializeVault {
+            vault_seed: config.vault_seed,
+            guardians: config.guardians,
+            threshold: config.threshold,
+            timelock: config.timelock,
+            inactivity_period: config.inactivity_period,
+            backup_wallet: config.backup_wallet,
+            stake_amount: config.stake_amount,
+            assets: config.assets,
+        },
+    );
+    instruction.accounts.extend(token_accounts);
+    instruction
+}
+
+/// Arguments to `initiate_recovery`. `peer` is the remote OApp the
+/// recovery message is sent to; `recovery` is the account the program
+/// creates, `pda::recovery(vault, vault.recovery_id + 1)`.
+#[derive(Clone, Debug)]
+pub struct InitiateRecovery {
+    pub vault: Pubkey,
+    pub recovery: Pubkey,
+    pub initiator: Pubkey,
+    pub payer: Pubkey,
+    pub peer: Pubkey,
+    pub new_owner: Pubkey,
+    pub dst_eid: u32,
+    pub options: Vec<u8>,
+}
+
+pub fn initiate_recovery(endpoint: &Endpoint, args: InitiateRecovery) -> Instruction {
+    build(
+        accounts::InitiateRecovery {
+            vault: args.vault,
+            initiator: args.initiator,
+            payer: args.payer,
+            recovery: args.recovery,
+            config: pda::config(),
+            endpoint: endpoint.settings,
+            endpoint_program: endpoint.program,
+            peer: args.peer,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::InitiateRecovery {
+            new_owner: args.new_owner,
+            dst_eid: args.dst_eid,
+            options: args.options,
+        },
+    )
+}
+
+/// The (vault token account, new owner token account) pairs a completed
+/// recovery moves `assets` through, in order. Destinations are the
+/// associated token accounts of `new_owner`, which must exist beforehand.
+pub fn recovery_asset_accounts(assets: &[Asset], new_owner: &Pubkey) -> Vec<(Pubkey, Pubkey)> {
+    assets
+        .iter()
+        .map(|asset| {
+            (
+                asset.token_account,
+                get_associated_token_address(new_owner, &asset.mint),
+            )
+        })
+        .collect()
+}
+
+/// Creates, if missing, the new owner's associated token account for each
+/// asset, paid for by `payer`. Goes ahead of an approval or execution that
+/// may complete the recovery.
+pub fn create_recovery_asset_accounts(
+    payer: &Pubkey,
+    assets: &[Asset],
+    new_owner: &Pubkey,
+) -> Vec<Instruction> {
+    assets
+        .iter()
+        .map(|asset| {
+            create_associated_token_account_idempotent(
+                payer,
+                new_owner,
+                &asset.mint,
+                &spl_token::ID,
+            )
+        })
+        .collect()
+}
+
+fn with_asset_accounts(
+    mut instruction: Instruction,
+    asset_accounts: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    for (from, to) in asset_accounts {
+        instruction.accounts.push(AccountMeta::new(*from, false));
+        instruction.accounts.push(AccountMeta::new(*to, false));
+    }
+    instruction
+}
+
+/// `asset_accounts` are only used if this approval completes the recovery;
+/// see [`recovery_asset_accounts`].
+pub fn approve_recovery(
+    vault: &Pubkey,
+    recovery: &Pubkey,
+    guardian: &Pubkey,
+    asset_accounts: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let instruction = build(
+        accounts::ApproveRecovery {
+            vault: *vault,
+            config: pda::config(),
+            recovery: *recovery,
+            guardian: *guardian,
+            token_program: spl_token::ID,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::ApproveRecovery {},
+    );
+    with_asset_accounts(instruction, asset_accounts)
+}
+
+/// Must follow an Ed25519 precompile instruction in the same transaction;
+/// see [`crate::attestation`].
+pub fn submit_approvals(vault: &Pubkey, recovery: &Pubkey, relayer: &Pubkey) -> Instruction {
+    build(
+        accounts::SubmitApprovals {
+            vault: *vault,
+            config: pda::config(),
+            recovery: *recovery,
+            relayer: *relayer,
+            instructions: sysvar::instructions::ID,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::SubmitApprovals {},
+    )
+}
+
+/// Must follow a Secp256k1 precompile instruction in the same transaction;
+/// `expiries` lists each signature's expiry in order.
+pub fn submit_evm_approvals(
+    vault: &Pubkey,
+    recovery: &Pubkey,
+    relayer: &Pubkey,
+    expiries: Vec<i64>,
+) -> Instruction {
+    build(
+        accounts::SubmitApprovals {
+            vault: *vault,
+            config: pda::config(),
+            recovery: *recovery,
+            relayer: *relayer,
+            instructions: sysvar::instructions::ID,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::SubmitEvmApprovals { expiries },
+    )
+}
+
+pub fn add_evm_guardian(vault: &Pubkey, owner: &Pubkey, evm_guardian: [u8; 20]) -> Instruction {
+    build(
+        accounts::AddEvmGuardian {
+            vault: *vault,
+            config: pda::config(),
+            owner: *owner,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::AddEvmGuardian { evm_guardian },
+    )
+}
+
+pub fn remove_evm_guardian(vault: &Pubkey, owner: &Pubkey, evm_guardian: [u8; 20]) -> Instruction {
+    build(
+        accounts::RemoveEvmGuardian {
+            vault: *vault,
+            config: pda::config(),
+            owner: *owner,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::RemoveEvmGuardian { evm_guardian },
+    )
+}
+
+/// `token_accounts` is the (vault, new owner) token account pair, required
+/// when the vault tracks assets.
+pub fn execute_recovery(
+    vault: &Pubkey,
+    recovery: &Pubkey,
+    cranker: &Pubkey,
+    crank_reserve: Option<Pubkey>,
+    asset_accounts: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let instruction = build(
+        accounts::ExecuteRecovery {
+            vault: *vault,
+            config: pda::config(),
+            recovery: *recovery,
+            cranker: *cranker,
+            crank_reserve,
+            token_program: spl_token::ID,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::ExecuteRecovery {},
+    );
+    with_asset_accounts(instruction, asset_accounts)
+}
+
+/// Accounts and arguments to `stake_guardian_oft`.
+#[derive(Clone, Debug)]
+pub struct StakeGuardianOft {
+    pub vault: Pubkey,
+    pub guardian: Pubkey,
+    pub guardian_token_account: Pubkey,
+    pub oft_program: Pubkey,
+    pub oft: Pubkey,
+    pub token_mint: Pubkey,
+    pub peer: Pubkey,
+    pub amount: u64,
+    pub dst_eid: u32,
+    pub options: Vec<u8>,
+}
+
+pub fn stake_guardian_oft(endpoint: &Endpoint, args: StakeGuardianOft) -> Instruction {
+    build(
+        accounts::StakeGuardianOFT {
+            vault: args.vault,
+            guardian: args.guardian,
+            guardian_token_account: args.guardian_token_account,
+            oft_program: args.oft_program,
+            oft: args.oft,
+            token_mint: args.token_mint,
+            config: pda::config(),
+            endpoint: endpoint.settings,
+            peer: args.peer,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::StakeGuardianOft {
+            amount: args.amount,
+            dst_eid: args.dst_eid,
+            options: args.options,
+        },
+    )
+}
+
+pub fn mark_malicious(
+    vault: &Pubkey,
+    recovery: &Pubkey,
+    signer: &Pubkey,
+    guardian: Pubkey,
+) -> Instruction {
+    build(
+        accounts::MarkMalicious {
+            vault: *vault,
+            config: pda::config(),
+            recovery: *recovery,
+            signer: *signer,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::MarkMalicious { guardian },
+    )
+}
+
+/// `recovery` is the account the program creates, paid for by the cranker:
+/// `pda::recovery(vault, vault.recovery_id + 1)`.
+pub fn check_inactivity(
+    vault: &Pubkey,
+    recovery: &Pubkey,
+    cranker: &Pubkey,
+    crank_reserve: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::CheckInactivity {
+            vault: *vault,
+            config: pda::config(),
+            cranker: *cranker,
+            recovery: *recovery,
+            crank_reserve,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::CheckInactivity {},
+    )
+}
+
+pub fn expire_recovery(
+    vault: &Pubkey,
+    recovery: &Pubkey,
+    cranker: &Pubkey,
+    crank_reserve: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::ExpireRecovery {
+            vault: *vault,
+            config: pda::config(),
+            recovery: *recovery,
+            cranker: *cranker,
+            crank_reserve,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::ExpireRecovery {},
+    )
+}
+
+pub fn set_recovery_deadlines(
+    vault: &Pubkey,
+    owner: &Pubkey,
+    recovery_expiry: u64,
+    approval_window: u64,
+) -> Instruction {
+    build(
+        accounts::SetRecoveryDeadlines {
+            vault: *vault,
+            config: pda::config(),
+            owner: *owner,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::SetRecoveryDeadlines {
+            recovery_expiry,
+            approval_window,
+        },
+    )
+}
+
+pub fn update_timing(
+    vault: &Pubkey,
+    owner: &Pubkey,
+    timelock: u64,
+    inactivity_period: u64,
+) -> Instruction {
+    build(
+        update_timing_accounts(vault, owner),
+        ix::UpdateTiming {
+            timelock,
+            inactivity_period,
+        },
+    )
+}
+
+pub fn transfer_ownership(vault: &Pubkey, owner: &Pubkey, new_owner: Pubkey) -> Instruction {
+    build(
+        transfer_ownership_accounts(vault, owner),
+        ix::TransferOwnership { new_owner },
+    )
+}
+
+pub fn accept_ownership(vault: &Pubkey, new_owner: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptOwnership {
+            vault: *vault,
+            config: pda::config(),
+            new_owner: *new_owner,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::AcceptOwnership {},
+    )
+}
+
+pub fn cancel_ownership_transfer(vault: &Pubkey, owner: &Pubkey) -> Instruction {
+    build(
+        transfer_ownership_accounts(vault, owner),
+        ix::CancelOwnershipTransfer {},
+    )
+}
+
+fn transfer_ownership_accounts(vault: &Pubkey, owner: &Pubkey) -> accounts::TransferOwnership {
+    accounts::TransferOwnership {
+        vault: *vault,
+        config: pda::config(),
+        owner: *owner,
+        event_authority: pda::event_authority(),
+        program: reclaimx::ID,
+    }
+}
+
+pub fn apply_timing(vault: &Pubkey, cranker: &Pubkey) -> Instruction {
+    build(
+        accounts::ApplyTiming {
+            vault: *vault,
+            config: pda::config(),
+            cranker: *cranker,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::ApplyTiming {},
+    )
+}
+
+pub fn cancel_timing_update(vault: &Pubkey, owner: &Pubkey) -> Instruction {
+    build(
+        update_timing_accounts(vault, owner),
+        ix::CancelTimingUpdate {},
+    )
+}
+
+fn update_timing_accounts(vault: &Pubkey, owner: &Pubkey) -> accounts::UpdateTiming {
+    accounts::UpdateTiming {
+        vault: *vault,
+        config: pda::config(),
+        owner: *owner,
+        event_authority: pda::event_authority(),
+        program: reclaimx::ID,
+    }
+}
+
+pub fn initialize_crank_reserve(vault: &Pubkey, owner: &Pubkey, bounty: u64) -> Instruction {
+    build(
+        accounts::InitializeCrankReserve {
+            vault: *vault,
+            config: pda::config(),
+            owner: *owner,
+            crank_reserve: pda::crank_reserve(vault),
+            system_program: system_program::ID,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::InitializeCrankReserve { bounty },
+    )
+}
+
+pub fn fund_crank_reserve(vault: &Pubkey, funder: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::FundCrankReserve {
+            vault: *vault,
+            config: pda::config(),
+            crank_reserve: pda::crank_reserve(vault),
+            funder: *funder,
+            system_program: system_program::ID,
+        },
+        ix::FundCrankReserve { amount },
+    )
+}
+
+fn manage_crank_reserve(vault: &Pubkey, owner: &Pubkey) -> accounts::ManageCrankReserve {
+    accounts::ManageCrankReserve {
+        vault: *vault,
+        config: pda::config(),
+        owner: *owner,
+        crank_reserve: pda::crank_reserve(vault),
+        event_authority: pda::event_authority(),
+        program: reclaimx::ID,
+    }
+}
+
+pub fn set_crank_bounty(vault: &Pubkey, owner: &Pubkey, bounty: u64) -> Instruction {
+    build(
+        manage_crank_reserve(vault, owner),
+        ix::SetCrankBounty { bounty },
+    )
+}
+
+pub fn withdraw_crank_reserve(vault: &Pubkey, owner: &Pubkey, amount: u64) -> Instruction {
+    build(
+        manage_crank_reserve(vault, owner),
+        ix::WithdrawCrankReserve { amount },
+    )
+}
+
+pub fn update_last_active(vault: &Pubkey, owner: &Pubkey) -> Instruction {
+    build(
+        accounts::UpdateLastActive {
+            vault: *vault,
+            config: pda::config(),
+            owner: *owner,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::UpdateLastActive {},
+    )
+}
+
+/// Check-in signed by the owner or a heartbeat key.
+pub fn proof_of_life(vault: &Pubkey, signer: &Pubkey) -> Instruction {
+    build(
+        accounts::ProofOfLife {
+            vault: *vault,
+            config: pda::config(),
+            signer: *signer,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::ProofOfLife {},
+    )
+}
+
+pub fn add_heartbeat_key(vault: &Pubkey, owner: &Pubkey, heartbeat_key: Pubkey) -> Instruction {
+    build(
+        accounts::AddHeartbeatKey {
+            vault: *vault,
+            config: pda::config(),
+            owner: *owner,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::AddHeartbeatKey { heartbeat_key },
+    )
+}
+
+pub fn remove_heartbeat_key(vault: &Pubkey, owner: &Pubkey, heartbeat_key: Pubkey) -> Instruction {
+    build(
+        accounts::RemoveHeartbeatKey {
+            vault: *vault,
+            config: pda::config(),
+            owner: *owner,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::RemoveHeartbeatKey { heartbeat_key },
+    )
+}
+
+pub fn cancel_inactivity_recovery(
+    vault: &Pubkey,
+    recovery: &Pubkey,
+    signer: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CancelRecovery {
+            vault: *vault,
+            config: pda::config(),
+            recovery: *recovery,
+            signer: *signer,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::CancelInactivityRecovery {},
+    )
+}
+
+/// Cancel of a recovery not started by inactivity: outright by the owner if
+/// they started it, otherwise as a vote by a guardian.
+pub fn cancel_recovery(vault: &Pubkey, recovery: &Pubkey, signer: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelRecovery {
+            vault: *vault,
+            config: pda::config(),
+            recovery: *recovery,
+            signer: *signer,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::CancelRecovery {},
+    )
+}
+
+/// Withdrawal by the owner; `guardian` co-signs amounts over the spending limit.
+pub fn withdraw_asset(
+    vault: &Pubkey,
+    owner: &Pubkey,
+    guardian: Option<Pubkey>,
+    vault_token_account: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::WithdrawAsset {
+            vault: *vault,
+            config: pda::config(),
+            owner: *owner,
+            guardian,
+            vault_token_account: *vault_token_account,
+            destination: *destination,
+            token_program: spl_token::ID,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::WithdrawAsset { amount },
+    )
+}
+
+fn set_spending_limit_accounts(
+    vault: &Pubkey,
+    owner: &Pubkey,
+    guardian: Option<Pubkey>,
+) -> accounts::SetSpendingLimit {
+    accounts::SetSpendingLimit {
+        vault: *vault,
+        config: pda::config(),
+        owner: *owner,
+        guardian,
+        event_authority: pda::event_authority(),
+        program: reclaimx::ID,
+    }
+}
+
+/// `guardian` must co-sign when the new limit is looser than the current one.
+pub fn set_spending_limit(
+    vault: &Pubkey,
+    owner: &Pubkey,
+    guardian: Option<Pubkey>,
+    mint: Pubkey,
+    max_amount: u64,
+    period: u64,
+    queue_above: Option<u64>,
+) -> Instruction {
+    build(
+        set_spending_limit_accounts(vault, owner, guardian),
+        ix::SetSpendingLimit {
+            mint,
+            max_amount,
+            period,
+            queue_above,
+        },
+    )
+}
+
+pub fn remove_spending_limit(
+    vault: &Pubkey,
+    owner: &Pubkey,
+    guardian: &Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    build(
+        set_spending_limit_accounts(vault, owner, Some(*guardian)),
+        ix::RemoveSpendingLimit { mint },
+    )
+}
+
+/// Queues a withdrawal as the vault's next `withdrawal_id` (its current one plus one).
+pub fn queue_withdrawal(
+    vault: &Pubkey,
+    withdrawal_id: u64,
+    owner: &Pubkey,
+    guardian: Option<Pubkey>,
+    vault_token_account: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::QueueWithdrawal {
+            vault: *vault,
+            config: pda::config(),
+            owner: *owner,
+            guardian,
+            withdrawal: pda::withdrawal(vault, withdrawal_id),
+            vault_token_account: *vault_token_account,
+            destination: *destination,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::QueueWithdrawal { amount },
+    )
+}
+
+pub fn execute_withdrawal(
+    vault: &Pubkey,
+    withdrawal: &Withdrawal,
+    cranker: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ExecuteWithdrawal {
+            vault: *vault,
+            config: pda::config(),
+            withdrawal: pda::withdrawal(vault, withdrawal.withdrawal_id),
+            cranker: *cranker,
+            vault_token_account: withdrawal.vault_token_account,
+            destination: withdrawal.destination,
+            token_program: spl_token::ID,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::ExecuteWithdrawal {},
+    )
+}
+
+fn resolve_withdrawal(
+    vault: &Pubkey,
+    withdrawal: &Pubkey,
+    signer: &Pubkey,
+) -> accounts::ResolveWithdrawal {
+    accounts::ResolveWithdrawal {
+        vault: *vault,
+        config: pda::config(),
+        withdrawal: *withdrawal,
+        signer: *signer,
+        event_authority: pda::event_authority(),
+        program: reclaimx::ID,
+    }
+}
+
+pub fn veto_withdrawal(vault: &Pubkey, withdrawal: &Pubkey, guardian: &Pubkey) -> Instruction {
+    build(
+        resolve_withdrawal(vault, withdrawal, guardian),
+        ix::VetoWithdrawal {},
+    )
+}
+
+pub fn cancel_withdrawal(vault: &Pubkey, withdrawal: &Pubkey, owner: &Pubkey) -> Instruction {
+    build(
+        resolve_withdrawal(vault, withdrawal, owner),
+        ix::CancelWithdrawal {},
+    )
+}
+
+fn freeze_vault_accounts(vault: &Pubkey, signer: &Pubkey) -> accounts::FreezeVault {
+    accounts::FreezeVault {
+        vault: *vault,
+        config: pda::config(),
+        signer: *signer,
+        event_authority: pda::event_authority(),
+        program: reclaimx::ID,
+    }
+}
+
+/// Freeze, or a guardian's vote to freeze. Signed by the owner or a guardian.
+pub fn freeze_vault(vault: &Pubkey, signer: &Pubkey) -> Instruction {
+    build(freeze_vault_accounts(vault, signer), ix::FreezeVault {})
+}
+
+pub fn unfreeze_vault(vault: &Pubkey, signer: &Pubkey) -> Instruction {
+    build(freeze_vault_accounts(vault, signer), ix::UnfreezeVault {})
+}
+
+pub fn set_peer(
+    vault: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    dst_eid: u32,
+    peer: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::SetPeer {
+            vault: *vault,
+            config: pda::config(),
+            owner: *owner,
+            peer_info: pda::peer(vault, dst_eid),
+            payer: *payer,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::SetPeer { dst_eid, peer },
+    )
+}
+
+/// Signed by the program's upgrade authority.
+pub fn initialize_config(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    admin: Pubkey,
+    params: ConfigParams,
+) -> Instruction {
+    build(
+        accounts::InitializeConfig {
+            config: pda::config(),
+            payer: *payer,
+            authority: *authority,
+            program_data: pda::program_data(),
+            system_program: system_program::ID,
+            event_authority: pda::event_authority(),
+            program: reclaimx::ID,
+        },
+        ix::InitializeConfig { admin, params },
+    )
+}
+
+fn manage_config_accounts(signer: &Pubkey) -> accounts::ManageConfig {
+    accounts::ManageConfig {
+        config: pda::config(),
+        signer: *signer,
+        event_authority: pda::event_authority(),
+        program: reclaimx::ID,
+    }
+}
+
+/// Replaces every setting but the admin and paused scopes. Signed by the admin.
+pub fn update_config(admin: &Pubkey, params: ConfigParams) -> Instruction {
+    build(manage_config_accounts(admin), ix::UpdateConfig { params })
+}
+
+/// Pauses or resumes `scope`. Signed by the admin or the security council.
+pub fn set_paused(signer: &Pubkey, scope: PauseScope, paused: bool) -> Instruction {
+    build(
+        manage_config_accounts(signer),
+        ix::SetPaused { scope, paused },
+    )
+}
+
+/// `None` withdraws a pending proposal.
+pub fn propose_admin(admin: &Pubkey, new_admin: Option<Pubkey>) -> Instruction {
+    build(
+        manage_config_accounts(admin),
+        ix::ProposeAdmin { new_admin },
+    )
+}
+
+/// Signed by the proposed admin.
+pub fn accept_admin(new_admin: &Pubkey) -> Instruction {
+    build(manage_config_accounts(new_admin), ix::AcceptAdmin {})
+}
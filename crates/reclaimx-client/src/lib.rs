@@ -0,0 +1,46 @@
+//! Client-side helpers for the ReclaimX program: PDA derivation, instruction
+//! builders, account fetching, LayerZero fee quotes and offline signing.
+
+pub mod attestation;
+pub mod error;
+pub mod fees;
+pub mod fetch;
+pub mod instruction;
+pub mod offline;
+pub mod pda;
+
+pub use error::{Error, Result};
+pub use reclaimx::{
+    config::ConfigParams, Asset, AssetType, CrankReserve, PauseScope, PeerInfo, ProtocolConfig,
+    Recovery, RecoveryAction, RecoveryMessage, RecoveryStatus, SpendingLimit, Vault, Withdrawal,
+    WithdrawalStatus, ID as PROGRAM_ID,
+};
+
+use anchor_lang::prelude::Pubkey;
+
+/// The LayerZero endpoint program and its settings account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Endpoint {
+    pub program: Pubkey,
+    pub settings: Pubkey,
+}
+
+impl Endpoint {
+    pub fn new(program: Pubkey) -> Self {
+        Self {
+            program,
+            settings: pda::endpoint_settings(&program),
+        }
+    }
+
+    /// The endpoint the protocol config currently points at.
+    pub fn from_config(config: &ProtocolConfig) -> Self {
+        Self::new(config.endpoint_program)
+    }
+}
+
+impl Default for Endpoint {
+    fn default() -> Self {
+        Self::new(reclaimx::LAYERZERO_ENDPOINT)
+    }
+}
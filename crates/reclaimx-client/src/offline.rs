@@ -0,0 +1,121 @@
+//! Offline signing. Transactions are built against a durable nonce so they
+//! stay valid while they travel to air-gapped signers, partially signed by
+//! each party, then combined and broadcast from an online machine.
+
+use anchor_lang::prelude::Pubkey;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_client::{nonce_utils, rpc_client::RpcClient};
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    signature::{Signature, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+
+use crate::error::{Error, Result};
+
+/// A durable nonce account and the key allowed to advance it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DurableNonce {
+    pub account: Pubkey,
+    pub authority: Pubkey,
+    pub blockhash: Hash,
+}
+
+impl DurableNonce {
+    /// Reads the nonce account's current blockhash and authority.
+    pub fn fetch(client: &RpcClient, account: &Pubkey) -> Result<Self> {
+        let data = nonce_utils::get_account_with_commitment(client, account, client.commitment())
+            .and_then(|account| nonce_utils::data_from_account(&account))
+            .map_err(|err| Error::InvalidNonce(*account, err.to_string()))?;
+        Ok(Self {
+            account: *account,
+            authority: data.authority,
+            blockhash: data.blockhash(),
+        })
+    }
+}
+
+/// Unsigned transaction whose first instruction advances `nonce`, so it does
+/// not expire with the recent blockhash.
+pub fn unsigned_transaction(
+    instructions: &[Instruction],
+    fee_payer: &Pubkey,
+    nonce: &DurableNonce,
+) -> Transaction {
+    let mut all = vec![system_instruction::advance_nonce_account(
+        &nonce.account,
+        &nonce.authority,
+    )];
+    all.extend_from_slice(instructions);
+    let mut tx = Transaction::new_with_payer(&all, Some(fee_payer));
+    tx.message.recent_blockhash = nonce.blockhash;
+    tx
+}
+
+/// Adds `signers`' signatures without requiring the others. Needs no network.
+pub fn sign(tx: &mut Transaction, signers: &[&dyn Signer]) -> Result<()> {
+    let blockhash = tx.message.recent_blockhash;
+    tx.try_partial_sign(signers, blockhash)
+        .map_err(|err| Error::InvalidTransaction(err.to_string()))
+}
+
+/// Merges signatures from copies of the same transaction signed by different
+/// parties. Every copy must carry the identical message and every signature
+/// must verify.
+pub fn combine(parts: &[Transaction]) -> Result<Transaction> {
+    let (first, rest) = parts
+        .split_first()
+        .ok_or_else(|| Error::InvalidTransaction("nothing to combine".to_string()))?;
+    let mut combined = first.clone();
+    for part in rest {
+        if part.message != combined.message {
+            return Err(Error::InvalidTransaction(
+                "transactions carry different messages".to_string(),
+            ));
+        }
+        for (slot, signature) in combined.signatures.iter_mut().zip(&part.signatures) {
+            if *signature != Signature::default() {
+                *slot = *signature;
+            }
+        }
+    }
+
+    let message = combined.message_data();
+    for (signature, key) in combined
+        .signatures
+        .iter()
+        .zip(&combined.message.account_keys)
+    {
+        if *signature != Signature::default() && !signature.verify(key.as_ref(), &message) {
+            return Err(Error::InvalidTransaction(format!(
+                "bad signature from {}",
+                key
+            )));
+        }
+    }
+    Ok(combined)
+}
+
+/// Required signers that have not signed yet.
+pub fn missing_signers(tx: &Transaction) -> Vec<Pubkey> {
+    tx.signatures
+        .iter()
+        .zip(&tx.message.account_keys)
+        .filter(|(signature, _)| **signature == Signature::default())
+        .map(|(_, key)| *key)
+        .collect()
+}
+
+pub fn encode(tx: &Transaction) -> Result<String> {
+    let bytes = bincode::serialize(tx).map_err(|err| Error::InvalidTransaction(err.to_string()))?;
+    Ok(STANDARD.encode(bytes))
+}
+
+pub fn decode(data: &str) -> Result<Transaction> {
+    let bytes = STANDARD
+        .decode(data.trim())
+        .map_err(|err| Error::InvalidTransaction(err.to_string()))?;
+    bincode::deserialize(&bytes).map_err(|err| Error::InvalidTransaction(err.to_string()))
+}
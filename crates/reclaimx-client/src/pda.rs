@@ -0,0 +1,66 @@
+use anchor_lang::{prelude::Pubkey, solana_program::bpf_loader_upgradeable};
+
+// Seed of the endpoint settings account, owned by the endpoint program
+pub const ENDPOINT_SEED: &[u8] = b"endpoint";
+
+/// The protocol's singleton config account.
+pub fn config() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &reclaimx::ID).0
+}
+
+/// The program's ProgramData account, which records its upgrade authority.
+pub fn program_data() -> Pubkey {
+    Pubkey::find_program_address(&[reclaimx::ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+/// The vault `creator` created with `vault_seed`; it keeps this address
+/// after ownership changes.
+pub fn vault(creator: &Pubkey, vault_seed: u64) -> Pubkey {
+    vault_with_bump(creator, vault_seed).0
+}
+
+pub fn vault_with_bump(creator: &Pubkey, vault_seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"vault", creator.as_ref(), &vault_seed.to_le_bytes()],
+        &reclaimx::ID,
+    )
+}
+
+pub fn peer(vault: &Pubkey, eid: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"peer", vault.as_ref(), &eid.to_le_bytes()],
+        &reclaimx::ID,
+    )
+    .0
+}
+
+/// The account of the vault's `recovery_id`th recovery; ids start at 1.
+pub fn recovery(vault: &Pubkey, recovery_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"recovery", vault.as_ref(), &recovery_id.to_le_bytes()],
+        &reclaimx::ID,
+    )
+    .0
+}
+
+/// The account of the vault's `withdrawal_id`th queued withdrawal; ids start at 1.
+pub fn withdrawal(vault: &Pubkey, withdrawal_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"withdrawal", vault.as_ref(), &withdrawal_id.to_le_bytes()],
+        &reclaimx::ID,
+    )
+    .0
+}
+
+pub fn crank_reserve(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"crank_reserve", vault.as_ref()], &reclaimx::ID).0
+}
+
+/// Signer PDA the program uses for `emit_cpi!` events.
+pub fn event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &reclaimx::ID).0
+}
+
+pub fn endpoint_settings(endpoint_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[ENDPOINT_SEED], endpoint_program).0
+}
@@ -0,0 +1,92 @@
+use reclaimx::attestation::{ed25519_entries, secp256k1_entries};
+use reclaimx_client::attestation::{
+    ed25519_instruction, eip712_preimage, secp256k1_instruction, ApprovalMessage,
+    EvmSignedApproval, SignedApproval,
+};
+use solana_sdk::{
+    keccak,
+    pubkey::Pubkey,
+    secp256k1_instruction::construct_eth_pubkey,
+    signature::{Keypair, Signature},
+};
+
+#[test]
+fn program_parses_every_signature_the_client_packs() {
+    let guardians = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let message = ApprovalMessage {
+        vault: Pubkey::new_unique(),
+        recovery_id: 3,
+        new_owner: Pubkey::new_unique(),
+        expiry: 1_700_000_000,
+    };
+    let approvals: Vec<SignedApproval> = guardians
+        .iter()
+        .map(|guardian| SignedApproval::sign(guardian, message.clone()))
+        .collect();
+
+    let ix = ed25519_instruction(&approvals);
+    let entries = ed25519_entries(&ix.data).unwrap();
+
+    assert_eq!(entries.len(), approvals.len());
+    for ((signer, signed), approval) in entries.iter().zip(&approvals) {
+        assert_eq!(*signer, approval.guardian);
+        assert_eq!(ApprovalMessage::from_bytes(signed), Some(message.clone()));
+        assert!(Signature::from(approval.signature).verify(signer.as_ref(), signed));
+    }
+}
+
+#[test]
+fn signed_approvals_round_trip_through_base64() {
+    let approval = SignedApproval::sign(
+        &Keypair::new(),
+        ApprovalMessage {
+            vault: Pubkey::new_unique(),
+            recovery_id: 1,
+            new_owner: Pubkey::new_unique(),
+            expiry: 0,
+        },
+    );
+    assert_eq!(
+        SignedApproval::decode(&approval.encode()).unwrap(),
+        approval
+    );
+}
+
+#[test]
+fn program_parses_evm_signatures_the_client_packs() {
+    let secret = libsecp256k1::SecretKey::parse(&[9u8; 32]).unwrap();
+    let address = construct_eth_pubkey(&libsecp256k1::PublicKey::from_secret_key(&secret));
+    let message = ApprovalMessage {
+        vault: Pubkey::new_unique(),
+        recovery_id: 2,
+        new_owner: Pubkey::new_unique(),
+        expiry: 1_700_000_000,
+    };
+    let preimage = eip712_preimage(&message).unwrap();
+
+    // What an EVM wallet returns for eth_signTypedData_v4
+    let digest = libsecp256k1::Message::parse(&keccak::hash(&preimage).0);
+    let (signature, recovery) = libsecp256k1::sign(&digest, &secret);
+    let mut rsv = [0u8; 65];
+    rsv[..64].copy_from_slice(&signature.serialize());
+    rsv[64] = recovery.serialize() + 27;
+
+    let approval = EvmSignedApproval {
+        address,
+        signature: rsv,
+        expiry: message.expiry,
+    };
+    let ix = secp256k1_instruction(
+        &message.vault,
+        message.recovery_id,
+        &message.new_owner,
+        &[approval],
+        3,
+    )
+    .unwrap();
+
+    let entries = secp256k1_entries(&ix.data, 3).unwrap();
+    assert_eq!(entries, vec![(address, preimage.to_vec())]);
+    // Entries pointing at another instruction are refused
+    assert!(secp256k1_entries(&ix.data, 0).is_err());
+}
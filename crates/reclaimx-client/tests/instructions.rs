@@ -0,0 +1,165 @@
+use anchor_lang::{prelude::Pubkey, AnchorDeserialize, Discriminator};
+use reclaimx_client::{fees, instruction, pda, Endpoint, RecoveryMessage};
+
+#[test]
+fn initialize_vault_derives_vault_from_owner_and_seed() {
+    let owner = Pubkey::new_unique();
+    let asset = reclaimx::Asset {
+        asset_type: reclaimx::AssetType::Token,
+        amount: 10,
+        token_account: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+    };
+    let ix = instruction::initialize_vault(
+        &owner,
+        &owner,
+        &Endpoint::default(),
+        instruction::VaultConfig {
+            vault_seed: 7,
+            guardians: vec![Pubkey::new_unique()],
+            threshold: 1,
+            timelock: 3_600,
+            inactivity_period: 86_400,
+            backup_wallet: Pubkey::new_unique(),
+            stake_amount: 0,
+            assets: vec![asset.clone()],
+        },
+    );
+
+    assert_eq!(ix.program_id, reclaimx::ID);
+    assert_eq!(ix.accounts[0].pubkey, pda::vault(&owner, 7));
+    // The asset's token account follows, for the program to check
+    let last = ix.accounts.last().unwrap();
+    assert_eq!(last.pubkey, asset.token_account);
+    assert!(!last.is_writable);
+    assert_ne!(pda::vault(&owner, 7), pda::vault(&owner, 0));
+    assert!(ix.accounts[0].is_writable);
+    assert_eq!(
+        &ix.data[..8],
+        &reclaimx::instruction::InitializeVault::DISCRIMINATOR
+    );
+}
+
+#[test]
+fn layerzero_instructions_read_the_protocol_config() {
+    let owner = Pubkey::new_unique();
+    let ix = instruction::initiate_recovery(
+        &Endpoint::default(),
+        instruction::InitiateRecovery {
+            vault: pda::vault(&owner, 0),
+            recovery: pda::recovery(&pda::vault(&owner, 0), 1),
+            initiator: owner,
+            payer: owner,
+            peer: Pubkey::new_unique(),
+            new_owner: Pubkey::new_unique(),
+            dst_eid: reclaimx::ETHEREUM_EID,
+            options: vec![],
+        },
+    );
+    let config = ix
+        .accounts
+        .iter()
+        .find(|meta| meta.pubkey == pda::config())
+        .unwrap();
+    assert!(!config.is_writable);
+
+    // Admin instructions write to the same singleton
+    let ix = instruction::set_paused(&owner, reclaimx::PauseScope::All, true);
+    assert_eq!(ix.accounts[0].pubkey, pda::config());
+    assert!(ix.accounts[0].is_writable);
+}
+
+#[test]
+fn set_peer_derives_peer_from_vault_and_eid() {
+    let owner = Pubkey::new_unique();
+    let vault = pda::vault(&owner, 0);
+    let ix = instruction::set_peer(&vault, &owner, &owner, reclaimx::ETHEREUM_EID, [7; 32]);
+
+    let peer_info = pda::peer(&vault, reclaimx::ETHEREUM_EID);
+    assert!(ix.accounts.iter().any(|meta| meta.pubkey == peer_info));
+    assert_ne!(peer_info, pda::peer(&vault, reclaimx::SEPOLIA_EID));
+}
+
+#[test]
+fn execute_recovery_leaves_optional_accounts_as_program_id() {
+    let vault = Pubkey::new_unique();
+    let ix = instruction::execute_recovery(
+        &vault,
+        &pda::recovery(&vault, 1),
+        &Pubkey::new_unique(),
+        None,
+        &[],
+    );
+
+    // Anchor encodes absent optional accounts as the program id
+    let placeholders = ix
+        .accounts
+        .iter()
+        .filter(|meta| meta.pubkey == reclaimx::ID)
+        .count();
+    assert!(placeholders >= 2);
+}
+
+#[test]
+fn recovery_asset_accounts_pair_each_asset_with_new_owner_ata() {
+    let assets: Vec<reclaimx::Asset> = (0..2)
+        .map(|_| reclaimx::Asset {
+            asset_type: reclaimx::AssetType::Token,
+            amount: 100,
+            token_account: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+        })
+        .collect();
+    let new_owner = Pubkey::new_unique();
+    let pairs = instruction::recovery_asset_accounts(&assets, &new_owner);
+    assert_eq!(pairs[0].0, assets[0].token_account);
+    assert_ne!(pairs[0].1, pairs[1].1);
+
+    let ix = instruction::execute_recovery(
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        None,
+        &pairs,
+    );
+    let appended: Vec<Pubkey> = ix.accounts[ix.accounts.len() - 2 * pairs.len()..]
+        .iter()
+        .map(|meta| meta.pubkey)
+        .collect();
+    let expected: Vec<Pubkey> = pairs.iter().flat_map(|(from, to)| [*from, *to]).collect();
+    assert_eq!(appended, expected);
+
+    // The destinations are the accounts created ahead of the recovery
+    let payer = Pubkey::new_unique();
+    let creates = instruction::create_recovery_asset_accounts(&payer, &assets, &new_owner);
+    let created: Vec<Pubkey> = creates.iter().map(|ix| ix.accounts[1].pubkey).collect();
+    let destinations: Vec<Pubkey> = pairs.iter().map(|(_, to)| *to).collect();
+    assert_eq!(created, destinations);
+}
+
+#[test]
+fn recovery_message_targets_next_recovery_id() {
+    let owner = Pubkey::new_unique();
+    let vault = pda::vault(&owner, 0);
+    let mut state = reclaimx::Vault {
+        owner,
+        recovery_id: 4,
+        creator: owner,
+        ..reclaimx::Vault::default()
+    };
+    let new_owner = Pubkey::new_unique();
+
+    let payload = fees::initiate_recovery_message(&vault, &state, new_owner);
+    let message = RecoveryMessage::try_from_slice(&payload).unwrap();
+    assert_eq!(message.recovery_id, 5);
+    assert_eq!(message.new_owner, new_owner);
+
+    state.recovery_id = 5;
+    let payload = fees::initiate_recovery_message(&vault, &state, new_owner);
+    assert_eq!(
+        RecoveryMessage::try_from_slice(&payload)
+            .unwrap()
+            .recovery_id,
+        6
+    );
+}
@@ -0,0 +1,66 @@
+use reclaimx_client::{
+    instruction,
+    offline::{self, DurableNonce},
+    pda,
+};
+use solana_sdk::{
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+fn approval(
+    fee_payer: &Keypair,
+    guardian: &Keypair,
+    nonce: &DurableNonce,
+) -> solana_sdk::transaction::Transaction {
+    let vault = pda::vault(&Pubkey::new_unique(), 0);
+    let ix = instruction::approve_recovery(
+        &vault,
+        &pda::recovery(&vault, 1),
+        &guardian.pubkey(),
+        &[(Pubkey::new_unique(), Pubkey::new_unique())],
+    );
+    offline::unsigned_transaction(&[ix], &fee_payer.pubkey(), nonce)
+}
+
+#[test]
+fn guardian_signs_offline_and_coordinator_combines() {
+    let coordinator = Keypair::new();
+    let guardian = Keypair::new();
+    let nonce = DurableNonce {
+        account: Pubkey::new_unique(),
+        authority: coordinator.pubkey(),
+        blockhash: Hash::new_unique(),
+    };
+    let unsigned = approval(&coordinator, &guardian, &nonce);
+    assert_eq!(unsigned.message.recent_blockhash, nonce.blockhash);
+
+    // The unsigned transaction travels to the air-gapped guardian and back
+    let mut guardian_copy = offline::decode(&offline::encode(&unsigned).unwrap()).unwrap();
+    offline::sign(&mut guardian_copy, &[&guardian]).unwrap();
+    let mut coordinator_copy = unsigned.clone();
+    offline::sign(&mut coordinator_copy, &[&coordinator]).unwrap();
+    assert_eq!(
+        offline::missing_signers(&guardian_copy),
+        vec![coordinator.pubkey()]
+    );
+
+    let combined = offline::combine(&[coordinator_copy, guardian_copy]).unwrap();
+    assert!(offline::missing_signers(&combined).is_empty());
+    assert!(combined.verify().is_ok());
+}
+
+#[test]
+fn combining_different_messages_fails() {
+    let coordinator = Keypair::new();
+    let nonce = DurableNonce {
+        account: Pubkey::new_unique(),
+        authority: coordinator.pubkey(),
+        blockhash: Hash::new_unique(),
+    };
+    let first = approval(&coordinator, &Keypair::new(), &nonce);
+    let second = approval(&coordinator, &Keypair::new(), &nonce);
+
+    assert!(offline::combine(&[first, second]).is_err());
+}
@@ -0,0 +1,258 @@
+use anchor_lang::{event::EVENT_IX_TAG_LE, prelude::Pubkey, AnchorDeserialize, Discriminator};
+use serde_json::{json, Value};
+
+use crate::{
+    error::{Error, Result},
+    source::RawTransaction,
+};
+
+macro_rules! vault_events {
+    ($($variant:ident($event:ident) => $kind:literal,)*) => {
+        /// A decoded `reclaimx` event.
+        #[derive(Clone)]
+        pub enum VaultEvent {
+            $($variant(reclaimx::$event),)*
+        }
+
+        impl VaultEvent {
+            /// Decodes the payload of an `emit_cpi!` self-invocation.
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                let data = data.strip_prefix(&EVENT_IX_TAG_LE[..])?;
+                if data.len() < 8 {
+                    return None;
+                }
+                let (discriminator, mut payload) = data.split_at(8);
+                $(
+                    if discriminator == reclaimx::$event::DISCRIMINATOR {
+                        return reclaimx::$event::deserialize(&mut payload)
+                            .ok()
+                            .map(VaultEvent::$variant);
+                    }
+                )*
+                None
+            }
+
+            pub fn kind(&self) -> &'static str {
+                match self {
+                    $(VaultEvent::$variant(_) => $kind,)*
+                }
+            }
+
+            pub fn vault_id(&self) -> Pubkey {
+                match self {
+                    $(VaultEvent::$variant(event) => event.vault_id,)*
+                }
+            }
+
+            pub fn recovery_id(&self) -> u64 {
+                match self {
+                    $(VaultEvent::$variant(event) => event.recovery_id,)*
+                }
+            }
+
+            pub fn actor(&self) -> Pubkey {
+                match self {
+                    $(VaultEvent::$variant(event) => event.actor,)*
+                }
+            }
+
+            pub fn timestamp(&self) -> i64 {
+                match self {
+                    $(VaultEvent::$variant(event) => event.timestamp,)*
+                }
+            }
+        }
+    };
+}
+
+vault_events! {
+    VaultInitialized(VaultInitializedEvent) => "vault_initialized",
+    RecoveryInitiated(RecoveryInitiatedEvent) => "recovery_initiated",
+    RecoveryApproved(RecoveryApprovedEvent) => "recovery_approved",
+    RecoveryCancelled(RecoveryCancelledEvent) => "recovery_cancelled",
+    RecoveryCancelVote(RecoveryCancelVoteEvent) => "recovery_cancel_vote",
+    RecoveryExecuted(RecoveryExecutedEvent) => "recovery_executed",
+    RecoveryExpired(RecoveryExpiredEvent) => "recovery_expired",
+    RecoveryDeadlinesSet(RecoveryDeadlinesSetEvent) => "recovery_deadlines_set",
+    TimingUpdateQueued(TimingUpdateQueuedEvent) => "timing_update_queued",
+    TimingUpdated(TimingUpdatedEvent) => "timing_updated",
+    TimingUpdateCancelled(TimingUpdateCancelledEvent) => "timing_update_cancelled",
+    OwnershipTransferProposed(OwnershipTransferProposedEvent) => "ownership_transfer_proposed",
+    OwnershipTransferred(OwnershipTransferredEvent) => "ownership_transferred",
+    OwnershipTransferCancelled(OwnershipTransferCancelledEvent) => "ownership_transfer_cancelled",
+    InactivityTriggered(InactivityTriggeredEvent) => "inactivity_triggered",
+    ActivityRecorded(ActivityRecordedEvent) => "activity_recorded",
+    HeartbeatKeyAdded(HeartbeatKeyAddedEvent) => "heartbeat_key_added",
+    HeartbeatKeyRemoved(HeartbeatKeyRemovedEvent) => "heartbeat_key_removed",
+    PeerSet(PeerSetEvent) => "peer_set",
+    GuardianStaked(GuardianStakedEvent) => "guardian_staked",
+    AssetTransferred(AssetTransferredEvent) => "asset_transferred",
+    MaliciousVote(MaliciousVoteEvent) => "malicious_vote",
+    GuardianSlashed(GuardianSlashedEvent) => "guardian_slashed",
+    CrankBountySet(CrankBountySetEvent) => "crank_bounty_set",
+    CrankRewarded(CrankRewardedEvent) => "crank_rewarded",
+    EvmGuardianAdded(EvmGuardianAddedEvent) => "evm_guardian_added",
+    EvmGuardianRemoved(EvmGuardianRemovedEvent) => "evm_guardian_removed",
+    FreezeVote(FreezeVoteEvent) => "freeze_vote",
+    VaultFrozen(VaultFrozenEvent) => "vault_frozen",
+    VaultUnfrozen(VaultUnfrozenEvent) => "vault_unfrozen",
+    AssetWithdrawn(AssetWithdrawnEvent) => "asset_withdrawn",
+    SpendingLimitSet(SpendingLimitSetEvent) => "spending_limit_set",
+    SpendingLimitRemoved(SpendingLimitRemovedEvent) => "spending_limit_removed",
+    WithdrawalQueued(WithdrawalQueuedEvent) => "withdrawal_queued",
+    WithdrawalExecuted(WithdrawalExecutedEvent) => "withdrawal_executed",
+    WithdrawalVetoed(WithdrawalVetoedEvent) => "withdrawal_vetoed",
+    WithdrawalCancelled(WithdrawalCancelledEvent) => "withdrawal_cancelled",
+}
+
+impl VaultEvent {
+    /// Event-specific fields beyond vault_id, recovery_id, actor and timestamp.
+    pub fn details(&self) -> Value {
+        match self {
+            VaultEvent::VaultInitialized(event) => json!({
+                "guardian_count": event.guardian_count,
+                "threshold": event.threshold,
+                "creator": event.creator.to_string(),
+                "vault_seed": event.vault_seed,
+            }),
+            VaultEvent::RecoveryInitiated(event) => json!({
+                "new_owner": event.new_owner.to_string(),
+                "src_eid": event.src_eid,
+            }),
+            VaultEvent::RecoveryApproved(event) => json!({ "approvals": event.approvals }),
+            VaultEvent::RecoveryExecuted(event) => json!({
+                "new_owner": event.new_owner.to_string(),
+            }),
+            VaultEvent::RecoveryDeadlinesSet(event) => json!({
+                "recovery_expiry": event.recovery_expiry,
+                "approval_window": event.approval_window,
+            }),
+            VaultEvent::TimingUpdateQueued(event) => json!({
+                "timelock": event.timelock,
+                "inactivity_period": event.inactivity_period,
+            }),
+            VaultEvent::TimingUpdated(event) => json!({
+                "timelock": event.timelock,
+                "inactivity_period": event.inactivity_period,
+            }),
+            VaultEvent::OwnershipTransferProposed(event) => json!({
+                "new_owner": event.new_owner.to_string(),
+            }),
+            VaultEvent::OwnershipTransferred(event) => json!({
+                "previous_owner": event.previous_owner.to_string(),
+            }),
+            VaultEvent::InactivityTriggered(event) => json!({
+                "backup_wallet": event.backup_wallet.to_string(),
+            }),
+            VaultEvent::HeartbeatKeyAdded(event) => json!({
+                "heartbeat_key": event.heartbeat_key.to_string(),
+            }),
+            VaultEvent::HeartbeatKeyRemoved(event) => json!({
+                "heartbeat_key": event.heartbeat_key.to_string(),
+            }),
+            VaultEvent::PeerSet(event) => json!({
+                "eid": event.eid,
+                "peer": bs58::encode(event.peer).into_string(),
+            }),
+            VaultEvent::GuardianStaked(event) => json!({
+                "amount": event.amount,
+                "dst_eid": event.dst_eid,
+            }),
+            VaultEvent::AssetTransferred(event) => json!({
+                "mint": event.mint.to_string(),
+                "amount": event.amount,
+                "to": event.to.to_string(),
+            }),
+            VaultEvent::MaliciousVote(event) => json!({
+                "guardian": event.guardian.to_string(),
+                "votes": event.votes,
+            }),
+            VaultEvent::GuardianSlashed(event) => json!({
+                "guardian": event.guardian.to_string(),
+                "amount": event.amount,
+            }),
+            VaultEvent::CrankBountySet(event) => json!({ "bounty": event.bounty }),
+            VaultEvent::CrankRewarded(event) => json!({ "amount": event.amount }),
+            VaultEvent::EvmGuardianAdded(event) => json!({
+                "evm_guardian": evm_address(&event.evm_guardian),
+            }),
+            VaultEvent::EvmGuardianRemoved(event) => json!({
+                "evm_guardian": evm_address(&event.evm_guardian),
+            }),
+            VaultEvent::RecoveryCancelVote(event) => json!({ "votes": event.votes }),
+            VaultEvent::FreezeVote(event) => json!({
+                "freeze": event.freeze,
+                "votes": event.votes,
+            }),
+            VaultEvent::VaultFrozen(event) => json!({
+                "frozen_until": event.frozen_until,
+                "by_guardians": event.by_guardians,
+            }),
+            VaultEvent::AssetWithdrawn(event) => json!({
+                "mint": event.mint.to_string(),
+                "amount": event.amount,
+                "to": event.to.to_string(),
+                "co_signer": event.co_signer.map(|key| key.to_string()),
+            }),
+            VaultEvent::SpendingLimitSet(event) => json!({
+                "mint": event.mint.to_string(),
+                "max_amount": event.max_amount,
+                "period": event.period,
+                "queue_above": event.queue_above,
+            }),
+            VaultEvent::SpendingLimitRemoved(event) => json!({
+                "mint": event.mint.to_string(),
+            }),
+            VaultEvent::WithdrawalQueued(event) => json!({
+                "withdrawal_id": event.withdrawal_id,
+                "mint": event.mint.to_string(),
+                "amount": event.amount,
+                "to": event.to.to_string(),
+                "co_signer": event.co_signer.map(|key| key.to_string()),
+            }),
+            VaultEvent::WithdrawalExecuted(event) => json!({
+                "withdrawal_id": event.withdrawal_id,
+                "mint": event.mint.to_string(),
+                "amount": event.amount,
+                "to": event.to.to_string(),
+            }),
+            VaultEvent::WithdrawalVetoed(event) => json!({ "withdrawal_id": event.withdrawal_id }),
+            VaultEvent::WithdrawalCancelled(event) => json!({
+                "withdrawal_id": event.withdrawal_id,
+            }),
+            VaultEvent::RecoveryCancelled(_)
+            | VaultEvent::RecoveryExpired(_)
+            | VaultEvent::ActivityRecorded(_)
+            | VaultEvent::TimingUpdateCancelled(_)
+            | VaultEvent::OwnershipTransferCancelled(_)
+            | VaultEvent::VaultUnfrozen(_) => json!({}),
+        }
+    }
+}
+
+fn evm_address(address: &[u8; 20]) -> String {
+    let hex: String = address.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("0x{}", hex)
+}
+
+/// Events emitted by `program_id` in a transaction, in execution order. A
+/// transaction that failed, or whose status is unknown, yields none.
+pub fn transaction_events(tx: &RawTransaction, program_id: &Pubkey) -> Result<Vec<VaultEvent>> {
+    if tx.succeeded() != Some(true) {
+        return Ok(vec![]);
+    }
+    let account_keys = tx.account_keys()?;
+    let mut events = vec![];
+    for ix in tx.inner_instructions() {
+        if account_keys.get(ix.program_id_index) != Some(program_id) {
+            continue;
+        }
+        let data = bs58::decode(&ix.data)
+            .into_vec()
+            .map_err(|err| Error::InvalidData(err.to_string()))?;
+        if let Some(event) = VaultEvent::decode(&data) {
+            events.push(event);
+        }
+    }
+    Ok(events)
+}
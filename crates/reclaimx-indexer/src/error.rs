@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("rpc error: {0}")]
+    Rpc(String),
+    #[error("sqlite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("invalid pubkey: {0}")]
+    InvalidPubkey(String),
+    #[error("invalid instruction data: {0}")]
+    InvalidData(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
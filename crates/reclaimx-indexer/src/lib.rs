@@ -0,0 +1,65 @@
+//! Off-chain indexer that rebuilds per-vault ReclaimX history from the
+//! events the program emits via `emit_cpi!`.
+
+pub mod decode;
+pub mod error;
+pub mod source;
+pub mod store;
+
+use anchor_lang::prelude::Pubkey;
+
+pub use decode::{transaction_events, VaultEvent};
+pub use error::{Error, Result};
+pub use source::{FileSource, RawTransaction, RpcSource, TransactionSource};
+pub use store::{RecoveryOutcome, RecoverySummary, Store, TimelineEntry};
+
+pub struct Indexer<S> {
+    source: S,
+    store: Store,
+    program_id: Pubkey,
+}
+
+impl<S: TransactionSource> Indexer<S> {
+    pub fn new(source: S, store: Store) -> Self {
+        Self::with_program_id(source, store, reclaimx::ID)
+    }
+
+    pub fn with_program_id(source: S, store: Store, program_id: Pubkey) -> Self {
+        Self {
+            source,
+            store,
+            program_id,
+        }
+    }
+
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    /// Fetches every transaction since the last sync and stores its events.
+    /// Returns the number of events indexed. Stops before the first
+    /// transaction the node cannot fully return yet, so the next sync
+    /// retries it instead of skipping its events.
+    pub fn sync(&mut self) -> Result<usize> {
+        let cursor = self.store.cursor(&self.program_id)?;
+        let signatures = self
+            .source
+            .signatures(&self.program_id, cursor.as_deref())?;
+
+        let mut indexed = 0;
+        for signature in signatures {
+            let Some(tx) = self
+                .source
+                .transaction(&signature)?
+                .filter(|tx| tx.succeeded().is_some())
+            else {
+                break;
+            };
+            let events = transaction_events(&tx, &self.program_id)?;
+            self.store.insert_events(&signature, tx.slot, &events)?;
+            self.store.set_cursor(&self.program_id, &signature)?;
+            indexed += events.len();
+        }
+        Ok(indexed)
+    }
+}
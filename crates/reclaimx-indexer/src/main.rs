@@ -0,0 +1,88 @@
+use std::{path::PathBuf, str::FromStr, thread, time::Duration};
+
+use anchor_lang::prelude::Pubkey;
+use clap::{Parser, Subcommand};
+use reclaimx_indexer::{Error, FileSource, Indexer, Result, RpcSource, Store};
+
+#[derive(Parser)]
+#[command(
+    name = "reclaimx-indexer",
+    about = "Index ReclaimX vault history into SQLite"
+)]
+struct Cli {
+    /// SQLite database path
+    #[arg(long, default_value = "reclaimx-index.db")]
+    db: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Pull new program transactions and index their events
+    Sync {
+        /// JSON-RPC endpoint, e.g. a local test validator
+        #[arg(long, default_value = "http://127.0.0.1:8899", conflicts_with = "file")]
+        rpc_url: String,
+        /// Read transactions from an exported JSON array instead of RPC
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Keep polling every N seconds
+        #[arg(long)]
+        poll: Option<u64>,
+    },
+    /// Print the event timeline of a vault
+    Timeline { vault: String },
+    /// Print one summary per recovery of a vault
+    Recoveries { vault: String },
+    /// Print guardian slashes of a vault
+    Slashes { vault: String },
+    /// List indexed vaults
+    Vaults,
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|_| Error::InvalidPubkey(value.to_string()))
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let store = Store::open(&cli.db)?;
+
+    match cli.command {
+        Command::Sync {
+            rpc_url,
+            file,
+            poll,
+        } => {
+            if let Some(file) = file {
+                let indexed = Indexer::new(FileSource::open(file)?, store).sync()?;
+                println!("Indexed {} events", indexed);
+                return Ok(());
+            }
+            let mut indexer = Indexer::new(RpcSource::new(rpc_url), store);
+            loop {
+                let indexed = indexer.sync()?;
+                println!("Indexed {} events", indexed);
+                match poll {
+                    Some(seconds) => thread::sleep(Duration::from_secs(seconds)),
+                    None => break,
+                }
+            }
+        }
+        Command::Timeline { vault } => print_json(&store.timeline(&parse_pubkey(&vault)?)?)?,
+        Command::Recoveries { vault } => print_json(&store.recoveries(&parse_pubkey(&vault)?)?)?,
+        Command::Slashes { vault } => print_json(&store.slashes(&parse_pubkey(&vault)?)?)?,
+        Command::Vaults => {
+            for vault in store.vaults()? {
+                println!("{}", vault);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,242 @@
+use std::{fs, path::Path, str::FromStr};
+
+use anchor_lang::prelude::Pubkey;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+
+/// A confirmed transaction in the `getTransaction` JSON encoding.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawTransaction {
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub transaction: RawTransactionBody,
+    pub meta: Option<RawMeta>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawTransactionBody {
+    pub signatures: Vec<String>,
+    pub message: RawMessage,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawMessage {
+    pub account_keys: Vec<String>,
+    pub instructions: Vec<RawInstruction>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawInstruction {
+    pub program_id_index: usize,
+    #[serde(default)]
+    pub accounts: Vec<usize>,
+    pub data: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawMeta {
+    pub err: Option<Value>,
+    #[serde(default)]
+    pub inner_instructions: Option<Vec<RawInnerInstructions>>,
+    #[serde(default)]
+    pub loaded_addresses: Option<RawLoadedAddresses>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawInnerInstructions {
+    pub index: usize,
+    pub instructions: Vec<RawInstruction>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct RawLoadedAddresses {
+    #[serde(default)]
+    pub writable: Vec<String>,
+    #[serde(default)]
+    pub readonly: Vec<String>,
+}
+
+impl RawTransaction {
+    pub fn signature(&self) -> &str {
+        self.transaction
+            .signatures
+            .first()
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    /// Whether the transaction succeeded, or `None` when the node returned it
+    /// without the status metadata needed to tell.
+    pub fn succeeded(&self) -> Option<bool> {
+        self.meta.as_ref().map(|meta| meta.err.is_none())
+    }
+
+    /// Static account keys followed by addresses loaded from lookup tables.
+    pub fn account_keys(&self) -> Result<Vec<Pubkey>> {
+        let loaded = self
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.loaded_addresses.clone())
+            .unwrap_or_default();
+        self.transaction
+            .message
+            .account_keys
+            .iter()
+            .chain(loaded.writable.iter())
+            .chain(loaded.readonly.iter())
+            .map(|key| Pubkey::from_str(key).map_err(|_| Error::InvalidPubkey(key.clone())))
+            .collect()
+    }
+
+    /// Inner (CPI) instructions in execution order.
+    pub fn inner_instructions(&self) -> impl Iterator<Item = &RawInstruction> {
+        self.meta
+            .iter()
+            .flat_map(|meta| meta.inner_instructions.iter().flatten())
+            .flat_map(|inner| inner.instructions.iter())
+    }
+}
+
+/// Where the indexer reads program transactions from.
+pub trait TransactionSource {
+    /// Signatures involving `program`, oldest first, strictly after `until`.
+    fn signatures(&self, program: &Pubkey, until: Option<&str>) -> Result<Vec<String>>;
+
+    fn transaction(&self, signature: &str) -> Result<Option<RawTransaction>>;
+}
+
+/// Reads from any Solana JSON-RPC compatible endpoint, e.g. a local test validator.
+pub struct RpcSource {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl RpcSource {
+    const PAGE_SIZE: usize = 1000;
+
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            agent: ureq::Agent::new(),
+        }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(request)
+            .map_err(|err| Error::Rpc(err.to_string()))?
+            .into_json()?;
+        if let Some(err) = response.get("error") {
+            return Err(Error::Rpc(err.to_string()));
+        }
+        Ok(response.get("result").cloned().unwrap_or(Value::Null))
+    }
+}
+
+impl TransactionSource for RpcSource {
+    fn signatures(&self, program: &Pubkey, until: Option<&str>) -> Result<Vec<String>> {
+        let mut signatures = vec![];
+        let mut before: Option<String> = None;
+        loop {
+            let mut config = json!({ "limit": Self::PAGE_SIZE, "commitment": "confirmed" });
+            if let Some(until) = until {
+                config["until"] = json!(until);
+            }
+            if let Some(before) = &before {
+                config["before"] = json!(before);
+            }
+            let page = self.call(
+                "getSignaturesForAddress",
+                json!([program.to_string(), config]),
+            )?;
+            let page = page.as_array().cloned().unwrap_or_default();
+            let page_len = page.len();
+            for entry in page {
+                if let Some(signature) = entry.get("signature").and_then(Value::as_str) {
+                    signatures.push(signature.to_string());
+                }
+            }
+            if page_len < Self::PAGE_SIZE {
+                break;
+            }
+            before = signatures.last().cloned();
+        }
+        // RPC returns newest first
+        signatures.reverse();
+        Ok(signatures)
+    }
+
+    fn transaction(&self, signature: &str) -> Result<Option<RawTransaction>> {
+        let result = self.call(
+            "getTransaction",
+            json!([
+                signature,
+                {
+                    "encoding": "json",
+                    "commitment": "confirmed",
+                    "maxSupportedTransactionVersion": 0,
+                }
+            ]),
+        )?;
+        if result.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_value(result)?))
+    }
+}
+
+/// Transactions exported to a JSON array file, oldest first.
+pub struct FileSource {
+    transactions: Vec<RawTransaction>,
+}
+
+impl FileSource {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let data = fs::read_to_string(path)?;
+        Ok(Self::new(serde_json::from_str(&data)?))
+    }
+
+    pub fn new(transactions: Vec<RawTransaction>) -> Self {
+        Self { transactions }
+    }
+}
+
+impl TransactionSource for FileSource {
+    fn signatures(&self, _program: &Pubkey, until: Option<&str>) -> Result<Vec<String>> {
+        let start = until
+            .and_then(|until| {
+                self.transactions
+                    .iter()
+                    .position(|tx| tx.signature() == until)
+            })
+            .map_or(0, |index| index + 1);
+        Ok(self.transactions[start..]
+            .iter()
+            .map(|tx| tx.signature().to_string())
+            .collect())
+    }
+
+    fn transaction(&self, signature: &str) -> Result<Option<RawTransaction>> {
+        Ok(self
+            .transactions
+            .iter()
+            .find(|tx| tx.signature() == signature)
+            .cloned())
+    }
+}
@@ -0,0 +1,219 @@
+use std::{path::Path, str::FromStr};
+
+use anchor_lang::prelude::Pubkey;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    decode::VaultEvent,
+    error::{Error, Result},
+};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS events (
+        signature   TEXT    NOT NULL,
+        event_index INTEGER NOT NULL,
+        slot        INTEGER NOT NULL,
+        vault       TEXT    NOT NULL,
+        recovery_id INTEGER NOT NULL,
+        kind        TEXT    NOT NULL,
+        actor       TEXT    NOT NULL,
+        timestamp   INTEGER NOT NULL,
+        details     TEXT    NOT NULL,
+        PRIMARY KEY (signature, event_index)
+    );
+    CREATE INDEX IF NOT EXISTS events_by_vault ON events (vault, slot);
+    CREATE TABLE IF NOT EXISTS cursors (
+        program   TEXT PRIMARY KEY,
+        signature TEXT NOT NULL
+    );
+";
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TimelineEntry {
+    pub signature: String,
+    pub slot: u64,
+    pub vault: String,
+    pub recovery_id: u64,
+    pub kind: String,
+    pub actor: String,
+    pub timestamp: i64,
+    pub details: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub enum RecoveryOutcome {
+    Pending,
+    Executed,
+    Cancelled,
+    Expired,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RecoverySummary {
+    pub recovery_id: u64,
+    pub initiated_by: String,
+    pub initiated_at: i64,
+    pub inactivity_triggered: bool,
+    pub approvals: Vec<String>,
+    pub outcome: RecoveryOutcome,
+    pub finished_at: Option<i64>,
+}
+
+/// SQLite-backed per-vault event history.
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    pub fn cursor(&self, program: &Pubkey) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT signature FROM cursors WHERE program = ?1",
+                params![program.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    pub fn set_cursor(&self, program: &Pubkey, signature: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO cursors (program, signature) VALUES (?1, ?2)
+             ON CONFLICT (program) DO UPDATE SET signature = excluded.signature",
+            params![program.to_string(), signature],
+        )?;
+        Ok(())
+    }
+
+    /// Stores the events of one transaction. Re-inserting a transaction is a no-op.
+    pub fn insert_events(
+        &mut self,
+        signature: &str,
+        slot: u64,
+        events: &[VaultEvent],
+    ) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        for (index, event) in events.iter().enumerate() {
+            tx.execute(
+                "INSERT OR IGNORE INTO events
+                 (signature, event_index, slot, vault, recovery_id, kind, actor, timestamp, details)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    signature,
+                    index as i64,
+                    slot as i64,
+                    event.vault_id().to_string(),
+                    event.recovery_id() as i64,
+                    event.kind(),
+                    event.actor().to_string(),
+                    event.timestamp(),
+                    event.details().to_string(),
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn vaults(&self) -> Result<Vec<Pubkey>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT vault FROM events ORDER BY vault")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        rows.map(|row| {
+            let vault = row?;
+            Pubkey::from_str(&vault).map_err(|_| Error::InvalidPubkey(vault))
+        })
+        .collect()
+    }
+
+    /// Every event for `vault` in the order it happened on-chain.
+    pub fn timeline(&self, vault: &Pubkey) -> Result<Vec<TimelineEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT signature, slot, vault, recovery_id, kind, actor, timestamp, details
+             FROM events WHERE vault = ?1 ORDER BY slot, rowid",
+        )?;
+        let rows = stmt.query_map(params![vault.to_string()], |row| {
+            let details: String = row.get(7)?;
+            Ok(TimelineEntry {
+                signature: row.get(0)?,
+                slot: row.get::<_, i64>(1)? as u64,
+                vault: row.get(2)?,
+                recovery_id: row.get::<_, i64>(3)? as u64,
+                kind: row.get(4)?,
+                actor: row.get(5)?,
+                timestamp: row.get(6)?,
+                details: serde_json::from_str(&details).unwrap_or(Value::Null),
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn slashes(&self, vault: &Pubkey) -> Result<Vec<TimelineEntry>> {
+        Ok(self
+            .timeline(vault)?
+            .into_iter()
+            .filter(|entry| entry.kind == "guardian_slashed")
+            .collect())
+    }
+
+    /// One summary per recovery_id, oldest first.
+    pub fn recoveries(&self, vault: &Pubkey) -> Result<Vec<RecoverySummary>> {
+        let mut summaries: Vec<RecoverySummary> = vec![];
+        for entry in self.timeline(vault)? {
+            match entry.kind.as_str() {
+                "recovery_initiated" | "inactivity_triggered" => summaries.push(RecoverySummary {
+                    recovery_id: entry.recovery_id,
+                    initiated_by: entry.actor,
+                    initiated_at: entry.timestamp,
+                    inactivity_triggered: entry.kind == "inactivity_triggered",
+                    approvals: vec![],
+                    outcome: RecoveryOutcome::Pending,
+                    finished_at: None,
+                }),
+                "recovery_approved" | "recovery_executed" | "recovery_cancelled"
+                | "recovery_expired" => {
+                    let Some(summary) = summaries
+                        .iter_mut()
+                        .rev()
+                        .find(|summary| summary.recovery_id == entry.recovery_id)
+                    else {
+                        continue;
+                    };
+                    match entry.kind.as_str() {
+                        "recovery_approved" => summary.approvals.push(entry.actor),
+                        "recovery_executed" => {
+                            summary.outcome = RecoveryOutcome::Executed;
+                            summary.finished_at = Some(entry.timestamp);
+                        }
+                        "recovery_expired" => {
+                            summary.outcome = RecoveryOutcome::Expired;
+                            summary.finished_at = Some(entry.timestamp);
+                        }
+                        _ => {
+                            summary.outcome = RecoveryOutcome::Cancelled;
+                            summary.finished_at = Some(entry.timestamp);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(summaries)
+    }
+}
@@ -0,0 +1,203 @@
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+
+use anchor_lang::{event::EVENT_IX_TAG_LE, prelude::Pubkey, Event};
+use reclaimx::{
+    GuardianSlashedEvent, RecoveryApprovedEvent, RecoveryExecutedEvent, RecoveryInitiatedEvent,
+};
+use reclaimx_indexer::{
+    FileSource, Indexer, RawTransaction, RecoveryOutcome, Result, Store, TransactionSource,
+};
+use serde_json::json;
+
+fn event_data(event: &impl Event) -> String {
+    let mut data = EVENT_IX_TAG_LE.to_vec();
+    data.extend(event.data());
+    bs58::encode(data).into_string()
+}
+
+fn transaction(signature: &str, slot: u64, events: &[String], failed: bool) -> RawTransaction {
+    let event_authority = Pubkey::new_unique();
+    let inner: Vec<_> = events
+        .iter()
+        .map(|data| json!({ "programIdIndex": 1, "accounts": [2], "data": data }))
+        .collect();
+    serde_json::from_value(json!({
+        "slot": slot,
+        "blockTime": 1_700_000_000,
+        "transaction": {
+            "signatures": [signature],
+            "message": {
+                "accountKeys": [
+                    Pubkey::new_unique().to_string(),
+                    reclaimx::ID.to_string(),
+                    event_authority.to_string(),
+                ],
+                "instructions": [{ "programIdIndex": 1, "accounts": [0], "data": "" }],
+            },
+        },
+        "meta": {
+            "err": if failed { json!({ "InstructionError": [0, "Custom"] }) } else { json!(null) },
+            "innerInstructions": [{ "index": 0, "instructions": inner }],
+        },
+    }))
+    .unwrap()
+}
+
+#[test]
+fn builds_recovery_timeline_from_events() {
+    let vault = Pubkey::new_unique();
+    let guardians = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let new_owner = Pubkey::new_unique();
+
+    let initiated = event_data(&RecoveryInitiatedEvent {
+        vault_id: vault,
+        recovery_id: 1,
+        actor: guardians[0],
+        timestamp: 100,
+        new_owner,
+        src_eid: reclaimx::SOLANA_EID,
+    });
+    let approvals: Vec<String> = guardians
+        .iter()
+        .enumerate()
+        .map(|(i, guardian)| {
+            event_data(&RecoveryApprovedEvent {
+                vault_id: vault,
+                recovery_id: 1,
+                actor: *guardian,
+                timestamp: 200 + i as i64,
+                approvals: i as u8 + 1,
+            })
+        })
+        .collect();
+    let executed = event_data(&RecoveryExecutedEvent {
+        vault_id: vault,
+        recovery_id: 1,
+        actor: guardians[1],
+        timestamp: 201,
+        new_owner,
+    });
+    let slashed = event_data(&GuardianSlashedEvent {
+        vault_id: vault,
+        guardian: guardians[0],
+        amount: 1_000,
+        recovery_id: 1,
+        actor: guardians[1],
+        timestamp: 300,
+    });
+
+    let source = FileSource::new(vec![
+        transaction("sig-1", 10, &[initiated], false),
+        transaction("sig-2", 11, &approvals[..1], false),
+        transaction("sig-3", 12, &[approvals[1].clone(), executed], false),
+        transaction("sig-4", 13, &[slashed.clone()], true),
+        transaction("sig-5", 14, &[slashed], false),
+    ]);
+    let mut indexer = Indexer::new(source, Store::in_memory().unwrap());
+    assert_eq!(indexer.sync().unwrap(), 5);
+    // Nothing new since the cursor
+    assert_eq!(indexer.sync().unwrap(), 0);
+
+    let store = indexer.store();
+    let timeline = store.timeline(&vault).unwrap();
+    let kinds: Vec<&str> = timeline.iter().map(|entry| entry.kind.as_str()).collect();
+    assert_eq!(
+        kinds,
+        [
+            "recovery_initiated",
+            "recovery_approved",
+            "recovery_approved",
+            "recovery_executed",
+            "guardian_slashed",
+        ]
+    );
+    assert_eq!(timeline[0].details["new_owner"], new_owner.to_string());
+
+    let recoveries = store.recoveries(&vault).unwrap();
+    assert_eq!(recoveries.len(), 1);
+    assert_eq!(recoveries[0].approvals.len(), 2);
+    assert_eq!(recoveries[0].outcome, RecoveryOutcome::Executed);
+    assert_eq!(recoveries[0].finished_at, Some(201));
+
+    let slashes = store.slashes(&vault).unwrap();
+    assert_eq!(slashes.len(), 1);
+    assert_eq!(slashes[0].signature, "sig-5");
+    assert_eq!(store.vaults().unwrap(), vec![vault]);
+}
+
+// A node that has not caught up yet: it lists every signature, but returns
+// nothing for those in `missing` and no status metadata for those in `no_meta`
+#[derive(Default)]
+struct Lagging {
+    missing: HashSet<String>,
+    no_meta: HashSet<String>,
+}
+
+struct LaggingSource {
+    inner: FileSource,
+    lag: Rc<RefCell<Lagging>>,
+}
+
+impl TransactionSource for LaggingSource {
+    fn signatures(&self, program: &Pubkey, until: Option<&str>) -> Result<Vec<String>> {
+        self.inner.signatures(program, until)
+    }
+
+    fn transaction(&self, signature: &str) -> Result<Option<RawTransaction>> {
+        let lag = self.lag.borrow();
+        if lag.missing.contains(signature) {
+            return Ok(None);
+        }
+        Ok(self.inner.transaction(signature)?.map(|mut tx| {
+            if lag.no_meta.contains(signature) {
+                tx.meta = None;
+            }
+            tx
+        }))
+    }
+}
+
+#[test]
+fn unavailable_transactions_are_retried() {
+    let vault = Pubkey::new_unique();
+    let guardian = Pubkey::new_unique();
+    let approved = |timestamp| {
+        event_data(&RecoveryApprovedEvent {
+            vault_id: vault,
+            recovery_id: 1,
+            actor: guardian,
+            timestamp,
+            approvals: 1,
+        })
+    };
+    let lag = Rc::new(RefCell::new(Lagging::default()));
+    let source = LaggingSource {
+        inner: FileSource::new(vec![
+            transaction("sig-1", 10, &[approved(100)], false),
+            transaction("sig-2", 11, &[approved(200)], false),
+            transaction("sig-3", 12, &[approved(300)], false),
+        ]),
+        lag: lag.clone(),
+    };
+    let mut indexer = Indexer::new(source, Store::in_memory().unwrap());
+
+    // getTransaction returns null for sig-2, so the sync stops in front of it
+    lag.borrow_mut().missing.insert("sig-2".into());
+    assert_eq!(indexer.sync().unwrap(), 1);
+    lag.borrow_mut().missing.clear();
+    // sig-3 comes back without meta, so whether it succeeded is unknown
+    lag.borrow_mut().no_meta.insert("sig-3".into());
+    assert_eq!(indexer.sync().unwrap(), 1);
+    lag.borrow_mut().no_meta.clear();
+    assert_eq!(indexer.sync().unwrap(), 1);
+    assert_eq!(indexer.sync().unwrap(), 0);
+
+    let timestamps: Vec<i64> = indexer
+        .store()
+        .timeline(&vault)
+        .unwrap()
+        .iter()
+        .map(|entry| entry.timestamp)
+        .collect();
+    assert_eq!(timestamps, [100, 200, 300]);
+}
@@ -0,0 +1,326 @@
+//! Finds vaults whose inactivity period, recovery timelock or recovery
+//! deadline has matured and cranks them with `check_inactivity`,
+//! `execute_recovery` or `expire_recovery`.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use anchor_lang::{AccountDeserialize, Discriminator, InstructionData, ToAccountMetas};
+use anchor_spl::{associated_token::get_associated_token_address, token::spl_token};
+use futures::{stream, StreamExt};
+use reclaimx::{recovery, CrankReserve, PauseScope, ProtocolConfig, Recovery, Vault};
+use reclaimx_client::pda;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    client_error::ClientError,
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    account::from_account,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    system_program, sysvar,
+    transaction::Transaction,
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("rpc error: {0}")]
+    Rpc(#[from] ClientError),
+    #[error("clock sysvar unavailable")]
+    Clock,
+    #[error("protocol config unavailable")]
+    Config,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Crank {
+    CheckInactivity,
+    ExecuteRecovery,
+    ExpireRecovery,
+}
+
+impl Crank {
+    /// The pause scope the program checks before running this crank.
+    pub fn scope(self) -> PauseScope {
+        match self {
+            Crank::ExecuteRecovery => PauseScope::RecoveryExecution,
+            Crank::CheckInactivity | Crank::ExpireRecovery => PauseScope::All,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Job {
+    pub vault: Pubkey,
+    pub state: Vault,
+    /// The vault's active recovery, if it has one
+    pub recovery: Option<Recovery>,
+    pub crank: Crank,
+    /// The vault's crank reserve, when it has one, so the cranker is paid
+    pub crank_reserve: Option<Pubkey>,
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    Sent(Signature),
+    Simulated { logs: Vec<String> },
+    Failed(String),
+}
+
+pub struct Config {
+    pub dry_run: bool,
+    pub concurrency: usize,
+}
+
+/// Decides which crank, if any, a vault needs at `now`, given its active
+/// recovery. Cranks whose scope `config` has paused are skipped, since the
+/// program would reject them.
+pub fn plan(
+    config: &ProtocolConfig,
+    vault: &Vault,
+    active: Option<&Recovery>,
+    now: i64,
+) -> Option<Crank> {
+    let crank = match active {
+        Some(active) if recovery::ready_to_execute(vault, active, now) => {
+            Some(Crank::ExecuteRecovery)
+        }
+        Some(active) if recovery::expired(vault, active, now) => Some(Crank::ExpireRecovery),
+        // Never start an inactivity recovery while another is in flight
+        Some(_) => None,
+        // The active recovery could not be fetched
+        None if vault.active_recovery.is_some() => None,
+        None if recovery::inactivity_elapsed(vault, now) => Some(Crank::CheckInactivity),
+        None => None,
+    };
+    crank.filter(|crank| !config.is_paused(crank.scope()))
+}
+
+/// Instructions that perform `job`, paid for and signed by `cranker`.
+pub fn instructions(job: &Job, cranker: &Pubkey) -> Vec<Instruction> {
+    let active_recovery = pda::recovery(&job.vault, job.state.recovery_id);
+    match job.crank {
+        Crank::CheckInactivity => vec![Instruction {
+            program_id: reclaimx::ID,
+            accounts: reclaimx::accounts::CheckInactivity {
+                vault: job.vault,
+                config: pda::config(),
+                cranker: *cranker,
+                recovery: pda::recovery(&job.vault, job.state.recovery_id + 1),
+                crank_reserve: job.crank_reserve,
+                system_program: system_program::ID,
+                event_authority: pda::event_authority(),
+                program: reclaimx::ID,
+            }
+            .to_account_metas(None),
+            data: reclaimx::instruction::CheckInactivity {}.data(),
+        }],
+        Crank::ExpireRecovery => vec![Instruction {
+            program_id: reclaimx::ID,
+            accounts: reclaimx::accounts::ExpireRecovery {
+                vault: job.vault,
+                config: pda::config(),
+                recovery: active_recovery,
+                cranker: *cranker,
+                crank_reserve: job.crank_reserve,
+                event_authority: pda::event_authority(),
+                program: reclaimx::ID,
+            }
+            .to_account_metas(None),
+            data: reclaimx::instruction::ExpireRecovery {}.data(),
+        }],
+        Crank::ExecuteRecovery => {
+            let Some(new_owner) = job.recovery.as_ref().map(|recovery| recovery.new_owner) else {
+                return vec![];
+            };
+            let mut ixs = vec![];
+            let mut accounts = reclaimx::accounts::ExecuteRecovery {
+                vault: job.vault,
+                config: pda::config(),
+                recovery: active_recovery,
+                cranker: *cranker,
+                crank_reserve: job.crank_reserve,
+                token_program: spl_token::ID,
+                event_authority: pda::event_authority(),
+                program: reclaimx::ID,
+            }
+            .to_account_metas(None);
+            // Each asset moves from its vault account to the new owner's ATA for its mint
+            for asset in &job.state.assets {
+                ixs.push(create_associated_token_account_idempotent(
+                    cranker,
+                    &new_owner,
+                    &asset.mint,
+                    &spl_token::ID,
+                ));
+                accounts.push(AccountMeta::new(asset.token_account, false));
+                accounts.push(AccountMeta::new(
+                    get_associated_token_address(&new_owner, &asset.mint),
+                    false,
+                ));
+            }
+            ixs.push(Instruction {
+                program_id: reclaimx::ID,
+                accounts,
+                data: reclaimx::instruction::ExecuteRecovery {}.data(),
+            });
+            ixs
+        }
+    }
+}
+
+/// On-chain unix timestamp, so keeper decisions match the program's clock.
+pub async fn chain_time(client: &RpcClient) -> Result<i64> {
+    let account = client.get_account(&sysvar::clock::id()).await?;
+    let clock: Clock = from_account(&account).ok_or(Error::Clock)?;
+    Ok(clock.unix_timestamp)
+}
+
+/// The protocol config, for its paused scopes.
+async fn protocol_config(client: &RpcClient) -> Result<ProtocolConfig> {
+    let account = client.get_account(&pda::config()).await?;
+    ProtocolConfig::try_deserialize(&mut account.data.as_slice()).map_err(|_| Error::Config)
+}
+
+/// Every account of type `T` owned by the program.
+async fn scan<T: AccountDeserialize + Discriminator>(
+    client: &RpcClient,
+) -> Result<Vec<(Pubkey, T)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            0,
+            &T::DISCRIMINATOR,
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let accounts = client
+        .get_program_accounts_with_config(&reclaimx::ID, config)
+        .await?;
+    Ok(accounts
+        .into_iter()
+        .filter_map(|(pubkey, account)| {
+            T::try_deserialize(&mut account.data.as_slice())
+                .ok()
+                .map(|state| (pubkey, state))
+        })
+        .collect())
+}
+
+/// The active recovery of each vault that has one, keyed by recovery address.
+async fn active_recoveries(
+    client: &RpcClient,
+    vaults: &[(Pubkey, Vault)],
+) -> Result<HashMap<Pubkey, Recovery>> {
+    let addresses: Vec<Pubkey> = vaults
+        .iter()
+        .filter_map(|(_, state)| state.active_recovery)
+        .collect();
+    let mut recoveries = HashMap::new();
+    // getMultipleAccounts accepts at most 100 keys per call
+    for chunk in addresses.chunks(100) {
+        let accounts = client.get_multiple_accounts(chunk).await?;
+        for (address, account) in chunk.iter().zip(accounts) {
+            if let Some(recovery) = account
+                .and_then(|account| Recovery::try_deserialize(&mut account.data.as_slice()).ok())
+            {
+                recoveries.insert(*address, recovery);
+            }
+        }
+    }
+    Ok(recoveries)
+}
+
+pub async fn find_jobs(client: &RpcClient) -> Result<Vec<Job>> {
+    let now = chain_time(client).await?;
+    let config = protocol_config(client).await?;
+    let reserves: HashSet<Pubkey> = scan::<CrankReserve>(client)
+        .await?
+        .into_iter()
+        .map(|(pubkey, _)| pubkey)
+        .collect();
+    let vaults = scan::<Vault>(client).await?;
+    let mut recoveries = active_recoveries(client, &vaults).await?;
+    Ok(vaults
+        .into_iter()
+        .filter_map(|(vault, state)| {
+            let crank_reserve =
+                Some(pda::crank_reserve(&vault)).filter(|reserve| reserves.contains(reserve));
+            let recovery = state
+                .active_recovery
+                .and_then(|address| recoveries.remove(&address));
+            plan(&config, &state, recovery.as_ref(), now).map(|crank| Job {
+                vault,
+                state,
+                recovery,
+                crank,
+                crank_reserve,
+            })
+        })
+        .collect())
+}
+
+async fn submit(client: &RpcClient, cranker: &Keypair, job: &Job, dry_run: bool) -> Outcome {
+    let blockhash = match client.get_latest_blockhash().await {
+        Ok(blockhash) => blockhash,
+        Err(err) => return Outcome::Failed(err.to_string()),
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &instructions(job, &cranker.pubkey()),
+        Some(&cranker.pubkey()),
+        &[cranker],
+        blockhash,
+    );
+
+    if dry_run {
+        return match client.simulate_transaction(&tx).await {
+            Ok(response) => match response.value.err {
+                Some(err) => Outcome::Failed(err.to_string()),
+                None => Outcome::Simulated {
+                    logs: response.value.logs.unwrap_or_default(),
+                },
+            },
+            Err(err) => Outcome::Failed(err.to_string()),
+        };
+    }
+    match client.send_and_confirm_transaction(&tx).await {
+        Ok(signature) => Outcome::Sent(signature),
+        Err(err) => Outcome::Failed(err.to_string()),
+    }
+}
+
+/// Scans once and cranks every due vault, at most `concurrency` at a time.
+pub async fn run_once(
+    client: Arc<RpcClient>,
+    cranker: Arc<Keypair>,
+    config: &Config,
+) -> Result<Vec<(Job, Outcome)>> {
+    let jobs = find_jobs(&client).await?;
+    Ok(stream::iter(jobs)
+        .map(|job| {
+            let client = client.clone();
+            let cranker = cranker.clone();
+            let dry_run = config.dry_run;
+            async move {
+                let outcome = submit(&client, &cranker, &job, dry_run).await;
+                (job, outcome)
+            }
+        })
+        .buffer_unordered(config.concurrency.max(1))
+        .collect()
+        .await)
+}
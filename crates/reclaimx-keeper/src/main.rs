@@ -0,0 +1,84 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use clap::Parser;
+use reclaimx_keeper::{run_once, Config, Job, Outcome};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, signature::read_keypair_file};
+
+#[derive(Parser)]
+#[command(
+    name = "reclaimx-keeper",
+    about = "Crank ReclaimX inactivity checks and matured recoveries"
+)]
+struct Cli {
+    #[arg(long, default_value = "http://127.0.0.1:8899")]
+    rpc_url: String,
+    /// Keypair that signs and pays for crank transactions
+    #[arg(long, default_value = "~/.config/solana/id.json")]
+    keypair: String,
+    /// Simulate transactions instead of sending them
+    #[arg(long)]
+    dry_run: bool,
+    /// Maximum transactions in flight
+    #[arg(long, default_value_t = 4)]
+    concurrency: usize,
+    /// Rescan every N seconds instead of exiting after one pass
+    #[arg(long)]
+    interval: Option<u64>,
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn report(results: Vec<(Job, Outcome)>) {
+    if results.is_empty() {
+        println!("No vaults due");
+    }
+    for (job, outcome) in results {
+        match outcome {
+            Outcome::Sent(signature) => {
+                println!("{:?} {} sent: {}", job.crank, job.vault, signature)
+            }
+            Outcome::Simulated { logs } => {
+                println!(
+                    "{:?} {} would succeed ({} log lines)",
+                    job.crank,
+                    job.vault,
+                    logs.len()
+                )
+            }
+            Outcome::Failed(err) => eprintln!("{:?} {} failed: {}", job.crank, job.vault, err),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    let cranker = Arc::new(read_keypair_file(expand_home(&cli.keypair))?);
+    let client = Arc::new(RpcClient::new_with_commitment(
+        cli.rpc_url,
+        CommitmentConfig::confirmed(),
+    ));
+    let config = Config {
+        dry_run: cli.dry_run,
+        concurrency: cli.concurrency,
+    };
+
+    loop {
+        match run_once(client.clone(), cranker.clone(), &config).await {
+            Ok(results) => report(results),
+            // A single pass reports the failure; a daemon retries on its next tick
+            Err(err) if cli.interval.is_some() => eprintln!("Scan failed: {}", err),
+            Err(err) => return Err(err.into()),
+        }
+        match cli.interval {
+            Some(seconds) => tokio::time::sleep(Duration::from_secs(seconds)).await,
+            None => return Ok(()),
+        }
+    }
+}
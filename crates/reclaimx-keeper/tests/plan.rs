@@ -0,0 +1,188 @@
+use anchor_lang::prelude::Pubkey;
+use reclaimx::{recovery, Asset, AssetType, PauseScope, ProtocolConfig, Recovery, Vault};
+use reclaimx_client::pda;
+use reclaimx_keeper::{instructions, plan, Crank, Job};
+
+const START: i64 = 1_700_000_000;
+
+fn vault() -> Vault {
+    Vault {
+        owner: Pubkey::new_unique(),
+        guardians: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+        threshold: 2,
+        timelock: 3_600,
+        inactivity_period: 86_400,
+        backup_wallet: Pubkey::new_unique(),
+        last_active_timestamp: START,
+        bump: 255,
+        recovery_expiry: 3_600 + Vault::DEFAULT_RECOVERY_EXPIRY,
+        approval_window: 3_600 + Vault::DEFAULT_RECOVERY_EXPIRY,
+        creator: Pubkey::new_unique(),
+        ..Vault::default()
+    }
+}
+
+fn config() -> ProtocolConfig {
+    ProtocolConfig {
+        admin: Pubkey::new_unique(),
+        pending_admin: None,
+        security_council: None,
+        endpoint_program: Pubkey::new_unique(),
+        allowed_eids: vec![],
+        local_eid: 0,
+        min_timelock: 0,
+        max_timelock: u64::MAX,
+        min_inactivity_period: 0,
+        max_inactivity_period: u64::MAX,
+        max_native_fee: 0,
+        stake_native_fee: 0,
+        paused_scopes: 0,
+        bump: 255,
+    }
+}
+
+fn paused(scope: PauseScope) -> ProtocolConfig {
+    let mut config = config();
+    config.set_paused(config.admin, scope, true).unwrap();
+    config
+}
+
+fn start(vault: &mut Vault) -> Recovery {
+    let mut recovery = Recovery::default();
+    let address = pda::recovery(&Pubkey::new_unique(), vault.recovery_id + 1);
+    recovery::start(
+        vault,
+        &mut recovery,
+        address,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        START,
+        false,
+    )
+    .unwrap();
+    recovery
+}
+
+#[test]
+fn inactive_vault_needs_check() {
+    let vault = vault();
+    assert_eq!(plan(&config(), &vault, None, START + 86_399), None);
+    assert_eq!(
+        plan(&config(), &vault, None, START + 86_400),
+        Some(Crank::CheckInactivity)
+    );
+}
+
+#[test]
+fn pending_recovery_is_never_overwritten() {
+    let mut vault = vault();
+    let active = start(&mut vault);
+    assert_eq!(
+        plan(&config(), &vault, Some(&active), START + 10 * 86_400),
+        None
+    );
+    // Nor when the active recovery could not be fetched
+    assert_eq!(plan(&config(), &vault, None, START + 10 * 86_400), None);
+}
+
+#[test]
+fn matured_recovery_is_executed() {
+    let mut vault = vault();
+    let mut active = start(&mut vault);
+    for guardian in vault.guardians.clone() {
+        recovery::approve(&mut vault, &mut active, guardian, START).unwrap();
+    }
+    assert_eq!(plan(&config(), &vault, Some(&active), START + 3_599), None);
+    assert_eq!(
+        plan(&config(), &vault, Some(&active), START + 3_600),
+        Some(Crank::ExecuteRecovery)
+    );
+
+    let job = Job {
+        vault: Pubkey::new_unique(),
+        state: vault,
+        recovery: Some(active),
+        crank: Crank::ExecuteRecovery,
+        crank_reserve: None,
+    };
+    let ixs = instructions(&job, &Pubkey::new_unique());
+    assert_eq!(ixs.len(), 1);
+    assert_eq!(ixs[0].program_id, reclaimx::ID);
+}
+
+#[test]
+fn execution_moves_each_asset_through_its_own_accounts() {
+    let mut vault = vault();
+    vault.assets = (0..2)
+        .map(|_| Asset {
+            asset_type: AssetType::Token,
+            amount: 100,
+            token_account: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+        })
+        .collect();
+    let mut active = start(&mut vault);
+    for guardian in vault.guardians.clone() {
+        recovery::approve(&mut vault, &mut active, guardian, START).unwrap();
+    }
+
+    let assets = vault.assets.clone();
+    let job = Job {
+        vault: Pubkey::new_unique(),
+        state: vault,
+        recovery: Some(active),
+        crank: Crank::ExecuteRecovery,
+        crank_reserve: None,
+    };
+    let ixs = instructions(&job, &Pubkey::new_unique());
+    // One new-owner ATA per asset, then the execution itself
+    assert_eq!(ixs.len(), 3);
+    let execute = &ixs[2];
+    let pairs = &execute.accounts[execute.accounts.len() - 4..];
+    assert_eq!(pairs[0].pubkey, assets[0].token_account);
+    assert_eq!(pairs[2].pubkey, assets[1].token_account);
+    assert!(pairs.iter().all(|meta| meta.is_writable));
+}
+
+#[test]
+fn abandoned_recovery_is_expired() {
+    let mut vault = vault();
+    let active = start(&mut vault);
+    let deadline = START + vault.recovery_expiry as i64;
+    assert_eq!(plan(&config(), &vault, Some(&active), deadline - 1), None);
+    assert_eq!(
+        plan(&config(), &vault, Some(&active), deadline),
+        Some(Crank::ExpireRecovery)
+    );
+}
+
+#[test]
+fn paused_cranks_are_skipped() {
+    let mut vault = vault();
+    let due = START + 86_400;
+    assert_eq!(plan(&paused(PauseScope::All), &vault, None, due), None);
+    // Scopes the crank does not use leave it due
+    assert_eq!(
+        plan(&paused(PauseScope::RecoveryExecution), &vault, None, due),
+        Some(Crank::CheckInactivity)
+    );
+
+    let mut active = start(&mut vault);
+    for guardian in vault.guardians.clone() {
+        recovery::approve(&mut vault, &mut active, guardian, START).unwrap();
+    }
+    let matured = START + 3_600;
+    assert_eq!(
+        plan(
+            &paused(PauseScope::RecoveryExecution),
+            &vault,
+            Some(&active),
+            matured
+        ),
+        None
+    );
+    assert_eq!(
+        plan(&paused(PauseScope::Staking), &vault, Some(&active), matured),
+        Some(Crank::ExecuteRecovery)
+    );
+}
@@ -2,6 +2,8 @@ import { type Connection, PublicKey, SystemProgram } from "@solana/web3.js"
 import { type Program, BN } from "@project-serum/anchor"
 
 export interface SolanaVaultConfig {
+  // Distinguishes vaults created by the same wallet; defaults to 0
+  vaultSeed?: number
   guardians: PublicKey[]
   threshold: number
   timelock: number
@@ -24,14 +26,16 @@ export const createSolanaVaultService = (program: Program, connection: Connectio
     async createVault(config: SolanaVaultConfig): Promise<string> {
       try {
         // Generate vault PDA
+        const vaultSeed = new BN(config.vaultSeed ?? 0)
         const [vaultPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("vault"), wallet.publicKey.toBuffer()],
+          [Buffer.from("vault"), wallet.publicKey.toBuffer(), vaultSeed.toArrayLike(Buffer, "le", 8)],
           program.programId,
         )
 
         // Create instruction
         const tx = await program.methods
           .initializeVault(
+            vaultSeed,
             config.guardians,
             config.threshold,
             new BN(config.timelock),
@@ -0,0 +1,329 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+// Deployed at the LayerZero V2 endpoint address on local validators only
+declare_id!("76y77prsiCMvXMjuoZ5VRrhG5qYBrUMYTE5WgHqgjEn6");
+
+pub const ENDPOINT_SEED: &[u8] = b"endpoint";
+
+#[program]
+pub mod mock_endpoint {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, base_fee: u64, fee_per_byte: u64) -> Result<()> {
+        let endpoint = &mut ctx.accounts.endpoint;
+        endpoint.base_fee = base_fee;
+        endpoint.fee_per_byte = fee_per_byte;
+        endpoint.outbound_nonce = 0;
+        endpoint.inbound_nonce = 0;
+        endpoint.registered_oapps = 0;
+        endpoint.packets = vec![];
+        endpoint.verified = vec![];
+        endpoint.bump = ctx.bumps.endpoint;
+
+        msg!(
+            "Mock endpoint initialized, base fee: {}, fee per byte: {}",
+            base_fee,
+            fee_per_byte
+        );
+        Ok(())
+    }
+
+    pub fn register_oapp(ctx: Context<RegisterOApp>, params: RegisterOAppParams) -> Result<()> {
+        let endpoint = &mut ctx.accounts.endpoint;
+        endpoint.registered_oapps += 1;
+
+        msg!(
+            "Registered OApp {} with delegate {}",
+            ctx.accounts.oapp.key(),
+            params.delegate
+        );
+        Ok(())
+    }
+
+    pub fn quote(ctx: Context<Quote>, params: QuoteParams) -> Result<MessagingFee> {
+        let native_fee = ctx.accounts.endpoint.fee_for(&params.message);
+        Ok(MessagingFee {
+            native_fee,
+            lz_token_fee: 0,
+        })
+    }
+
+    pub fn send(ctx: Context<Send>, params: SendParams) -> Result<()> {
+        let endpoint = &mut ctx.accounts.endpoint;
+        let receiver: [u8; 32] = params
+            .to
+            .as_slice()
+            .try_into()
+            .map_err(|_| error!(MockEndpointError::InvalidReceiver))?;
+        require!(
+            params.message.len() <= EndpointState::MAX_MESSAGE_SIZE,
+            MockEndpointError::MessageTooLarge
+        );
+        require!(
+            params.native_fee >= endpoint.fee_for(&params.message),
+            MockEndpointError::InsufficientFee
+        );
+
+        endpoint.outbound_nonce += 1;
+        if endpoint.packets.len() == EndpointState::MAX_PACKETS {
+            endpoint.packets.remove(0);
+        }
+        endpoint.packets.push(Packet {
+            nonce: endpoint.outbound_nonce,
+            sender: ctx.accounts.oapp.key(),
+            dst_eid: params.dst_eid,
+            receiver,
+            message: params.message,
+            native_fee: params.native_fee,
+        });
+
+        msg!(
+            "Packet {} sent to EID {}",
+            endpoint.outbound_nonce,
+            params.dst_eid
+        );
+        Ok(())
+    }
+
+    // Stands in for the DVNs committing an inbound packet. As on the real
+    // endpoint, the executor then calls the receiver's `lz_receive` directly
+    // and the receiver must `clear` the payload for delivery to count.
+    pub fn verify(ctx: Context<Verify>, params: VerifyParams) -> Result<()> {
+        let endpoint = &mut ctx.accounts.endpoint;
+        require!(
+            endpoint.verified.len() < EndpointState::MAX_VERIFIED,
+            MockEndpointError::TooManyVerified
+        );
+        endpoint.verified.push(VerifiedPayload {
+            receiver: params.receiver,
+            src_eid: params.src_eid,
+            sender: params.sender,
+            nonce: params.nonce,
+            payload_hash: payload_hash(&params.guid, &params.message),
+        });
+
+        msg!(
+            "Verified packet {} from EID {} for {}",
+            params.nonce,
+            params.src_eid,
+            params.receiver
+        );
+        Ok(())
+    }
+
+    // Called by the receiving OApp from `lz_receive`. Fails unless the packet
+    // was verified for that OApp, and consumes it so it cannot be replayed.
+    pub fn clear(ctx: Context<Clear>, params: ClearParams) -> Result<()> {
+        require!(
+            params.receiver == ctx.accounts.oapp.key(),
+            MockEndpointError::InvalidReceiver
+        );
+        let endpoint = &mut ctx.accounts.endpoint;
+        let hash = payload_hash(&params.guid, &params.message);
+        let index = endpoint
+            .verified
+            .iter()
+            .position(|payload| {
+                payload.receiver == params.receiver
+                    && payload.src_eid == params.src_eid
+                    && payload.sender == params.sender
+                    && payload.nonce == params.nonce
+                    && payload.payload_hash == hash
+            })
+            .ok_or(MockEndpointError::PayloadNotVerified)?;
+        endpoint.verified.remove(index);
+        endpoint.inbound_nonce += 1;
+
+        msg!(
+            "Cleared packet {} from EID {}",
+            params.nonce,
+            params.src_eid
+        );
+        Ok(())
+    }
+}
+
+fn payload_hash(guid: &[u8; 32], message: &[u8]) -> [u8; 32] {
+    hashv(&[guid, message]).to_bytes()
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + EndpointState::SPACE,
+        seeds = [ENDPOINT_SEED],
+        bump
+    )]
+    pub endpoint: Account<'info, EndpointState>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterOApp<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: OApp being registered
+    pub oapp: UncheckedAccount<'info>,
+    #[account(mut, seeds = [ENDPOINT_SEED], bump = endpoint.bump)]
+    pub endpoint: Account<'info, EndpointState>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Quote<'info> {
+    #[account(seeds = [ENDPOINT_SEED], bump = endpoint.bump)]
+    pub endpoint: Account<'info, EndpointState>,
+}
+
+#[derive(Accounts)]
+pub struct Send<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub oapp: Signer<'info>,
+    #[account(mut, seeds = [ENDPOINT_SEED], bump = endpoint.bump)]
+    pub endpoint: Account<'info, EndpointState>,
+}
+
+#[derive(Accounts)]
+pub struct Verify<'info> {
+    // The mock has no DVN set; any signer may verify
+    pub verifier: Signer<'info>,
+    #[account(mut, seeds = [ENDPOINT_SEED], bump = endpoint.bump)]
+    pub endpoint: Account<'info, EndpointState>,
+}
+
+#[derive(Accounts)]
+pub struct Clear<'info> {
+    pub oapp: Signer<'info>,
+    #[account(mut, seeds = [ENDPOINT_SEED], bump = endpoint.bump)]
+    pub endpoint: Account<'info, EndpointState>,
+}
+
+#[account]
+pub struct EndpointState {
+    pub base_fee: u64,
+    pub fee_per_byte: u64,
+    pub outbound_nonce: u64,
+    pub inbound_nonce: u64,
+    pub registered_oapps: u64,
+    pub packets: Vec<Packet>,
+    pub verified: Vec<VerifiedPayload>,
+    pub bump: u8,
+}
+
+impl EndpointState {
+    pub const MAX_PACKETS: usize = 8;
+    pub const MAX_VERIFIED: usize = 8;
+    pub const MAX_MESSAGE_SIZE: usize = 256;
+
+    pub const SPACE: usize = 8 + // base_fee
+        8 + // fee_per_byte
+        8 + // outbound_nonce
+        8 + // inbound_nonce
+        8 + // registered_oapps
+        4 + (Packet::SPACE * EndpointState::MAX_PACKETS) + // packets
+        4 + (VerifiedPayload::SPACE * EndpointState::MAX_VERIFIED) + // verified
+        1; // bump
+
+    pub fn fee_for(&self, message: &[u8]) -> u64 {
+        self.base_fee + self.fee_per_byte * message.len() as u64
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Packet {
+    pub nonce: u64,
+    pub sender: Pubkey,
+    pub dst_eid: u32,
+    pub receiver: [u8; 32],
+    pub message: Vec<u8>,
+    pub native_fee: u64,
+}
+
+impl Packet {
+    pub const SPACE: usize = 8 + 32 + 4 + 32 + 4 + EndpointState::MAX_MESSAGE_SIZE + 8;
+}
+
+// An inbound packet the DVNs committed and the receiver has not cleared yet
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VerifiedPayload {
+    pub receiver: Pubkey,
+    pub src_eid: u32,
+    pub sender: [u8; 32],
+    pub nonce: u64,
+    pub payload_hash: [u8; 32],
+}
+
+impl VerifiedPayload {
+    pub const SPACE: usize = 32 + 4 + 32 + 8 + 32;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RegisterOAppParams {
+    pub delegate: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct QuoteParams {
+    pub dst_eid: u32,
+    pub to: Vec<u8>,
+    pub message: Vec<u8>,
+    pub options: Vec<u8>,
+    pub pay_in_lz_token: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SendParams {
+    pub dst_eid: u32,
+    pub to: Vec<u8>,
+    pub message: Vec<u8>,
+    pub options: Vec<u8>,
+    pub native_fee: u64,
+    pub lz_token_fee: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MessagingFee {
+    pub native_fee: u64,
+    pub lz_token_fee: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct VerifyParams {
+    pub receiver: Pubkey,
+    pub src_eid: u32,
+    pub sender: [u8; 32],
+    pub nonce: u64,
+    pub guid: [u8; 32],
+    pub message: Vec<u8>,
+}
+
+// Mirrors the endpoint's `clear` parameters
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ClearParams {
+    pub receiver: Pubkey,
+    pub src_eid: u32,
+    pub sender: [u8; 32],
+    pub nonce: u64,
+    pub guid: [u8; 32],
+    pub message: Vec<u8>,
+}
+
+#[error_code]
+pub enum MockEndpointError {
+    #[msg("Receiver must be 32 bytes")]
+    InvalidReceiver,
+    #[msg("Message too large")]
+    MessageTooLarge,
+    #[msg("Insufficient messaging fee")]
+    InsufficientFee,
+    #[msg("Packet was not verified for this receiver")]
+    PayloadNotVerified,
+    #[msg("Too many packets awaiting clear")]
+    TooManyVerified,
+}
@@ -0,0 +1,198 @@
+#![no_main]
+
+// Drives arbitrary instruction sequences through the pure recovery state
+// machine and checks that ownership only moves after threshold + timelock
+// and never while the vault is frozen.
+use anchor_lang::prelude::Pubkey;
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use reclaimx::freeze;
+use reclaimx::recovery::{self, ApprovalOutcome};
+use reclaimx::{Recovery, RecoveryStatus, Vault};
+
+const START: i64 = 1_700_000_000;
+const MAX_GUARDIANS: usize = 10;
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Initiate { new_owner: u8, by_inactivity: bool },
+    Approve { guardian: u8 },
+    ApproveStranger { key: u8 },
+    MarkMalicious { guardian: u8 },
+    Warp { seconds: u32 },
+    CheckInactivity,
+    CancelInactivity,
+    Expire,
+    ProofOfLife,
+    Freeze { guardian: u8, by_owner: bool },
+    Unfreeze { guardian: u8, by_owner: bool },
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    guardian_count: u8,
+    threshold: u8,
+    timelock: u32,
+    inactivity_period: u32,
+    approval_window: u32,
+    ops: Vec<Op>,
+}
+
+fn key(tag: u8, index: u8) -> Pubkey {
+    let mut bytes = [0u8; 32];
+    bytes[0] = tag;
+    bytes[1] = index;
+    Pubkey::new_from_array(bytes)
+}
+
+fuzz_target!(|input: Input| {
+    let guardian_count = input.guardian_count as usize % MAX_GUARDIANS + 1;
+    let threshold = input.threshold % guardian_count as u8 + 1;
+    let timelock = input.timelock as u64;
+    let guardians: Vec<Pubkey> = (0..guardian_count as u8).map(|i| key(1, i)).collect();
+
+    let mut vault = Vault {
+        owner: key(0, 0),
+        guardians: guardians.clone(),
+        threshold,
+        timelock,
+        inactivity_period: input.inactivity_period as u64,
+        backup_wallet: key(2, 0),
+        last_active_timestamp: START,
+        bump: 255,
+        recovery_expiry: timelock + Vault::DEFAULT_RECOVERY_EXPIRY,
+        approval_window: input.approval_window as u64 + 1,
+        creator: key(0, 0),
+        ..Vault::default()
+    };
+    let mut now = START;
+    // Latest recovery account; the program only ever acts on this one
+    let mut current: Option<Recovery> = None;
+
+    for op in input.ops {
+        let before = vault.clone();
+        let before_recovery = current.clone();
+        match (op, current.as_mut()) {
+            (
+                Op::Initiate {
+                    new_owner,
+                    by_inactivity,
+                },
+                _,
+            ) => {
+                let mut recovery = Recovery::default();
+                match recovery::start(
+                    &mut vault,
+                    &mut recovery,
+                    key(5, new_owner),
+                    key(3, new_owner),
+                    key(3, new_owner),
+                    now,
+                    by_inactivity,
+                ) {
+                    Ok(_) => {
+                        assert_eq!(vault.recovery_id, before.recovery_id + 1);
+                        current = Some(recovery);
+                    }
+                    Err(_) => assert!(before.active_recovery.is_some()),
+                }
+            }
+            (Op::CheckInactivity, _) => {
+                let mut recovery = Recovery::default();
+                if recovery::check_inactivity(&mut vault, &mut recovery, key(5, 0), key(4, 0), now)
+                    .is_ok()
+                {
+                    assert!(now >= before.last_active_timestamp + before.inactivity_period as i64);
+                    current = Some(recovery);
+                }
+            }
+            (Op::Warp { seconds }, _) => now += seconds as i64,
+            (Op::ProofOfLife, _) => vault.last_active_timestamp = now,
+            (Op::Freeze { guardian, by_owner }, _) => {
+                let signer = if by_owner {
+                    vault.owner
+                } else {
+                    guardians[guardian as usize % guardian_count]
+                };
+                if freeze::freeze(&mut vault, signer, now).is_ok() {
+                    assert!(!freeze::is_frozen(&before, now));
+                }
+                assert!(vault.frozen_until <= now + freeze::MAX_FREEZE_DURATION);
+            }
+            (Op::Unfreeze { guardian, by_owner }, _) => {
+                let signer = if by_owner {
+                    vault.owner
+                } else {
+                    guardians[guardian as usize % guardian_count]
+                };
+                if let Ok(freeze::UnfreezeOutcome::Unfrozen) =
+                    freeze::unfreeze(&mut vault, signer, now)
+                {
+                    assert!(!by_owner || !before.frozen_by_guardians);
+                }
+            }
+            (_, None) => {}
+            (Op::Approve { guardian }, Some(recovery)) => {
+                let guardian = guardians[guardian as usize % guardian_count];
+                if let Ok(ApprovalOutcome::Completed { new_owner, .. }) =
+                    recovery::approve(&mut vault, recovery, guardian, now)
+                {
+                    let before_recovery = before_recovery.as_ref().unwrap();
+                    assert_eq!(before_recovery.status, RecoveryStatus::Pending);
+                    assert_eq!(before_recovery.new_owner, new_owner);
+                    assert!(before_recovery.approvals.len() + 1 >= threshold as usize);
+                    assert!(now >= before_recovery.start_timestamp + timelock as i64);
+                    assert!(now < before_recovery.start_timestamp + before.recovery_expiry as i64);
+                }
+            }
+            (Op::ApproveStranger { key: index }, Some(recovery)) => {
+                assert!(recovery::approve(&mut vault, recovery, key(4, index), now).is_err());
+            }
+            (Op::MarkMalicious { guardian }, Some(recovery)) => {
+                let guardian = guardians[guardian as usize % guardian_count];
+                let _ = recovery::mark_malicious(&vault, recovery, guardian);
+            }
+            (Op::CancelInactivity, Some(recovery)) => {
+                if recovery::cancel_inactivity_recovery(&mut vault, recovery, now).is_ok() {
+                    let before_recovery = before_recovery.as_ref().unwrap();
+                    assert_eq!(before_recovery.status, RecoveryStatus::Pending);
+                    assert!(before_recovery.inactivity_triggered);
+                }
+            }
+            (Op::Expire, Some(recovery)) => {
+                if recovery::expire(&mut vault, recovery, now).is_ok() {
+                    assert!(recovery::expired(
+                        &before,
+                        before_recovery.as_ref().unwrap(),
+                        now
+                    ));
+                    assert_eq!(recovery.status, RecoveryStatus::Expired);
+                }
+            }
+        }
+
+        assert!(vault.recovery_id >= before.recovery_id);
+        let Some(current) = &current else {
+            assert_eq!(vault.owner, before.owner);
+            continue;
+        };
+        if vault.owner != before.owner {
+            assert_eq!(current.status, RecoveryStatus::Executed);
+            assert!(!freeze::is_frozen(&before, now));
+        }
+        assert_eq!(
+            vault.active_recovery.is_some(),
+            current.status == RecoveryStatus::Pending
+        );
+        if before.active_recovery.is_some() && vault.active_recovery.is_some() {
+            assert_eq!(vault.recovery_id, before.recovery_id);
+        }
+        if current.status == RecoveryStatus::Pending {
+            assert!(current.approvals.len() <= guardian_count);
+            assert!(current
+                .approvals
+                .iter()
+                .all(|a| guardians.contains(&a.guardian)));
+        }
+    }
+});
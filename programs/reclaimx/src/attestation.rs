@@ -0,0 +1,241 @@
+// Guardian approvals signed off-chain. The Ed25519 and Secp256k1 precompiles
+// verify the signatures; this module only locates and parses what they verified.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program, keccak, secp256k1_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+use crate::{ErrorCode, Recovery, RecoveryStatus};
+
+// Prefix of every signed approval, so the signature cannot be replayed as
+// some other ed25519-signed message
+pub const APPROVAL_DOMAIN: &[u8] = b"reclaimx:approve:v1";
+
+// Ed25519 precompile layout: u8 count, u8 padding, then one offsets entry per
+// signature
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+// Offsets pointing at the precompile instruction's own data
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ApprovalMessage {
+    pub vault: Pubkey,
+    pub recovery_id: u64,
+    pub new_owner: Pubkey,
+    pub expiry: i64,
+}
+
+impl ApprovalMessage {
+    /// The exact bytes a guardian signs.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = APPROVAL_DOMAIN.to_vec();
+        self.serialize(&mut bytes)
+            .expect("approval message serializes");
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut payload = bytes.strip_prefix(APPROVAL_DOMAIN)?;
+        let message = Self::deserialize(&mut payload).ok()?;
+        payload.is_empty().then_some(message)
+    }
+}
+
+fn read_u16(data: &[u8], at: usize) -> Result<u16> {
+    data.get(at..at + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| error!(ErrorCode::InvalidAttestation))
+}
+
+/// (public key, message) pairs in one Ed25519 precompile instruction. Entries
+/// must reference the instruction's own data; offsets into other instructions
+/// would let a caller pair a verified signature with an unverified message.
+pub fn ed25519_entries(data: &[u8]) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+    let count = *data.first().ok_or(ErrorCode::InvalidAttestation)? as usize;
+    let mut entries = Vec::with_capacity(count);
+    for index in 0..count {
+        let start = SIGNATURE_OFFSETS_START + index * SIGNATURE_OFFSETS_SIZE;
+        let signature_instruction = read_u16(data, start + 2)?;
+        let pubkey_offset = read_u16(data, start + 4)? as usize;
+        let pubkey_instruction = read_u16(data, start + 6)?;
+        let message_offset = read_u16(data, start + 8)? as usize;
+        let message_size = read_u16(data, start + 10)? as usize;
+        let message_instruction = read_u16(data, start + 12)?;
+        require!(
+            signature_instruction == CURRENT_INSTRUCTION
+                && pubkey_instruction == CURRENT_INSTRUCTION
+                && message_instruction == CURRENT_INSTRUCTION,
+            ErrorCode::InvalidAttestation
+        );
+
+        let pubkey = data
+            .get(pubkey_offset..pubkey_offset + PUBKEY_SIZE)
+            .ok_or(ErrorCode::InvalidAttestation)?;
+        let message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(ErrorCode::InvalidAttestation)?;
+        entries.push((Pubkey::try_from(pubkey).unwrap(), message.to_vec()));
+    }
+    Ok(entries)
+}
+
+/// Approvals signed in Ed25519 precompile instructions that precede the
+/// current instruction. Signed messages that are not approvals are ignored.
+pub fn ed25519_approvals(instructions: &AccountInfo) -> Result<Vec<(Pubkey, ApprovalMessage)>> {
+    let current = load_current_index_checked(instructions)?;
+    let mut approvals = vec![];
+    for index in 0..current {
+        let ix = load_instruction_at_checked(index as usize, instructions)?;
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+        for (signer, message) in ed25519_entries(&ix.data)? {
+            if let Some(message) = ApprovalMessage::from_bytes(&message) {
+                approvals.push((signer, message));
+            }
+        }
+    }
+    Ok(approvals)
+}
+
+/// Checks that a signed approval is for this vault's pending `recovery` and
+/// has not expired.
+pub fn check_approval(
+    message: &ApprovalMessage,
+    vault_id: &Pubkey,
+    recovery: &Recovery,
+    now: i64,
+) -> Result<()> {
+    require_keys_eq!(message.vault, *vault_id, ErrorCode::InvalidVault);
+    require!(now <= message.expiry, ErrorCode::ApprovalExpired);
+    require!(
+        recovery.status == RecoveryStatus::Pending,
+        ErrorCode::NoActiveRecovery
+    );
+    require!(
+        message.recovery_id == recovery.recovery_id,
+        ErrorCode::InvalidRecoveryId
+    );
+    require_keys_eq!(
+        message.new_owner,
+        recovery.new_owner,
+        ErrorCode::InvalidAttestation
+    );
+    Ok(())
+}
+
+// EVM guardians sign EIP-712 typed data. The Secp256k1 precompile hashes the
+// 66-byte preimage `0x1901 || domainSeparator || structHash` and recovers the
+// signer, so the program only has to rebuild the preimage it expects.
+pub const EIP712_DOMAIN_TYPE: &[u8] = b"EIP712Domain(string name,string version,bytes32 salt)";
+pub const APPROVAL_TYPE: &[u8] =
+    b"Approval(bytes32 vault,uint256 recoveryId,bytes32 newOwner,uint256 expiry)";
+pub const EIP712_NAME: &[u8] = b"ReclaimX";
+pub const EIP712_VERSION: &[u8] = b"1";
+
+// Secp256k1 precompile layout: u8 count, then one offsets entry per signature
+const SECP_OFFSETS_START: usize = 1;
+const SECP_OFFSETS_SIZE: usize = 11;
+const ETH_ADDRESS_SIZE: usize = 20;
+
+/// abi.encode of a uint256 holding `value`.
+fn abi_uint(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// Domain separator, salted with the program id in place of a chain id.
+pub fn eip712_domain_separator() -> [u8; 32] {
+    keccak::hashv(&[
+        &keccak::hash(EIP712_DOMAIN_TYPE).0,
+        &keccak::hash(EIP712_NAME).0,
+        &keccak::hash(EIP712_VERSION).0,
+        crate::ID.as_ref(),
+    ])
+    .0
+}
+
+/// The bytes an EVM guardian's wallet signs for `message`. Expiries are
+/// encoded as uint256, so negative values are rejected.
+pub fn eip712_preimage(message: &ApprovalMessage) -> Result<[u8; 66]> {
+    let expiry =
+        u64::try_from(message.expiry).map_err(|_| error!(ErrorCode::InvalidAttestation))?;
+    let struct_hash = keccak::hashv(&[
+        &keccak::hash(APPROVAL_TYPE).0,
+        message.vault.as_ref(),
+        &abi_uint(message.recovery_id),
+        message.new_owner.as_ref(),
+        &abi_uint(expiry),
+    ])
+    .0;
+    let mut preimage = [0u8; 66];
+    preimage[..2].copy_from_slice(b"\x19\x01");
+    preimage[2..34].copy_from_slice(&eip712_domain_separator());
+    preimage[34..].copy_from_slice(&struct_hash);
+    Ok(preimage)
+}
+
+/// EVM guardians take part in approvals under their address left-padded to
+/// 32 bytes, the same encoding LayerZero uses for EVM peers.
+pub fn evm_guardian_key(address: &[u8; 20]) -> Pubkey {
+    let mut bytes = [0u8; 32];
+    bytes[12..].copy_from_slice(address);
+    Pubkey::new_from_array(bytes)
+}
+
+/// (Ethereum address, message) pairs in the Secp256k1 precompile instruction
+/// at `own_index`. Like the Ed25519 case, entries must reference the
+/// instruction's own data.
+pub fn secp256k1_entries(data: &[u8], own_index: u8) -> Result<Vec<([u8; 20], Vec<u8>)>> {
+    let count = *data.first().ok_or(ErrorCode::InvalidAttestation)? as usize;
+    let mut entries = Vec::with_capacity(count);
+    for index in 0..count {
+        let start = SECP_OFFSETS_START + index * SECP_OFFSETS_SIZE;
+        let offsets = data
+            .get(start..start + SECP_OFFSETS_SIZE)
+            .ok_or(ErrorCode::InvalidAttestation)?;
+        let signature_instruction = offsets[2];
+        let address_offset = u16::from_le_bytes([offsets[3], offsets[4]]) as usize;
+        let address_instruction = offsets[5];
+        let message_offset = u16::from_le_bytes([offsets[6], offsets[7]]) as usize;
+        let message_size = u16::from_le_bytes([offsets[8], offsets[9]]) as usize;
+        let message_instruction = offsets[10];
+        require!(
+            signature_instruction == own_index
+                && address_instruction == own_index
+                && message_instruction == own_index,
+            ErrorCode::InvalidAttestation
+        );
+
+        let address: [u8; 20] = data
+            .get(address_offset..address_offset + ETH_ADDRESS_SIZE)
+            .ok_or(ErrorCode::InvalidAttestation)?
+            .try_into()
+            .unwrap();
+        let message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(ErrorCode::InvalidAttestation)?;
+        entries.push((address, message.to_vec()));
+    }
+    Ok(entries)
+}
+
+/// Signed (address, message) pairs from Secp256k1 precompile instructions
+/// that precede the current instruction, in transaction order.
+pub fn secp256k1_signatures(instructions: &AccountInfo) -> Result<Vec<([u8; 20], Vec<u8>)>> {
+    let current = load_current_index_checked(instructions)?;
+    let mut signatures = vec![];
+    for index in 0..current {
+        let ix = load_instruction_at_checked(index as usize, instructions)?;
+        if ix.program_id != secp256k1_program::ID {
+            continue;
+        }
+        let own_index = u8::try_from(index).map_err(|_| error!(ErrorCode::InvalidAttestation))?;
+        signatures.extend(secp256k1_entries(&ix.data, own_index)?);
+    }
+    Ok(signatures)
+}
@@ -0,0 +1,138 @@
+// Pure rules for the singleton ProtocolConfig. The admin sets protocol-wide
+// LayerZero and timing parameters; handing the role over takes a proposal
+// from the current admin and an acceptance from the new key. The admin and the
+// security council can each pause any PauseScope.
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, PauseScope, ProtocolConfig, ETHEREUM_EID, LAYERZERO_ENDPOINT, SOLANA_EID};
+
+/// Arguments to `initialize_config` and `update_config`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ConfigParams {
+    pub endpoint_program: Pubkey,
+    pub allowed_eids: Vec<u32>,
+    pub local_eid: u32,
+    pub min_timelock: u64,
+    pub max_timelock: u64,
+    pub min_inactivity_period: u64,
+    pub max_inactivity_period: u64,
+    pub max_native_fee: u64,
+    pub stake_native_fee: u64,
+    pub security_council: Option<Pubkey>,
+}
+
+impl Default for ConfigParams {
+    fn default() -> Self {
+        Self {
+            endpoint_program: LAYERZERO_ENDPOINT,
+            allowed_eids: vec![ETHEREUM_EID],
+            local_eid: SOLANA_EID,
+            min_timelock: ProtocolConfig::DEFAULT_MIN_TIMELOCK,
+            max_timelock: ProtocolConfig::DEFAULT_MAX_TIMELOCK,
+            min_inactivity_period: ProtocolConfig::DEFAULT_MIN_INACTIVITY_PERIOD,
+            max_inactivity_period: ProtocolConfig::DEFAULT_MAX_INACTIVITY_PERIOD,
+            max_native_fee: ProtocolConfig::DEFAULT_MAX_NATIVE_FEE,
+            stake_native_fee: 0,
+            security_council: None,
+        }
+    }
+}
+
+impl PauseScope {
+    fn flag(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+impl ProtocolConfig {
+    /// Replaces every adjustable setting; the admin and paused scopes are untouched.
+    pub fn apply(&mut self, params: ConfigParams) -> Result<()> {
+        require!(
+            params.allowed_eids.len() <= ProtocolConfig::MAX_ALLOWED_EIDS,
+            ErrorCode::TooManyAllowedEids
+        );
+        require!(
+            params.min_timelock <= params.max_timelock,
+            ErrorCode::InvalidTimelockBounds
+        );
+        require!(
+            params.min_inactivity_period <= params.max_inactivity_period,
+            ErrorCode::InvalidInactivityBounds
+        );
+        self.endpoint_program = params.endpoint_program;
+        self.allowed_eids = params.allowed_eids;
+        self.local_eid = params.local_eid;
+        self.min_timelock = params.min_timelock;
+        self.max_timelock = params.max_timelock;
+        self.min_inactivity_period = params.min_inactivity_period;
+        self.max_inactivity_period = params.max_inactivity_period;
+        self.max_native_fee = params.max_native_fee;
+        self.stake_native_fee = params.stake_native_fee;
+        self.security_council = params.security_council;
+        Ok(())
+    }
+
+    pub fn require_admin(&self, signer: Pubkey) -> Result<()> {
+        require!(signer == self.admin, ErrorCode::Unauthorized);
+        Ok(())
+    }
+
+    /// Checks a vault timelock and inactivity period against the configured bounds.
+    pub fn require_valid_timing(&self, timelock: u64, inactivity_period: u64) -> Result<()> {
+        require!(
+            (self.min_timelock..=self.max_timelock).contains(&timelock)
+                && (self.min_inactivity_period..=self.max_inactivity_period)
+                    .contains(&inactivity_period),
+            ErrorCode::TimingOutOfBounds
+        );
+        Ok(())
+    }
+
+    /// Whether `scope`, or the whole protocol, is paused.
+    pub fn is_paused(&self, scope: PauseScope) -> bool {
+        self.paused_scopes & (scope.flag() | PauseScope::All.flag()) != 0
+    }
+
+    pub fn require_not_paused(&self, scope: PauseScope) -> Result<()> {
+        require!(!self.is_paused(scope), ErrorCode::ProtocolPaused);
+        Ok(())
+    }
+
+    /// Pauses or resumes `scope` alone; resuming `All` leaves narrower pauses
+    /// in force.
+    pub fn set_paused(&mut self, signer: Pubkey, scope: PauseScope, paused: bool) -> Result<()> {
+        require!(
+            signer == self.admin || self.security_council == Some(signer),
+            ErrorCode::Unauthorized
+        );
+        if paused {
+            self.paused_scopes |= scope.flag();
+        } else {
+            self.paused_scopes &= !scope.flag();
+        }
+        Ok(())
+    }
+
+    /// Whether messages may be sent to or received from `eid`.
+    pub fn is_allowed_eid(&self, eid: u32) -> bool {
+        self.allowed_eids.contains(&eid)
+    }
+
+    /// Starts handing the admin role to `new_admin`, or withdraws a pending
+    /// proposal when `None`.
+    pub fn propose_admin(&mut self, signer: Pubkey, new_admin: Option<Pubkey>) -> Result<()> {
+        self.require_admin(signer)?;
+        self.pending_admin = new_admin;
+        Ok(())
+    }
+
+    /// Completes a handover; only the proposed key can accept. Returns the
+    /// previous admin.
+    pub fn accept_admin(&mut self, signer: Pubkey) -> Result<Pubkey> {
+        require!(self.pending_admin == Some(signer), ErrorCode::Unauthorized);
+        let previous = self.admin;
+        self.admin = signer;
+        self.pending_admin = None;
+        Ok(previous)
+    }
+}
@@ -0,0 +1,122 @@
+// Pure emergency-freeze transitions. The owner acts alone; guardians vote and
+// act once `threshold` of them agree. Every freeze lapses on its own so no
+// single key can hold the vault indefinitely.
+use anchor_lang::prelude::*;
+
+use crate::{recovery, ErrorCode, GuardianApproval, Vault};
+
+// Longest a freeze lasts before the vault unfreezes by itself
+pub const MAX_FREEZE_DURATION: i64 = 7 * 24 * 60 * 60;
+// Time after a freeze ends before the owner may freeze again, so a stolen
+// owner key cannot keep a recovery from ever executing
+pub const OWNER_FREEZE_COOLDOWN: i64 = MAX_FREEZE_DURATION;
+
+#[derive(Clone, Copy)]
+pub enum FreezeOutcome {
+    Voted { votes: usize },
+    Frozen { until: i64 },
+}
+
+#[derive(Clone, Copy)]
+pub enum UnfreezeOutcome {
+    Voted { votes: usize },
+    Unfrozen,
+}
+
+pub fn is_frozen(vault: &Vault, now: i64) -> bool {
+    now < vault.frozen_until
+}
+
+pub fn require_not_frozen(vault: &Vault, now: i64) -> Result<()> {
+    require!(!is_frozen(vault, now), ErrorCode::VaultFrozen);
+    Ok(())
+}
+
+// Votes needed from guardians; EVM guardians cannot sign Solana transactions
+pub fn quorum(vault: &Vault) -> usize {
+    (vault.threshold as usize).min(vault.guardians.len())
+}
+
+// Records a guardian vote and returns the number of votes inside the approval window
+fn vote(
+    guardians: &[Pubkey],
+    window: u64,
+    votes: &mut Vec<GuardianApproval>,
+    guardian: Pubkey,
+    now: i64,
+) -> Result<usize> {
+    require!(guardians.contains(&guardian), ErrorCode::Unauthorized);
+    match votes.iter_mut().find(|vote| vote.guardian == guardian) {
+        Some(vote) if !recovery::timelock_elapsed(vote.timestamp, window, now) => {
+            return err!(ErrorCode::DuplicateApproval);
+        }
+        Some(vote) => vote.timestamp = now,
+        None => votes.push(GuardianApproval {
+            guardian,
+            timestamp: now,
+        }),
+    }
+    Ok(recovery::live_approvals(votes, window, now))
+}
+
+fn set_frozen(vault: &mut Vault, until: i64, by_guardians: bool) {
+    vault.frozen_until = until;
+    vault.frozen_by_guardians = by_guardians;
+    vault.freeze_votes.clear();
+    vault.unfreeze_votes.clear();
+}
+
+/// Freezes the vault for `MAX_FREEZE_DURATION` when `signer` is the owner or
+/// casts the vote that completes a guardian quorum.
+pub fn freeze(vault: &mut Vault, signer: Pubkey, now: i64) -> Result<FreezeOutcome> {
+    require_not_frozen(vault, now)?;
+    let until = now.saturating_add(MAX_FREEZE_DURATION);
+
+    if signer == vault.owner {
+        require!(
+            vault.frozen_until == 0
+                || now >= vault.frozen_until.saturating_add(OWNER_FREEZE_COOLDOWN),
+            ErrorCode::FreezeCooldown
+        );
+        set_frozen(vault, until, false);
+        return Ok(FreezeOutcome::Frozen { until });
+    }
+
+    let count = vote(
+        &vault.guardians,
+        vault.approval_window,
+        &mut vault.freeze_votes,
+        signer,
+        now,
+    )?;
+    if count >= quorum(vault) {
+        set_frozen(vault, until, true);
+        return Ok(FreezeOutcome::Frozen { until });
+    }
+    Ok(FreezeOutcome::Voted { votes: count })
+}
+
+/// Lifts a freeze early. The owner may only lift a freeze they imposed
+/// themselves; a guardian freeze needs a guardian quorum.
+pub fn unfreeze(vault: &mut Vault, signer: Pubkey, now: i64) -> Result<UnfreezeOutcome> {
+    require!(is_frozen(vault, now), ErrorCode::VaultNotFrozen);
+
+    if signer == vault.owner {
+        require!(!vault.frozen_by_guardians, ErrorCode::Unauthorized);
+        set_frozen(vault, now, false);
+        return Ok(UnfreezeOutcome::Unfrozen);
+    }
+
+    let count = vote(
+        &vault.guardians,
+        vault.approval_window,
+        &mut vault.unfreeze_votes,
+        signer,
+        now,
+    )?;
+    if count >= quorum(vault) {
+        set_frozen(vault, now, false);
+        return Ok(UnfreezeOutcome::Unfrozen);
+    }
+    Ok(UnfreezeOutcome::Voted { votes: count })
+}
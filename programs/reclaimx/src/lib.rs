@@ -1,9 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
 use oapp_solana_sdk::endpoint::{
-    instructions::{RegisterOAppParams, SendParams, QuoteParams},
-    cpi::{register_oapp, send, quote},
-    accounts::{RegisterOApp, Send, Quote},
+    instructions::{RegisterOAppParams, SendParams, QuoteParams, ClearParams},
+    cpi::{register_oapp, send, quote, clear},
+    accounts::{RegisterOApp, Send, Quote, Clear},
 };
 use oapp_solana_sdk::oft::{
     instructions::{SendOFTParams, QuoteOFTParams},
@@ -12,12 +12,27 @@ use oapp_solana_sdk::oft::{
 };
 use solana_program::pubkey::Pubkey;
 
+pub mod attestation;
+pub mod config;
+pub mod freeze;
+pub mod ownership;
+pub mod recovery;
+pub mod spending;
+pub mod timing;
+pub mod withdrawal;
+
+use attestation::ApprovalMessage;
+use config::ConfigParams;
+use freeze::{FreezeOutcome, UnfreezeOutcome};
+use recovery::{ApprovalOutcome, CancelOutcome};
+
 declare_id!("YourDeployedReclaimXVaultProgramId"); // Replace post-deployment
 
-// LayerZero V2 Solana Devnet Endpoint - Official Address
+// LayerZero V2 Solana Devnet Endpoint - Official Address. Only the default for
+// the protocol config; instructions check the configured endpoint program.
 pub const LAYERZERO_ENDPOINT: Pubkey = solana_program::pubkey!("76y77prsiCMvXMjuoZ5VRrhG5qYBrUMYTE5WgHqgjEn6");
 
-// LayerZero V2 Chain IDs
+// LayerZero V2 Chain IDs, the config defaults for allowed and local EIDs
 pub const ETHEREUM_EID: u32 = 30101; // Ethereum Mainnet
 pub const SOLANA_EID: u32 = 40168;   // Solana Devnet
 pub const SEPOLIA_EID: u32 = 40161;  // Sepolia Testnet (for testing)
@@ -26,8 +41,12 @@ pub const SEPOLIA_EID: u32 = 40161;  // Sepolia Testnet (for testing)
 pub mod reclaimx {
     use super::*;
 
-    pub fn initialize_vault(
-        ctx: Context<InitializeVault>,
+    // `vault_seed` tells apart vaults created by the same key.
+    // remaining_accounts: the token account of each asset, in order; each must
+    // already belong to the vault and hold the asset's amount.
+    pub fn initialize_vault<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitializeVault<'info>>,
+        vault_seed: u64,
         guardians: Vec<Pubkey>,
         threshold: u8,
         timelock: u64,
@@ -36,8 +55,15 @@ pub mod reclaimx {
         stake_amount: u64,
         assets: Vec<Asset>,
     ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        // Registering with the endpoint is an outbound LayerZero call
+        config.require_not_paused(PauseScope::OutboundLayerZero)?;
+        require!(ctx.accounts.endpoint_program.key() == config.endpoint_program, ErrorCode::InvalidEndpoint);
+
         let vault = &mut ctx.accounts.vault;
         require!(guardians.len() >= threshold as usize && threshold > 0, ErrorCode::InvalidThreshold);
+        config.require_valid_timing(timelock, inactivity_period)?;
+        check_assets(&vault.key(), &assets, ctx.remaining_accounts)?;
         
         vault.owner = ctx.accounts.owner.key();
         vault.guardians = guardians;
@@ -47,9 +73,23 @@ pub mod reclaimx {
         vault.backup_wallet = backup_wallet;
         vault.stake_amount = stake_amount;
         vault.assets = assets;
-        vault.recovery_state = RecoveryState::None;
+        vault.active_recovery = None;
         vault.last_active_timestamp = Clock::get()?.unix_timestamp;
         vault.recovery_id = 0;
+        vault.heartbeat_keys = vec![];
+        vault.evm_guardians = vec![];
+        vault.recovery_expiry = timelock.saturating_add(Vault::DEFAULT_RECOVERY_EXPIRY);
+        vault.approval_window = vault.recovery_expiry;
+        vault.frozen_until = 0;
+        vault.frozen_by_guardians = false;
+        vault.freeze_votes = vec![];
+        vault.unfreeze_votes = vec![];
+        vault.spending_limits = vec![];
+        vault.withdrawal_id = 0;
+        vault.pending_timing = None;
+        vault.creator = ctx.accounts.owner.key();
+        vault.vault_seed = vault_seed;
+        vault.pending_ownership = None;
         vault.bump = ctx.bumps.vault;
 
         // Register as OApp with LayerZero endpoint
@@ -69,6 +109,18 @@ pub mod reclaimx {
 
         register_oapp(cpi_ctx, register_params)?;
 
+        let vault = &ctx.accounts.vault;
+        emit_cpi!(VaultInitializedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: vault.last_active_timestamp,
+            guardian_count: vault.guardians.len() as u8,
+            threshold: vault.threshold,
+            creator: vault.creator,
+            vault_seed,
+        });
+
         msg!("Vault initialized with {} guardians, threshold: {}", vault.guardians.len(), vault.threshold);
         Ok(())
     }
@@ -79,21 +131,35 @@ pub mod reclaimx {
         dst_eid: u32,
         options: Vec<u8>,
     ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        config.require_not_paused(PauseScope::OutboundLayerZero)?;
+        require!(ctx.accounts.endpoint_program.key() == config.endpoint_program, ErrorCode::InvalidEndpoint);
+        require!(config.is_allowed_eid(dst_eid), ErrorCode::UnsupportedEid);
+
         let vault = &mut ctx.accounts.vault;
         require!(
             ctx.accounts.initiator.key() == vault.owner || 
             vault.guardians.contains(&ctx.accounts.initiator.key()), 
             ErrorCode::Unauthorized
         );
+        if ctx.accounts.initiator.key() == vault.owner {
+            vault.record_activity()?;
+        }
 
-        vault.recovery_id += 1;
-        vault.recovery_state = RecoveryState::Pending {
+        let now = Clock::get()?.unix_timestamp;
+        let recovery_key = ctx.accounts.recovery.key();
+        let recovery = &mut ctx.accounts.recovery;
+        recovery.vault = vault.key();
+        recovery.bump = ctx.bumps.recovery;
+        recovery::start(
+            vault,
+            recovery,
+            recovery_key,
+            ctx.accounts.initiator.key(),
             new_owner,
-            approvals: vec![],
-            malicious_approvals: vec![],
-            start_timestamp: Clock::get()?.unix_timestamp,
-            recovery_id: vault.recovery_id,
-        };
+            now,
+            false,
+        )?;
 
         // Prepare cross-chain message payload
         let message = RecoveryMessage {
@@ -121,6 +187,10 @@ pub mod reclaimx {
         );
 
         let messaging_fee = quote(quote_ctx, quote_params)?;
+        require!(
+            messaging_fee.native_fee <= ctx.accounts.config.max_native_fee,
+            ErrorCode::MessagingFeeTooHigh
+        );
 
         // Send cross-chain message
         let send_params = SendParams {
@@ -132,9 +202,11 @@ pub mod reclaimx {
             lz_token_fee: messaging_fee.lz_token_fee,
         };
 
+        let vault_seed = vault.vault_seed.to_le_bytes();
         let vault_seeds = &[
             b"vault",
-            vault.owner.as_ref(),
+            vault.creator.as_ref(),
+            &vault_seed,
             &[vault.bump],
         ];
         let signer_seeds = &[&vault_seeds[..]];
@@ -151,95 +223,278 @@ pub mod reclaimx {
 
         send(send_ctx, send_params)?;
 
+        emit_cpi!(RecoveryInitiatedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: ctx.accounts.initiator.key(),
+            timestamp: now,
+            new_owner,
+            src_eid: ctx.accounts.config.local_eid,
+        });
+
         msg!("Recovery initiated for vault: {}, new owner: {}, recovery_id: {}", 
              vault.key(), new_owner, vault.recovery_id);
 
         Ok(())
     }
 
-    pub fn approve_recovery(ctx: Context<ApproveRecovery>) -> Result<()> {
+    // remaining_accounts: per vault asset, in order, the vault token account
+    // and a token account of the new owner for the same mint. Only read when
+    // this approval completes the recovery. While recovery execution is paused
+    // the approval is only recorded and execute_recovery finishes the recovery.
+    pub fn approve_recovery<'info>(ctx: Context<'_, '_, 'info, 'info, ApproveRecovery<'info>>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let guardian = ctx.accounts.guardian.key();
         let vault = &mut ctx.accounts.vault;
-        require!(vault.guardians.contains(&ctx.accounts.guardian.key()), ErrorCode::Unauthorized);
-
-        if let RecoveryState::Pending { 
-            approvals, 
-            malicious_approvals, 
-            new_owner, 
-            start_timestamp, 
-            recovery_id 
-        } = &mut vault.recovery_state {
-            require!(!approvals.contains(&ctx.accounts.guardian.key()), ErrorCode::DuplicateApproval);
-            approvals.push(ctx.accounts.guardian.key());
-
-            msg!("Guardian {} approved recovery. Approvals: {}/{}", 
-                 ctx.accounts.guardian.key(), approvals.len(), vault.threshold);
-
-            // Check if threshold is met and timelock has passed
-            if approvals.len() >= vault.threshold as usize && 
-               Clock::get()?.unix_timestamp >= start_timestamp + vault.timelock {
-                
-                vault.owner = *new_owner;
-                vault.recovery_state = RecoveryState::Completed;
-                
+        let now = Clock::get()?.unix_timestamp;
+        let vault_id = vault.key();
+        let recovery = &mut ctx.accounts.recovery;
+        let recovery_id = recovery.recovery_id;
+
+        let outcome = if ctx.accounts.config.is_paused(PauseScope::RecoveryExecution) {
+            let approvals = recovery::record_approval(vault, recovery, guardian, now)?;
+            ApprovalOutcome::Pending { approvals }
+        } else {
+            recovery::approve(vault, recovery, guardian, now)?
+        };
+        let approvals = match outcome {
+            ApprovalOutcome::Pending { approvals } | ApprovalOutcome::Completed { approvals, .. } => approvals,
+        };
+        emit_cpi!(RecoveryApprovedEvent {
+            vault_id,
+            recovery_id,
+            actor: guardian,
+            timestamp: now,
+            approvals: approvals as u8,
+        });
+
+        match outcome {
+            ApprovalOutcome::Pending { approvals } => {
+                msg!("Guardian {} approved recovery. Approvals: {}/{}", 
+                     guardian, approvals, vault.threshold);
+            }
+            ApprovalOutcome::Completed { new_owner, .. } => {
                 msg!("Recovery completed! New owner: {}", new_owner);
+                emit_cpi!(RecoveryExecutedEvent {
+                    vault_id,
+                    recovery_id,
+                    actor: guardian,
+                    timestamp: now,
+                    new_owner,
+                });
 
                 // Transfer assets to new owner
-                for asset in &vault.assets {
-                    match asset.asset_type {
-                        AssetType::Token => {
-                            let cpi_accounts = Transfer {
-                                from: ctx.accounts.vault_token_account.to_account_info(),
-                                to: ctx.accounts.new_owner_token_account.to_account_info(),
-                                authority: ctx.accounts.vault.to_account_info(),
-                            };
-                            let vault_seeds = &[
-                                b"vault",
-                                vault.owner.as_ref(),
-                                &[vault.bump],
-                            ];
-                            let signer_seeds = &[&vault_seeds[..]];
-                            let cpi_ctx = CpiContext::new_with_signer(
-                                ctx.accounts.token_program.to_account_info(),
-                                cpi_accounts,
-                                signer_seeds,
-                            );
-                            token::transfer(cpi_ctx, asset.amount)?;
-                        }
-                        AssetType::NFT => {
-                            let cpi_accounts = Transfer {
-                                from: ctx.accounts.vault_token_account.to_account_info(),
-                                to: ctx.accounts.new_owner_token_account.to_account_info(),
-                                authority: ctx.accounts.vault.to_account_info(),
-                            };
-                            let vault_seeds = &[
-                                b"vault",
-                                vault.owner.as_ref(),
-                                &[vault.bump],
-                            ];
-                            let signer_seeds = &[&vault_seeds[..]];
-                            let cpi_ctx = CpiContext::new_with_signer(
-                                ctx.accounts.token_program.to_account_info(),
-                                cpi_accounts,
-                                signer_seeds,
-                            );
-                            token::transfer(cpi_ctx, 1)?;
-                        }
-                    }
+                let transfers = transfer_assets(
+                    &ctx.accounts.vault,
+                    new_owner,
+                    ctx.remaining_accounts,
+                    &ctx.accounts.token_program,
+                )?;
+                for (mint, amount, to) in transfers {
+                    emit_cpi!(AssetTransferredEvent {
+                        vault_id,
+                        recovery_id,
+                        actor: guardian,
+                        timestamp: now,
+                        mint,
+                        amount,
+                        to,
+                    });
                 }
             }
-        } else {
-            return err!(ErrorCode::NoActiveRecovery);
         }
 
         Ok(())
     }
 
+    // Permissionless: anyone may finish a recovery once approvals and timelock are satisfied.
+    // remaining_accounts carry the asset account pairs, as for approve_recovery.
+    pub fn execute_recovery<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteRecovery<'info>>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::RecoveryExecution)?;
+        let cranker = ctx.accounts.cranker.key();
+        let vault = &mut ctx.accounts.vault;
+        let now = Clock::get()?.unix_timestamp;
+        let vault_id = vault.key();
+        let recovery = &mut ctx.accounts.recovery;
+        let recovery_id = recovery.recovery_id;
+
+        let new_owner = recovery::execute(vault, recovery, now)?;
+        emit_cpi!(RecoveryExecutedEvent {
+            vault_id,
+            recovery_id,
+            actor: cranker,
+            timestamp: now,
+            new_owner,
+        });
+        msg!("Recovery {} executed by {}. New owner: {}", recovery_id, cranker, new_owner);
+
+        let reward = match ctx.accounts.crank_reserve.as_mut() {
+            Some(reserve) => pay_crank_bounty(reserve, &ctx.accounts.cranker, CrankKind::ExecuteRecovery, recovery_id)?,
+            None => 0,
+        };
+        if reward > 0 {
+            emit_cpi!(CrankRewardedEvent {
+                vault_id,
+                recovery_id,
+                actor: cranker,
+                timestamp: now,
+                amount: reward,
+            });
+        }
+
+        let transfers = transfer_assets(
+            &ctx.accounts.vault,
+            new_owner,
+            ctx.remaining_accounts,
+            &ctx.accounts.token_program,
+        )?;
+        for (mint, amount, to) in transfers {
+            emit_cpi!(AssetTransferredEvent {
+                vault_id,
+                recovery_id,
+                actor: cranker,
+                timestamp: now,
+                mint,
+                amount,
+                to,
+            });
+        }
+        Ok(())
+    }
+
+    // A relayer submits guardian approvals signed off-chain and verified by
+    // Ed25519 precompile instructions earlier in the same transaction.
+    // Execution is left to execute_recovery.
+    pub fn submit_approvals(ctx: Context<SubmitApprovals>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &ctx.accounts.vault;
+        let vault_id = vault.key();
+        let recovery = &mut ctx.accounts.recovery;
+        let now = Clock::get()?.unix_timestamp;
+
+        let signed = attestation::ed25519_approvals(&ctx.accounts.instructions)?;
+        require!(!signed.is_empty(), ErrorCode::InvalidAttestation);
+        for (guardian, message) in &signed {
+            attestation::check_approval(message, &vault_id, recovery, now)?;
+            let approvals = recovery::record_approval(vault, recovery, *guardian, now)?;
+            emit_cpi!(RecoveryApprovedEvent {
+                vault_id,
+                recovery_id: message.recovery_id,
+                actor: *guardian,
+                timestamp: now,
+                approvals: approvals as u8,
+            });
+        }
+
+        msg!("Relayer {} submitted {} guardian approvals for vault {}",
+             ctx.accounts.relayer.key(), signed.len(), vault_id);
+        Ok(())
+    }
+
+    // Approvals from EVM guardians, signed as EIP-712 typed data and verified
+    // by Secp256k1 precompile instructions earlier in the transaction.
+    // `expiries` holds each signature's expiry, in transaction order.
+    pub fn submit_evm_approvals(ctx: Context<SubmitApprovals>, expiries: Vec<i64>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &ctx.accounts.vault;
+        let vault_id = vault.key();
+        let recovery = &mut ctx.accounts.recovery;
+        let now = Clock::get()?.unix_timestamp;
+        let (recovery_id, new_owner) = (recovery.recovery_id, recovery.new_owner);
+
+        let signatures = attestation::secp256k1_signatures(&ctx.accounts.instructions)?;
+        require!(
+            !signatures.is_empty() && signatures.len() == expiries.len(),
+            ErrorCode::InvalidAttestation
+        );
+        for ((address, signed), expiry) in signatures.iter().zip(expiries) {
+            require!(vault.evm_guardians.contains(address), ErrorCode::Unauthorized);
+            let message = ApprovalMessage { vault: vault_id, recovery_id, new_owner, expiry };
+            require!(
+                signed[..] == attestation::eip712_preimage(&message)?[..],
+                ErrorCode::InvalidAttestation
+            );
+            attestation::check_approval(&message, &vault_id, recovery, now)?;
+
+            let guardian = attestation::evm_guardian_key(address);
+            let approvals = recovery::record_approval(vault, recovery, guardian, now)?;
+            emit_cpi!(RecoveryApprovedEvent {
+                vault_id,
+                recovery_id,
+                actor: guardian,
+                timestamp: now,
+                approvals: approvals as u8,
+            });
+        }
+
+        msg!("Relayer {} submitted {} EVM guardian approvals for vault {}",
+             ctx.accounts.relayer.key(), signatures.len(), vault_id);
+        Ok(())
+    }
+
+    pub fn add_evm_guardian(ctx: Context<AddEvmGuardian>, evm_guardian: [u8; 20]) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+        freeze::require_not_frozen(vault, Clock::get()?.unix_timestamp)?;
+        require!(
+            !vault.evm_guardians.contains(&evm_guardian),
+            ErrorCode::DuplicateEvmGuardian
+        );
+        require!(
+            vault.evm_guardians.len() < Vault::MAX_EVM_GUARDIANS,
+            ErrorCode::TooManyEvmGuardians
+        );
+
+        vault.evm_guardians.push(evm_guardian);
+        vault.record_activity()?;
+        emit_cpi!(EvmGuardianAddedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: vault.last_active_timestamp,
+            evm_guardian,
+        });
+        msg!("EVM guardian 0x{} added to vault {}", hex_address(&evm_guardian), vault.key());
+        Ok(())
+    }
+
+    pub fn remove_evm_guardian(ctx: Context<RemoveEvmGuardian>, evm_guardian: [u8; 20]) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+        freeze::require_not_frozen(vault, Clock::get()?.unix_timestamp)?;
+
+        let index = vault
+            .evm_guardians
+            .iter()
+            .position(|address| *address == evm_guardian)
+            .ok_or(ErrorCode::EvmGuardianNotFound)?;
+        vault.evm_guardians.remove(index);
+        vault.record_activity()?;
+        emit_cpi!(EvmGuardianRemovedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: vault.last_active_timestamp,
+            evm_guardian,
+        });
+        msg!("EVM guardian 0x{} removed from vault {}", hex_address(&evm_guardian), vault.key());
+        Ok(())
+    }
+
     pub fn stake_guardian_oft(
         ctx: Context<StakeGuardianOFT>,
         amount: u64,
         dst_eid: u32,
         options: Vec<u8>,
     ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        config.require_not_paused(PauseScope::Staking)?;
+        config.require_not_paused(PauseScope::OutboundLayerZero)?;
+        require!(*ctx.accounts.endpoint.owner == config.endpoint_program, ErrorCode::InvalidEndpoint);
+        require!(config.is_allowed_eid(dst_eid), ErrorCode::UnsupportedEid);
+
         let vault = &ctx.accounts.vault;
         require!(vault.guardians.contains(&ctx.accounts.guardian.key()), ErrorCode::Unauthorized);
         require!(amount >= vault.stake_amount, ErrorCode::InsufficientStake);
@@ -251,7 +506,7 @@ pub mod reclaimx {
             amount_ld: amount,
             min_amount_ld: amount,
             options,
-            native_fee: 0, // Will be calculated
+            native_fee: config.stake_native_fee,
             lz_token_fee: 0,
         };
 
@@ -268,77 +523,548 @@ pub mod reclaimx {
 
         send_oft(cpi_ctx, send_params)?;
 
+        emit_cpi!(GuardianStakedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: ctx.accounts.guardian.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+            amount,
+            dst_eid,
+        });
+
         msg!("Guardian {} staked {} RXOFT tokens via OFT", ctx.accounts.guardian.key(), amount);
         Ok(())
     }
 
     pub fn mark_malicious(ctx: Context<MarkMalicious>, guardian: Pubkey) -> Result<()> {
-        let vault = &mut ctx.accounts.vault;
-        require!(vault.guardians.contains(&ctx.accounts.signer.key()), ErrorCode::Unauthorized);
-
-        if let RecoveryState::Pending { malicious_approvals, .. } = &mut vault.recovery_state {
-            require!(!malicious_approvals.contains(&ctx.accounts.signer.key()), ErrorCode::DuplicateApproval);
-            malicious_approvals.push(ctx.accounts.signer.key());
-
-            msg!("Guardian {} marked as malicious by {}. Malicious votes: {}", 
-                 guardian, ctx.accounts.signer.key(), malicious_approvals.len());
-
-            // If 3 or more guardians mark as malicious, slash the guardian
-            if malicious_approvals.len() >= 3 {
-                // Slash guardian by burning their staked tokens via OFT
-                msg!("Guardian {} slashed for malicious behavior!", guardian);
-                
-                // Emit event for off-chain slashing process
-                emit!(GuardianSlashedEvent {
-                    vault_id: vault.key(),
-                    guardian,
-                    amount: vault.stake_amount,
-                });
-            }
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let signer = ctx.accounts.signer.key();
+        let vault = &ctx.accounts.vault;
+        let recovery = &mut ctx.accounts.recovery;
+        let votes = recovery::mark_malicious(vault, recovery, signer)?;
+        let now = Clock::get()?.unix_timestamp;
+
+        emit_cpi!(MaliciousVoteEvent {
+            vault_id: vault.key(),
+            recovery_id: recovery.recovery_id,
+            actor: signer,
+            timestamp: now,
+            guardian,
+            votes: votes as u8,
+        });
+
+        msg!("Guardian {} marked as malicious by {}. Malicious votes: {}", 
+             guardian, signer, votes);
+
+        // If 3 or more guardians mark as malicious, slash the guardian
+        if votes >= recovery::SLASH_VOTES {
+            // Slash guardian by burning their staked tokens via OFT
+            msg!("Guardian {} slashed for malicious behavior!", guardian);
+            
+            // Emit event for off-chain slashing process
+            emit_cpi!(GuardianSlashedEvent {
+                vault_id: vault.key(),
+                guardian,
+                amount: vault.stake_amount,
+                recovery_id: recovery.recovery_id,
+                actor: signer,
+                timestamp: now,
+            });
         }
         Ok(())
     }
 
+    // Permissionless crank; the cranker pays for the new Recovery account and is
+    // paid from the vault's crank reserve, if any
     pub fn check_inactivity(ctx: Context<CheckInactivity>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let cranker = ctx.accounts.cranker.key();
         let vault = &mut ctx.accounts.vault;
+        let vault_id = vault.key();
         let current_time = Clock::get()?.unix_timestamp;
 
-        if current_time >= vault.last_active_timestamp + vault.inactivity_period {
-            vault.recovery_id += 1;
-            vault.recovery_state = RecoveryState::Pending {
-                new_owner: vault.backup_wallet,
-                approvals: vec![],
-                malicious_approvals: vec![],
-                start_timestamp: current_time,
-                recovery_id: vault.recovery_id,
-            };
-
-            msg!("Inactivity detected! Auto-recovery initiated to backup wallet: {}", vault.backup_wallet);
+        let recovery_key = ctx.accounts.recovery.key();
+        let recovery = &mut ctx.accounts.recovery;
+        recovery.vault = vault_id;
+        recovery.bump = ctx.bumps.recovery;
+        let recovery_id = recovery::check_inactivity(vault, recovery, recovery_key, cranker, current_time)?;
+        emit_cpi!(InactivityTriggeredEvent {
+            vault_id,
+            recovery_id,
+            actor: cranker,
+            timestamp: current_time,
+            backup_wallet: vault.backup_wallet,
+        });
+        msg!("Inactivity detected! Auto-recovery initiated to backup wallet: {}", vault.backup_wallet);
+
+        let reward = match ctx.accounts.crank_reserve.as_mut() {
+            Some(reserve) => pay_crank_bounty(reserve, &ctx.accounts.cranker, CrankKind::CheckInactivity, recovery_id)?,
+            None => 0,
+        };
+        if reward > 0 {
+            emit_cpi!(CrankRewardedEvent {
+                vault_id,
+                recovery_id,
+                actor: cranker,
+                timestamp: current_time,
+                amount: reward,
+            });
+        }
+        Ok(())
+    }
+
+    // Permissionless crank that clears a pending recovery past its deadline
+    pub fn expire_recovery(ctx: Context<ExpireRecovery>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let cranker = ctx.accounts.cranker.key();
+        let vault = &mut ctx.accounts.vault;
+        let vault_id = vault.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        let recovery_id = recovery::expire(vault, &mut ctx.accounts.recovery, now)?;
+        emit_cpi!(RecoveryExpiredEvent {
+            vault_id,
+            recovery_id,
+            actor: cranker,
+            timestamp: now,
+        });
+        msg!("Recovery {} for vault {} expired", recovery_id, vault_id);
+
+        let reward = match ctx.accounts.crank_reserve.as_mut() {
+            Some(reserve) => pay_crank_bounty(reserve, &ctx.accounts.cranker, CrankKind::ExpireRecovery, recovery_id)?,
+            None => 0,
+        };
+        if reward > 0 {
+            emit_cpi!(CrankRewardedEvent {
+                vault_id,
+                recovery_id,
+                actor: cranker,
+                timestamp: now,
+                amount: reward,
+            });
         }
         Ok(())
     }
 
+    // Deadlines cannot change mid-recovery, so a compromised owner key cannot
+    // shorten them to run out a recovery that is already underway
+    pub fn set_recovery_deadlines(
+        ctx: Context<SetRecoveryDeadlines>,
+        recovery_expiry: u64,
+        approval_window: u64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+        require!(vault.active_recovery.is_none(), ErrorCode::RecoveryInProgress);
+        require!(
+            recovery_expiry > vault.timelock && approval_window > 0,
+            ErrorCode::InvalidRecoveryDeadline
+        );
+
+        vault.recovery_expiry = recovery_expiry;
+        vault.approval_window = approval_window;
+        vault.record_activity()?;
+        emit_cpi!(RecoveryDeadlinesSetEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: vault.last_active_timestamp,
+            recovery_expiry,
+            approval_window,
+        });
+        msg!("Recovery expiry set to {}s and approval window to {}s for vault {}",
+             recovery_expiry, approval_window, vault.key());
+        Ok(())
+    }
+
+    // Queues a new timelock and inactivity period; they apply once the current timelock has passed
+    pub fn update_timing(ctx: Context<UpdateTiming>, timelock: u64, inactivity_period: u64) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+        let now = Clock::get()?.unix_timestamp;
+
+        timing::propose(vault, &ctx.accounts.config, timelock, inactivity_period, now)?;
+        vault.record_activity()?;
+        emit_cpi!(TimingUpdateQueuedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: now,
+            timelock,
+            inactivity_period,
+        });
+        msg!("Timelock {}s and inactivity period {}s queued for vault {}",
+             timelock, inactivity_period, vault.key());
+        Ok(())
+    }
+
+    // Anyone may apply a queued timing change once its delay has passed
+    pub fn apply_timing(ctx: Context<ApplyTiming>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        let now = Clock::get()?.unix_timestamp;
+
+        let applied = timing::apply(vault, &ctx.accounts.config, now)?;
+        emit_cpi!(TimingUpdatedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: ctx.accounts.cranker.key(),
+            timestamp: now,
+            timelock: applied.timelock,
+            inactivity_period: applied.inactivity_period,
+        });
+        msg!("Vault {} now has timelock {}s and inactivity period {}s",
+             vault.key(), applied.timelock, applied.inactivity_period);
+        Ok(())
+    }
+
+    // Starts handing the vault to `new_owner`, who accepts once the transfer delay has passed
+    pub fn transfer_ownership(ctx: Context<TransferOwnership>, new_owner: Pubkey) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+        let now = Clock::get()?.unix_timestamp;
+
+        ownership::propose(vault, new_owner, now)?;
+        vault.record_activity()?;
+        emit_cpi!(OwnershipTransferProposedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: now,
+            new_owner,
+        });
+        msg!("Ownership of vault {} proposed to {}", vault.key(), new_owner);
+        Ok(())
+    }
+
+    pub fn accept_ownership(ctx: Context<AcceptOwnership>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        let new_owner = ctx.accounts.new_owner.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        let previous_owner = ownership::accept(vault, new_owner, now)?;
+        vault.record_activity()?;
+        emit_cpi!(OwnershipTransferredEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: new_owner,
+            timestamp: now,
+            previous_owner,
+        });
+        msg!("Vault {} transferred from {} to {}", vault.key(), previous_owner, new_owner);
+        Ok(())
+    }
+
+    pub fn cancel_ownership_transfer(ctx: Context<TransferOwnership>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+
+        ownership::cancel(vault)?;
+        vault.record_activity()?;
+        emit_cpi!(OwnershipTransferCancelledEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: vault.last_active_timestamp,
+        });
+        msg!("Ownership transfer of vault {} cancelled", vault.key());
+        Ok(())
+    }
+
+    pub fn cancel_timing_update(ctx: Context<UpdateTiming>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+
+        timing::cancel(vault)?;
+        vault.record_activity()?;
+        emit_cpi!(TimingUpdateCancelledEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: vault.last_active_timestamp,
+        });
+        msg!("Queued timing change on vault {} cancelled", vault.key());
+        Ok(())
+    }
+
+    pub fn initialize_crank_reserve(ctx: Context<InitializeCrankReserve>, bounty: u64) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+        vault.record_activity()?;
+
+        let reserve = &mut ctx.accounts.crank_reserve;
+        reserve.vault = vault.key();
+        reserve.bounty = bounty;
+        reserve.inactivity_rewarded_id = 0;
+        reserve.execution_rewarded_id = 0;
+        reserve.expiry_rewarded_id = 0;
+        reserve.bump = ctx.bumps.crank_reserve;
+
+        emit_cpi!(CrankBountySetEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: vault.last_active_timestamp,
+            bounty,
+        });
+        msg!("Crank reserve initialized for vault {} with bounty {}", vault.key(), bounty);
+        Ok(())
+    }
+
+    // Anyone may top up a vault's crank reserve
+    pub fn fund_crank_reserve(ctx: Context<FundCrankReserve>, amount: u64) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.crank_reserve.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+
+        msg!("Crank reserve for vault {} funded with {} lamports", ctx.accounts.vault.key(), amount);
+        Ok(())
+    }
+
+    pub fn set_crank_bounty(ctx: Context<ManageCrankReserve>, bounty: u64) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+        vault.record_activity()?;
+
+        ctx.accounts.crank_reserve.bounty = bounty;
+        emit_cpi!(CrankBountySetEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: vault.last_active_timestamp,
+            bounty,
+        });
+        msg!("Crank bounty for vault {} set to {}", vault.key(), bounty);
+        Ok(())
+    }
+
+    pub fn withdraw_crank_reserve(ctx: Context<ManageCrankReserve>, amount: u64) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+        vault.record_activity()?;
+
+        let reserve = ctx.accounts.crank_reserve.to_account_info();
+        require!(
+            CrankReserve::available_lamports(&reserve)? >= amount,
+            ErrorCode::InsufficientCrankReserve
+        );
+        **reserve.try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.owner.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        msg!("Withdrew {} lamports from crank reserve of vault {}", amount, vault.key());
+        Ok(())
+    }
+
     pub fn update_last_active(ctx: Context<UpdateLastActive>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
         let vault = &mut ctx.accounts.vault;
         require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
         
-        vault.last_active_timestamp = Clock::get()?.unix_timestamp;
+        vault.record_activity()?;
+        emit_cpi!(ActivityRecordedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: vault.last_active_timestamp,
+        });
         msg!("Vault activity updated for owner: {}", vault.owner);
         Ok(())
     }
 
-    // LayerZero receive function - called by the endpoint
+    // Check-in from the owner or any registered heartbeat key
+    pub fn proof_of_life(ctx: Context<ProofOfLife>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        let signer = ctx.accounts.signer.key();
+        require!(
+            signer == vault.owner || vault.heartbeat_keys.contains(&signer),
+            ErrorCode::Unauthorized
+        );
+
+        vault.record_activity()?;
+        emit_cpi!(ActivityRecordedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: signer,
+            timestamp: vault.last_active_timestamp,
+        });
+        msg!("Proof of life for vault {} from {}", vault.key(), signer);
+        Ok(())
+    }
+
+    pub fn add_heartbeat_key(ctx: Context<AddHeartbeatKey>, heartbeat_key: Pubkey) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+        require!(
+            !vault.heartbeat_keys.contains(&heartbeat_key),
+            ErrorCode::DuplicateHeartbeatKey
+        );
+        require!(
+            vault.heartbeat_keys.len() < Vault::MAX_HEARTBEAT_KEYS,
+            ErrorCode::TooManyHeartbeatKeys
+        );
+
+        vault.heartbeat_keys.push(heartbeat_key);
+        vault.record_activity()?;
+        emit_cpi!(HeartbeatKeyAddedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: vault.last_active_timestamp,
+            heartbeat_key,
+        });
+        msg!("Heartbeat key {} added to vault {}", heartbeat_key, vault.key());
+        Ok(())
+    }
+
+    pub fn remove_heartbeat_key(ctx: Context<RemoveHeartbeatKey>, heartbeat_key: Pubkey) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+
+        let index = vault
+            .heartbeat_keys
+            .iter()
+            .position(|key| *key == heartbeat_key)
+            .ok_or(ErrorCode::HeartbeatKeyNotFound)?;
+        vault.heartbeat_keys.remove(index);
+        vault.record_activity()?;
+        emit_cpi!(HeartbeatKeyRemovedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: vault.last_active_timestamp,
+            heartbeat_key,
+        });
+        msg!("Heartbeat key {} removed from vault {}", heartbeat_key, vault.key());
+        Ok(())
+    }
+
+    // Heartbeat keys may only cancel recoveries started by check_inactivity
+    pub fn cancel_inactivity_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        let signer = ctx.accounts.signer.key();
+        require!(
+            signer == vault.owner || vault.heartbeat_keys.contains(&signer),
+            ErrorCode::Unauthorized
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let recovery_id = recovery::cancel_inactivity_recovery(vault, &mut ctx.accounts.recovery, now)?;
+        vault.record_activity()?;
+        emit_cpi!(RecoveryCancelledEvent {
+            vault_id: vault.key(),
+            recovery_id,
+            actor: signer,
+            timestamp: vault.last_active_timestamp,
+        });
+        msg!("Inactivity recovery {} cancelled by {}", recovery_id, signer);
+        Ok(())
+    }
+
+    // Any other recovery is cancelled by the owner if they proposed it, or by
+    // guardians once `threshold` of them have voted
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        let recovery = &mut ctx.accounts.recovery;
+        let signer = ctx.accounts.signer.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        let outcome = recovery::cancel(vault, recovery, signer, now)?;
+        if signer == vault.owner {
+            vault.record_activity()?;
+        }
+        match outcome {
+            CancelOutcome::Voted { votes } => {
+                emit_cpi!(RecoveryCancelVoteEvent {
+                    vault_id: vault.key(),
+                    recovery_id: recovery.recovery_id,
+                    actor: signer,
+                    timestamp: now,
+                    votes: votes as u8,
+                });
+                msg!("Guardian {} voted to cancel recovery {}. Votes: {}/{}",
+                     signer, recovery.recovery_id, votes, vault.threshold);
+            }
+            CancelOutcome::Cancelled => {
+                emit_cpi!(RecoveryCancelledEvent {
+                    vault_id: vault.key(),
+                    recovery_id: recovery.recovery_id,
+                    actor: signer,
+                    timestamp: now,
+                });
+                msg!("Recovery {} cancelled by {}", recovery.recovery_id, signer);
+            }
+        }
+        Ok(())
+    }
+
+    // LayerZero receive function - called by the executor
     pub fn lz_receive(
         ctx: Context<LzReceive>,
         params: LzReceiveParams,
     ) -> Result<()> {
-        let vault = &mut ctx.accounts.vault;
-        
-        // Verify the message is from our trusted peer
+        let config = &ctx.accounts.config;
+        config.require_not_paused(PauseScope::InboundLayerZero)?;
+        // Messages come through the configured endpoint, and only from allowed chains
+        require!(
+            ctx.accounts.endpoint_program.key() == config.endpoint_program,
+            ErrorCode::InvalidEndpoint
+        );
         require!(
-            params.src_eid == ETHEREUM_EID,
+            config.is_allowed_eid(params.src_eid),
             ErrorCode::UnauthorizedSource
         );
+        // and only from the vault's own peer on that chain
+        require!(params.sender == ctx.accounts.peer.peer, ErrorCode::UnknownPeer);
+
+        // Anyone can call lz_receive; clearing the payload with the endpoint
+        // proves it was verified for this vault and stops it being replayed
+        let vault = &ctx.accounts.vault;
+        let vault_seed = vault.vault_seed.to_le_bytes();
+        let vault_seeds = &[
+            b"vault",
+            vault.creator.as_ref(),
+            &vault_seed,
+            &[vault.bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
+        let clear_ctx = CpiContext::new_with_signer(
+            ctx.accounts.endpoint_program.to_account_info(),
+            Clear {
+                oapp: ctx.accounts.vault.to_account_info(),
+                endpoint: ctx.accounts.endpoint.to_account_info(),
+            },
+            signer_seeds,
+        );
+        clear(clear_ctx, ClearParams {
+            receiver: ctx.accounts.vault.key(),
+            src_eid: params.src_eid,
+            sender: params.sender,
+            nonce: params.nonce,
+            guid: params.guid,
+            message: params.message.clone(),
+        })?;
+
+        let vault = &mut ctx.accounts.vault;
 
         let message: RecoveryMessage = RecoveryMessage::try_from_slice(&params.message)?;
         require!(message.vault_id == ctx.accounts.vault.key(), ErrorCode::InvalidVault);
@@ -346,22 +1072,41 @@ pub mod reclaimx {
         match message.action {
             RecoveryAction::Initiate => {
                 // Handle cross-chain recovery initiation from EVM
-                vault.recovery_id += 1;
-                vault.recovery_state = RecoveryState::Pending {
-                    new_owner: message.new_owner,
-                    approvals: vec![],
-                    malicious_approvals: vec![],
-                    start_timestamp: Clock::get()?.unix_timestamp,
-                    recovery_id: vault.recovery_id,
+                let now = Clock::get()?.unix_timestamp;
+                let (Some(recovery), Some(bump)) = (ctx.accounts.recovery.as_mut(), ctx.bumps.recovery) else {
+                    return err!(ErrorCode::MissingRecoveryAccount);
                 };
+                let recovery_key = recovery.key();
+                recovery.vault = vault.key();
+                recovery.bump = bump;
+                // Rejected while a recovery is pending; the executor can retry
+                // delivery once it has been executed, cancelled or expired
+                let recovery_id = recovery::start(
+                    vault,
+                    recovery,
+                    recovery_key,
+                    Pubkey::new_from_array(params.sender),
+                    message.new_owner,
+                    now,
+                    false,
+                )?;
+                emit_cpi!(RecoveryInitiatedEvent {
+                    vault_id: vault.key(),
+                    recovery_id,
+                    actor: Pubkey::new_from_array(params.sender),
+                    timestamp: now,
+                    new_owner: message.new_owner,
+                    src_eid: params.src_eid,
+                });
                 msg!("Received cross-chain recovery initiation from EID: {}", params.src_eid);
             }
             RecoveryAction::Approve => {
                 // Handle cross-chain approval from EVM
-                if let RecoveryState::Pending { recovery_id, approvals, .. } = &mut vault.recovery_state {
-                    require!(message.recovery_id == *recovery_id, ErrorCode::InvalidRecoveryId);
+                if vault.active_recovery.is_some() {
+                    // The active recovery is always the vault's latest one
+                    require!(message.recovery_id == vault.recovery_id, ErrorCode::InvalidRecoveryId);
                     // Add cross-chain approval (could be from EVM guardian)
-                    msg!("Received cross-chain approval for recovery_id: {}", recovery_id);
+                    msg!("Received cross-chain approval for recovery_id: {}", vault.recovery_id);
                 }
             }
         }
@@ -369,33 +1114,515 @@ pub mod reclaimx {
         Ok(())
     }
 
-    pub fn set_peer(
-        ctx: Context<SetPeer>,
-        dst_eid: u32,
-        peer: [u8; 32],
-    ) -> Result<()> {
-        // Only vault owner can set peers
-        let vault = &ctx.accounts.vault;
-        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
-
-        // Store peer information
-        let peer_info = &mut ctx.accounts.peer_info;
-        peer_info.eid = dst_eid;
-        peer_info.peer = peer;
-        peer_info.vault = vault.key();
+    // Emergency stop: the owner freezes at once, guardians once `threshold` of
+    // them have voted. Blocks asset transfers, guardian changes and recovery
+    // execution until unfrozen or MAX_FREEZE_DURATION has passed.
+    pub fn freeze_vault(ctx: Context<FreezeVault>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let signer = ctx.accounts.signer.key();
+        let vault = &mut ctx.accounts.vault;
+        let now = Clock::get()?.unix_timestamp;
 
-        msg!("Peer set for EID {}: {:?}", dst_eid, peer);
-        Ok(())
+        let outcome = freeze::freeze(vault, signer, now)?;
+        if signer == vault.owner {
+            vault.record_activity()?;
+        }
+        match outcome {
+            FreezeOutcome::Voted { votes } => {
+                emit_cpi!(FreezeVoteEvent {
+                    vault_id: vault.key(),
+                    recovery_id: vault.recovery_id,
+                    actor: signer,
+                    timestamp: now,
+                    freeze: true,
+                    votes: votes as u8,
+                });
+                msg!("Guardian {} voted to freeze vault {}. Votes: {}/{}",
+                     signer, vault.key(), votes, vault.threshold);
+            }
+            FreezeOutcome::Frozen { until } => {
+                emit_cpi!(VaultFrozenEvent {
+                    vault_id: vault.key(),
+                    recovery_id: vault.recovery_id,
+                    actor: signer,
+                    timestamp: now,
+                    frozen_until: until,
+                    by_guardians: vault.frozen_by_guardians,
+                });
+                msg!("Vault {} frozen until {}", vault.key(), until);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn unfreeze_vault(ctx: Context<FreezeVault>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let signer = ctx.accounts.signer.key();
+        let vault = &mut ctx.accounts.vault;
+        let now = Clock::get()?.unix_timestamp;
+
+        let outcome = freeze::unfreeze(vault, signer, now)?;
+        if signer == vault.owner {
+            vault.record_activity()?;
+        }
+        match outcome {
+            UnfreezeOutcome::Voted { votes } => {
+                emit_cpi!(FreezeVoteEvent {
+                    vault_id: vault.key(),
+                    recovery_id: vault.recovery_id,
+                    actor: signer,
+                    timestamp: now,
+                    freeze: false,
+                    votes: votes as u8,
+                });
+                msg!("Guardian {} voted to unfreeze vault {}. Votes: {}/{}",
+                     signer, vault.key(), votes, vault.threshold);
+            }
+            UnfreezeOutcome::Unfrozen => {
+                emit_cpi!(VaultUnfrozenEvent {
+                    vault_id: vault.key(),
+                    recovery_id: vault.recovery_id,
+                    actor: signer,
+                    timestamp: now,
+                });
+                msg!("Vault {} unfrozen", vault.key());
+            }
+        }
+        Ok(())
+    }
+
+    // Owner withdrawal from a vault-owned token account. Amounts beyond the
+    // mint's spending limit for the current period need a guardian to co-sign;
+    // amounts over its queue threshold must use queue_withdrawal instead.
+    pub fn withdraw_asset(ctx: Context<WithdrawAsset>, amount: u64) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+        freeze::require_not_frozen(vault, now)?;
+        let from = &ctx.accounts.vault_token_account;
+        require!(from.owner == vault.key(), ErrorCode::Unauthorized);
+
+        require!(!spending::must_queue(vault, &from.mint, amount), ErrorCode::WithdrawalMustBeQueued);
+
+        let co_signer = ctx.accounts.guardian.as_ref().map(|guardian| guardian.key());
+        spending::record_withdrawal(vault, from.mint, amount, co_signer, now)?;
+        withdrawal::deduct_assets(vault, &from.key(), amount);
+        vault.record_activity()?;
+
+        transfer_from_vault(
+            &ctx.accounts.vault,
+            from,
+            &ctx.accounts.destination,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+        emit_cpi!(AssetWithdrawnEvent {
+            vault_id: ctx.accounts.vault.key(),
+            recovery_id: ctx.accounts.vault.recovery_id,
+            actor: ctx.accounts.owner.key(),
+            timestamp: now,
+            mint: from.mint,
+            amount,
+            to: ctx.accounts.destination.key(),
+            co_signer,
+        });
+        msg!("Withdrew {} of {} from vault {}", amount, from.mint, ctx.accounts.vault.key());
+        Ok(())
+    }
+
+    // Tightening a limit only needs the owner; raising the amount or queue
+    // threshold, or shortening the period, also needs a guardian co-signature.
+    // Withdrawals above `queue_above` wait out the timelock in the queue.
+    pub fn set_spending_limit(
+        ctx: Context<SetSpendingLimit>,
+        mint: Pubkey,
+        max_amount: u64,
+        period: u64,
+        queue_above: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+        let co_signer = ctx.accounts.guardian.as_ref().map(|guardian| guardian.key());
+
+        spending::set_limit(vault, mint, max_amount, period, queue_above, co_signer, Clock::get()?.unix_timestamp)?;
+        vault.record_activity()?;
+        emit_cpi!(SpendingLimitSetEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: vault.last_active_timestamp,
+            mint,
+            max_amount,
+            period,
+            queue_above,
+        });
+        msg!("Spending limit for {} on vault {}: {} per {}s", mint, vault.key(), max_amount, period);
+        Ok(())
+    }
+
+    pub fn remove_spending_limit(ctx: Context<SetSpendingLimit>, mint: Pubkey) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+        let co_signer = ctx.accounts.guardian.as_ref().map(|guardian| guardian.key());
+
+        spending::remove_limit(vault, mint, co_signer)?;
+        vault.record_activity()?;
+        emit_cpi!(SpendingLimitRemovedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: vault.last_active_timestamp,
+            mint,
+        });
+        msg!("Spending limit for {} removed from vault {}", mint, vault.key());
+        Ok(())
+    }
+
+    // Starts the delay on a large withdrawal. It executes once the vault
+    // timelock has passed unless a guardian vetoes or the owner cancels it first.
+    pub fn queue_withdrawal(ctx: Context<QueueWithdrawal>, amount: u64) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+        let from = &ctx.accounts.vault_token_account;
+        require!(from.owner == vault.key(), ErrorCode::Unauthorized);
+        let co_signer = ctx.accounts.guardian.as_ref().map(|guardian| guardian.key());
+
+        let withdrawal = &mut ctx.accounts.withdrawal;
+        withdrawal.vault = vault.key();
+        withdrawal.mint = from.mint;
+        withdrawal.vault_token_account = from.key();
+        withdrawal.destination = ctx.accounts.destination.key();
+        withdrawal.amount = amount;
+        withdrawal.bump = ctx.bumps.withdrawal;
+        let withdrawal_id = withdrawal::queue(vault, withdrawal, co_signer, now)?;
+        vault.record_activity()?;
+
+        emit_cpi!(WithdrawalQueuedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: now,
+            withdrawal_id,
+            mint: withdrawal.mint,
+            amount,
+            to: withdrawal.destination,
+            co_signer,
+        });
+        msg!("Withdrawal {} of {} queued on vault {}", withdrawal_id, amount, vault.key());
+        Ok(())
+    }
+
+    // Anyone may crank a queued withdrawal once its delay has passed; the
+    // destination was fixed when it was queued.
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        let now = Clock::get()?.unix_timestamp;
+        let withdrawal = &mut ctx.accounts.withdrawal;
+        require!(
+            ctx.accounts.vault_token_account.key() == withdrawal.vault_token_account
+                && ctx.accounts.destination.key() == withdrawal.destination,
+            ErrorCode::TokenAccountMismatch
+        );
+
+        withdrawal::execute(vault, withdrawal, now)?;
+        withdrawal::deduct_assets(vault, &withdrawal.vault_token_account, withdrawal.amount);
+        let (withdrawal_id, mint, amount) = (withdrawal.withdrawal_id, withdrawal.mint, withdrawal.amount);
+
+        transfer_from_vault(
+            &ctx.accounts.vault,
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.destination,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+        emit_cpi!(WithdrawalExecutedEvent {
+            vault_id: ctx.accounts.vault.key(),
+            recovery_id: ctx.accounts.vault.recovery_id,
+            actor: ctx.accounts.cranker.key(),
+            timestamp: now,
+            withdrawal_id,
+            mint,
+            amount,
+            to: ctx.accounts.destination.key(),
+        });
+        msg!("Withdrawal {} executed on vault {}", withdrawal_id, ctx.accounts.vault.key());
+        Ok(())
+    }
+
+    pub fn veto_withdrawal(ctx: Context<ResolveWithdrawal>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let guardian = ctx.accounts.signer.key();
+        let vault = &mut ctx.accounts.vault;
+        let now = Clock::get()?.unix_timestamp;
+        let withdrawal = &mut ctx.accounts.withdrawal;
+
+        withdrawal::veto(vault, withdrawal, guardian, now)?;
+        emit_cpi!(WithdrawalVetoedEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: guardian,
+            timestamp: now,
+            withdrawal_id: withdrawal.withdrawal_id,
+        });
+        msg!("Withdrawal {} on vault {} vetoed by {}", withdrawal.withdrawal_id, vault.key(), guardian);
+        Ok(())
+    }
+
+    pub fn cancel_withdrawal(ctx: Context<ResolveWithdrawal>) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.signer.key() == vault.owner, ErrorCode::Unauthorized);
+        let now = Clock::get()?.unix_timestamp;
+        let withdrawal = &mut ctx.accounts.withdrawal;
+
+        withdrawal::cancel(vault, withdrawal, now)?;
+        vault.record_activity()?;
+        emit_cpi!(WithdrawalCancelledEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: now,
+            withdrawal_id: withdrawal.withdrawal_id,
+        });
+        msg!("Withdrawal {} on vault {} cancelled", withdrawal.withdrawal_id, vault.key());
+        Ok(())
+    }
+
+    pub fn set_peer(
+        ctx: Context<SetPeer>,
+        dst_eid: u32,
+        peer: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused(PauseScope::All)?;
+        // Only vault owner can set peers
+        let vault = &mut ctx.accounts.vault;
+        require!(ctx.accounts.owner.key() == vault.owner, ErrorCode::Unauthorized);
+        vault.record_activity()?;
+
+        // Store peer information
+        let peer_info = &mut ctx.accounts.peer_info;
+        peer_info.eid = dst_eid;
+        peer_info.peer = peer;
+        peer_info.vault = vault.key();
+
+        emit_cpi!(PeerSetEvent {
+            vault_id: vault.key(),
+            recovery_id: vault.recovery_id,
+            actor: vault.owner,
+            timestamp: vault.last_active_timestamp,
+            eid: dst_eid,
+            peer,
+        });
+
+        msg!("Peer set for EID {}: {:?}", dst_eid, peer);
+        Ok(())
+    }
+
+    // One-time setup of the protocol config, signed by the program's upgrade
+    // authority, which picks the admin.
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        admin: Pubkey,
+        params: ConfigParams,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = admin;
+        config.pending_admin = None;
+        config.paused_scopes = 0;
+        config.bump = ctx.bumps.config;
+        config.apply(params)?;
+
+        emit_cpi!(ConfigUpdatedEvent {
+            actor: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+            paused_scopes: config.paused_scopes,
+        });
+        msg!("Protocol config initialized with admin {}", admin);
+        Ok(())
+    }
+
+    pub fn update_config(ctx: Context<ManageConfig>, params: ConfigParams) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.require_admin(ctx.accounts.signer.key())?;
+        config.apply(params)?;
+
+        emit_cpi!(ConfigUpdatedEvent {
+            actor: config.admin,
+            timestamp: Clock::get()?.unix_timestamp,
+            paused_scopes: config.paused_scopes,
+        });
+        msg!("Protocol config updated, allowed EIDs: {:?}", config.allowed_eids);
+        Ok(())
+    }
+
+    // The admin or the security council halts or resumes one area of the protocol
+    pub fn set_paused(ctx: Context<ManageConfig>, scope: PauseScope, paused: bool) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        let config = &mut ctx.accounts.config;
+        config.set_paused(signer, scope, paused)?;
+
+        emit_cpi!(ConfigUpdatedEvent {
+            actor: signer,
+            timestamp: Clock::get()?.unix_timestamp,
+            paused_scopes: config.paused_scopes,
+        });
+        msg!("Protocol scope {:?} {}", scope, if paused { "paused" } else { "unpaused" });
+        Ok(())
+    }
+
+    // First step of an admin handover; `None` withdraws a pending proposal
+    pub fn propose_admin(ctx: Context<ManageConfig>, new_admin: Option<Pubkey>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.propose_admin(ctx.accounts.signer.key(), new_admin)?;
+        msg!("Protocol admin proposed: {:?}", new_admin);
+        Ok(())
+    }
+
+    pub fn accept_admin(ctx: Context<ManageConfig>) -> Result<()> {
+        let new_admin = ctx.accounts.signer.key();
+        let config = &mut ctx.accounts.config;
+        let previous_admin = config.accept_admin(new_admin)?;
+
+        emit_cpi!(AdminChangedEvent {
+            actor: new_admin,
+            timestamp: Clock::get()?.unix_timestamp,
+            previous_admin,
+        });
+        msg!("Protocol admin changed from {} to {}", previous_admin, new_admin);
+        Ok(())
+    }
+}
+
+fn hex_address(address: &[u8; 20]) -> String {
+    address.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// Moves every tracked asset from the vault to the new owner. `accounts` holds one
+// (vault token account, new owner token account) pair per asset, in vault.assets
+// order; each pair must match the asset's account and mint, and the destination
+// must belong to `new_owner`. Returns the (mint, amount, destination) of each transfer.
+// Units of `asset` a recovery moves to the new owner
+fn recoverable_amount(asset: &Asset) -> u64 {
+    match asset.asset_type {
+        AssetType::Token => asset.amount,
+        AssetType::NFT => 1,
+    }
+}
+
+// Each asset's token account must exist, belong to the vault and hold what
+// the vault claims, or recovery could never move it
+fn check_assets(vault: &Pubkey, assets: &[Asset], accounts: &[AccountInfo]) -> Result<()> {
+    require!(accounts.len() == assets.len(), ErrorCode::MissingTokenAccount);
+    for (asset, info) in assets.iter().zip(accounts) {
+        require!(info.key() == asset.token_account, ErrorCode::AssetAccountMismatch);
+        let account = Account::<TokenAccount>::try_from(info)?;
+        require!(
+            account.mint == asset.mint
+                && account.owner == *vault
+                && account.amount >= recoverable_amount(asset),
+            ErrorCode::AssetAccountMismatch
+        );
+    }
+    Ok(())
+}
+
+// Moves what is left of each asset to the new owner. An asset whose token
+// account has since been closed is skipped so it cannot block the recovery.
+fn transfer_assets<'info>(
+    vault: &Account<'info, Vault>,
+    new_owner: Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    token_program: &Program<'info, Token>,
+) -> Result<Vec<(Pubkey, u64, Pubkey)>> {
+    require!(accounts.len() == 2 * vault.assets.len(), ErrorCode::MissingTokenAccount);
+    let mut transfers = vec![];
+    for (asset, pair) in vault.assets.iter().zip(accounts.chunks(2)) {
+        require!(pair[0].key() == asset.token_account, ErrorCode::AssetAccountMismatch);
+        if pair[0].owner != &token::ID || pair[0].data_is_empty() {
+            continue;
+        }
+        let from = Account::<TokenAccount>::try_from(&pair[0])?;
+        require!(from.mint == asset.mint, ErrorCode::AssetAccountMismatch);
+        let amount = recoverable_amount(asset).min(from.amount);
+        if amount == 0 {
+            continue;
+        }
+        let to = Account::<TokenAccount>::try_from(&pair[1])?;
+        require!(
+            to.mint == asset.mint && to.owner == new_owner,
+            ErrorCode::AssetAccountMismatch
+        );
+
+        transfer_from_vault(vault, &from, &to, token_program, amount)?;
+        transfers.push((asset.mint, amount, to.key()));
+    }
+    Ok(transfers)
+}
+
+// Token transfer out of a vault-owned account, signing as the vault PDA
+fn transfer_from_vault<'info>(
+    vault: &Account<'info, Vault>,
+    from: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let vault_seed = vault.vault_seed.to_le_bytes();
+    let vault_seeds = &[
+        b"vault",
+        vault.creator.as_ref(),
+        &vault_seed,
+        &[vault.bump],
+    ];
+    let signer_seeds = &[&vault_seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: from.to_account_info(),
+        to: to.to_account_info(),
+        authority: vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, amount)
+}
+
+// Pays the bounty for `crank` on `recovery_id` out of the reserve. Each crank is
+// rewarded at most once per recovery_id, and never below the reserve's rent floor.
+// Returns the lamports paid.
+fn pay_crank_bounty<'info>(
+    reserve: &mut Account<'info, CrankReserve>,
+    cranker: &Signer<'info>,
+    crank: CrankKind,
+    recovery_id: u64,
+) -> Result<u64> {
+    let bounty = reserve.bounty;
+    let available = CrankReserve::available_lamports(&reserve.to_account_info())?;
+    if bounty == 0 || available < bounty || !reserve.claim(crank, recovery_id) {
+        return Ok(0);
     }
+    **reserve.to_account_info().try_borrow_mut_lamports()? -= bounty;
+    **cranker.to_account_info().try_borrow_mut_lamports()? += bounty;
+    Ok(bounty)
 }
 
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(vault_seed: u64)]
 pub struct InitializeVault<'info> {
     #[account(
         init,
         payer = payer,
         space = 8 + Vault::SPACE,
-        seeds = [b"vault", owner.key().as_ref()],
+        seeds = [b"vault", owner.key().as_ref(), &vault_seed.to_le_bytes()],
         bump
     )]
     pub vault: Account<'info, Vault>,
@@ -403,13 +1630,17 @@ pub struct InitializeVault<'info> {
     pub owner: Signer<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
     /// CHECK: LayerZero endpoint
+    #[account(mut)]
     pub endpoint: AccountInfo<'info>,
     /// CHECK: LayerZero endpoint program
     pub endpoint_program: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitiateRecovery<'info> {
     #[account(mut)]
@@ -417,26 +1648,106 @@ pub struct InitiateRecovery<'info> {
     pub initiator: Signer<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Recovery::SPACE,
+        seeds = [b"recovery", vault.key().as_ref(), &(vault.recovery_id + 1).to_le_bytes()],
+        bump
+    )]
+    pub recovery: Account<'info, Recovery>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
     /// CHECK: LayerZero endpoint
+    #[account(mut)]
     pub endpoint: AccountInfo<'info>,
     /// CHECK: LayerZero endpoint program
     pub endpoint_program: AccountInfo<'info>,
     /// CHECK: Peer address for cross-chain messaging
     pub peer: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ApproveRecovery<'info> {
     #[account(mut)]
     pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [b"recovery", vault.key().as_ref(), &recovery.recovery_id.to_le_bytes()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, Recovery>,
     pub guardian: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
     #[account(mut)]
-    pub vault_token_account: Account<'info, TokenAccount>,
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [b"recovery", vault.key().as_ref(), &recovery.recovery_id.to_le_bytes()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, Recovery>,
     #[account(mut)]
-    pub new_owner_token_account: Account<'info, TokenAccount>,
+    pub cranker: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"crank_reserve", vault.key().as_ref()],
+        bump = crank_reserve.bump
+    )]
+    pub crank_reserve: Option<Account<'info, CrankReserve>>,
     pub token_program: Program<'info, Token>,
 }
 
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SubmitApprovals<'info> {
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [b"recovery", vault.key().as_ref(), &recovery.recovery_id.to_le_bytes()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, Recovery>,
+    pub relayer: Signer<'info>,
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AddEvmGuardian<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveEvmGuardian<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct StakeGuardianOFT<'info> {
     pub vault: Account<'info, Vault>,
@@ -448,86 +1759,581 @@ pub struct StakeGuardianOFT<'info> {
     /// CHECK: OFT account
     pub oft: AccountInfo<'info>,
     pub token_mint: Account<'info, Mint>,
-    /// CHECK: LayerZero endpoint
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    /// CHECK: LayerZero endpoint, checked against the config
     pub endpoint: AccountInfo<'info>,
     /// CHECK: Peer address
     pub peer: AccountInfo<'info>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct MarkMalicious<'info> {
-    #[account(mut)]
     pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [b"recovery", vault.key().as_ref(), &recovery.recovery_id.to_le_bytes()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, Recovery>,
     pub signer: Signer<'info>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CheckInactivity<'info> {
     #[account(mut)]
     pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(
+        init,
+        payer = cranker,
+        space = 8 + Recovery::SPACE,
+        seeds = [b"recovery", vault.key().as_ref(), &(vault.recovery_id + 1).to_le_bytes()],
+        bump
+    )]
+    pub recovery: Account<'info, Recovery>,
+    #[account(
+        mut,
+        seeds = [b"crank_reserve", vault.key().as_ref()],
+        bump = crank_reserve.bump
+    )]
+    pub crank_reserve: Option<Account<'info, CrankReserve>>,
+    pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct UpdateLastActive<'info> {
+pub struct ExpireRecovery<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [b"recovery", vault.key().as_ref(), &recovery.recovery_id.to_le_bytes()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, Recovery>,
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"crank_reserve", vault.key().as_ref()],
+        bump = crank_reserve.bump
+    )]
+    pub crank_reserve: Option<Account<'info, CrankReserve>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetRecoveryDeadlines<'info> {
     #[account(mut)]
     pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
     pub owner: Signer<'info>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct LzReceive<'info> {
+pub struct UpdateTiming<'info> {
     #[account(mut)]
     pub vault: Account<'info, Vault>,
-    /// CHECK: LayerZero endpoint
-    pub endpoint: AccountInfo<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub owner: Signer<'info>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct SetPeer<'info> {
+pub struct TransferOwnership<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptOwnership<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub new_owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ApplyTiming<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub cranker: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeCrankReserve<'info> {
+    #[account(mut)]
     pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
     pub owner: Signer<'info>,
     #[account(
         init,
         payer = owner,
-        space = 8 + PeerInfo::SPACE,
-        seeds = [b"peer", vault.key().as_ref(), &dst_eid.to_le_bytes()],
+        space = 8 + CrankReserve::SPACE,
+        seeds = [b"crank_reserve", vault.key().as_ref()],
         bump
     )]
-    pub peer_info: Account<'info, PeerInfo>,
+    pub crank_reserve: Account<'info, CrankReserve>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundCrankReserve<'info> {
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [b"crank_reserve", vault.key().as_ref()],
+        bump = crank_reserve.bump
+    )]
+    pub crank_reserve: Account<'info, CrankReserve>,
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub funder: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-#[account]
-pub struct Vault {
-    pub owner: Pubkey,
-    pub guardians: Vec<Pubkey>,
-    pub threshold: u8,
-    pub timelock: u64,
-    pub inactivity_period: u64,
-    pub backup_wallet: Pubkey,
-    pub stake_amount: u64,
-    pub assets: Vec<Asset>,
-    pub recovery_state: RecoveryState,
-    pub recovery_id: u64,
-    pub last_active_timestamp: i64,
-    pub bump: u8,
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ManageCrankReserve<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"crank_reserve", vault.key().as_ref()],
+        bump = crank_reserve.bump
+    )]
+    pub crank_reserve: Account<'info, CrankReserve>,
 }
 
-impl Vault {
-    pub const SPACE: usize = 32 + // owner
-        4 + (32 * 10) + // guardians (max 10)
-        1 + // threshold
-        8 + // timelock
-        8 + // inactivity_period
-        32 + // backup_wallet
-        8 + // stake_amount
-        4 + (64 * 10) + // assets (max 10)
-        1 + 200 + // recovery_state (enum + data)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateLastActive<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProofOfLife<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub signer: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AddHeartbeatKey<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveHeartbeatKey<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [b"recovery", vault.key().as_ref(), &recovery.recovery_id.to_le_bytes()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, Recovery>,
+    pub signer: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: LzReceiveParams)]
+pub struct LzReceive<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        seeds = [b"peer", vault.key().as_ref(), &params.src_eid.to_le_bytes()],
+        bump
+    )]
+    pub peer: Account<'info, PeerInfo>,
+    /// CHECK: LayerZero endpoint, validated by its program in `clear`
+    #[account(mut)]
+    pub endpoint: AccountInfo<'info>,
+    /// CHECK: LayerZero endpoint program, checked against the config
+    pub endpoint_program: AccountInfo<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // Only required for Initiate messages
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Recovery::SPACE,
+        seeds = [b"recovery", vault.key().as_ref(), &(vault.recovery_id + 1).to_le_bytes()],
+        bump
+    )]
+    pub recovery: Option<Account<'info, Recovery>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(dst_eid: u32)]
+pub struct SetPeer<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub owner: Signer<'info>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + PeerInfo::SPACE,
+        seeds = [b"peer", vault.key().as_ref(), &dst_eid.to_le_bytes()],
+        bump
+    )]
+    pub peer_info: Account<'info, PeerInfo>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ProtocolConfig::SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // Only the program's upgrade authority picks the first admin
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ManageConfig<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub signer: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FreezeVault<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub signer: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawAsset<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub owner: Signer<'info>,
+    // Only required above the spending limit
+    pub guardian: Option<Signer<'info>>,
+    #[account(mut)]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetSpendingLimit<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    pub owner: Signer<'info>,
+    // Only required when loosening or removing a limit
+    pub guardian: Option<Signer<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct QueueWithdrawal<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    // Only required above the spending limit
+    pub guardian: Option<Signer<'info>>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Withdrawal::SPACE,
+        seeds = [b"withdrawal", vault.key().as_ref(), &(vault.withdrawal_id + 1).to_le_bytes()],
+        bump
+    )]
+    pub withdrawal: Account<'info, Withdrawal>,
+    pub vault_token_account: Account<'info, TokenAccount>,
+    pub destination: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [b"withdrawal", vault.key().as_ref(), &withdrawal.withdrawal_id.to_le_bytes()],
+        bump = withdrawal.bump
+    )]
+    pub withdrawal: Account<'info, Withdrawal>,
+    pub cranker: Signer<'info>,
+    #[account(mut)]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+// Veto by a guardian or cancellation by the owner
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveWithdrawal<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [b"withdrawal", vault.key().as_ref(), &withdrawal.withdrawal_id.to_le_bytes()],
+        bump = withdrawal.bump
+    )]
+    pub withdrawal: Account<'info, Withdrawal>,
+    pub signer: Signer<'info>,
+}
+
+#[account]
+#[derive(Default)]
+pub struct Vault {
+    pub owner: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timelock: u64,
+    pub inactivity_period: u64,
+    pub backup_wallet: Pubkey,
+    pub stake_amount: u64,
+    pub assets: Vec<Asset>,
+    // The pending Recovery account, if any
+    pub active_recovery: Option<Pubkey>,
+    // Latest recovery_id; recoveries are numbered from 1
+    pub recovery_id: u64,
+    pub last_active_timestamp: i64,
+    pub bump: u8,
+    pub heartbeat_keys: Vec<Pubkey>,
+    pub evm_guardians: Vec<[u8; 20]>,
+    // Seconds after initiation at which a pending recovery expires
+    pub recovery_expiry: u64,
+    // Seconds for which a guardian approval counts towards the threshold
+    pub approval_window: u64,
+    // Frozen while now < frozen_until; set to the unfreeze time once lifted
+    pub frozen_until: i64,
+    // A freeze imposed by guardians can only be lifted early by guardians
+    pub frozen_by_guardians: bool,
+    pub freeze_votes: Vec<GuardianApproval>,
+    pub unfreeze_votes: Vec<GuardianApproval>,
+    // Per-mint caps on what the owner may withdraw without a guardian
+    pub spending_limits: Vec<SpendingLimit>,
+    // Latest withdrawal_id; queued withdrawals are numbered from 1
+    pub withdrawal_id: u64,
+    // Timelock and inactivity period waiting out the current timelock
+    pub pending_timing: Option<PendingTiming>,
+    // Owner at creation; the vault PDA is derived from it and vault_seed, so
+    // the address survives ownership changes
+    pub creator: Pubkey,
+    // Owner proposed with transfer_ownership, not yet accepted
+    pub pending_ownership: Option<PendingOwnership>,
+    // Chosen by the creator so one key can create several vaults
+    pub vault_seed: u64,
+}
+
+impl Vault {
+    pub const SPACE: usize = 32 + // owner
+        4 + (32 * 10) + // guardians (max 10)
+        1 + // threshold
+        8 + // timelock
+        8 + // inactivity_period
+        32 + // backup_wallet
+        8 + // stake_amount
+        4 + (64 * 10) + // assets (max 10)
+        1 + 32 + // active_recovery
         8 + // recovery_id
         8 + // last_active_timestamp
+        1 + // bump
+        4 + (32 * Vault::MAX_HEARTBEAT_KEYS) + // heartbeat_keys
+        4 + (20 * Vault::MAX_EVM_GUARDIANS) + // evm_guardians
+        8 + // recovery_expiry
+        8 + // approval_window
+        8 + // frozen_until
+        1 + // frozen_by_guardians
+        4 + (GuardianApproval::SPACE * 10) + // freeze_votes
+        4 + (GuardianApproval::SPACE * 10) + // unfreeze_votes
+        4 + (SpendingLimit::SPACE * Vault::MAX_SPENDING_LIMITS) + // spending_limits
+        8 + // withdrawal_id
+        1 + PendingTiming::SPACE + // pending_timing
+        32 + // creator
+        1 + PendingOwnership::SPACE + // pending_ownership
+        8; // vault_seed
+
+    pub const MAX_HEARTBEAT_KEYS: usize = 5;
+    pub const MAX_EVM_GUARDIANS: usize = 5;
+    pub const MAX_SPENDING_LIMITS: usize = 10;
+    // Time left to gather approvals and execute once the timelock has passed
+    pub const DEFAULT_RECOVERY_EXPIRY: u64 = 30 * 24 * 60 * 60;
+
+    // Solana guardians sign directly; EVM guardians approve under their padded address
+    pub fn is_guardian(&self, key: &Pubkey) -> bool {
+        self.guardians.contains(key)
+            || self
+                .evm_guardians
+                .iter()
+                .any(|address| attestation::evm_guardian_key(address) == *key)
+    }
+
+    pub fn record_activity(&mut self) -> Result<()> {
+        self.last_active_timestamp = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+}
+
+// Protocol-wide settings, a single account per deployment
+#[account]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,
+    // Becomes admin once it accepts
+    pub pending_admin: Option<Pubkey>,
+    // May pause and unpause alongside the admin, e.g. a multisig
+    pub security_council: Option<Pubkey>,
+    // LayerZero endpoint program messages are sent through and received from
+    pub endpoint_program: Pubkey,
+    // Remote EIDs messages may be sent to and received from
+    pub allowed_eids: Vec<u32>,
+    // This chain's EID, as reported in events
+    pub local_eid: u32,
+    // Bounds on vault timelocks, in seconds
+    pub min_timelock: u64,
+    pub max_timelock: u64,
+    // Bounds on vault inactivity periods, in seconds
+    pub min_inactivity_period: u64,
+    pub max_inactivity_period: u64,
+    // Most a recovery message may pay the endpoint in native fees
+    pub max_native_fee: u64,
+    // Native fee attached to guardian stake sends
+    pub stake_native_fee: u64,
+    // PauseScope flags currently in force
+    pub paused_scopes: u8,
+    pub bump: u8,
+}
+
+impl ProtocolConfig {
+    pub const SPACE: usize = 32 + // admin
+        1 + 32 + // pending_admin
+        1 + 32 + // security_council
+        32 + // endpoint_program
+        4 + (4 * ProtocolConfig::MAX_ALLOWED_EIDS) + // allowed_eids
+        4 + // local_eid
+        8 + // min_timelock
+        8 + // max_timelock
+        8 + // min_inactivity_period
+        8 + // max_inactivity_period
+        8 + // max_native_fee
+        8 + // stake_native_fee
+        1 + // paused_scopes
         1; // bump
+
+    pub const MAX_ALLOWED_EIDS: usize = 16;
+    // The 24 to 168 hour range vaults are documented with
+    pub const DEFAULT_MIN_TIMELOCK: u64 = 24 * 60 * 60;
+    pub const DEFAULT_MAX_TIMELOCK: u64 = 168 * 60 * 60;
+    // One to 24 months
+    pub const DEFAULT_MIN_INACTIVITY_PERIOD: u64 = 30 * 24 * 60 * 60;
+    pub const DEFAULT_MAX_INACTIVITY_PERIOD: u64 = 730 * 24 * 60 * 60;
+    pub const DEFAULT_MAX_NATIVE_FEE: u64 = 100_000_000;
+}
+
+// Areas of the protocol that can be paused. `All` halts every vault instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseScope {
+    All,
+    InboundLayerZero,
+    OutboundLayerZero,
+    RecoveryExecution,
+    Staking,
 }
 
 #[account]
@@ -541,17 +2347,189 @@ impl PeerInfo {
     pub const SPACE: usize = 32 + 4 + 32;
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub enum RecoveryState {
-    None,
-    Pending {
-        new_owner: Pubkey,
-        approvals: Vec<Pubkey>,
-        malicious_approvals: Vec<Pubkey>,
-        start_timestamp: i64,
-        recovery_id: u64,
-    },
-    Completed,
+// Lamports set aside by a vault to pay permissionless crankers
+#[account]
+pub struct CrankReserve {
+    pub vault: Pubkey,
+    pub bounty: u64,
+    // Highest recovery_id each crank has been rewarded for
+    pub inactivity_rewarded_id: u64,
+    pub execution_rewarded_id: u64,
+    pub expiry_rewarded_id: u64,
+    pub bump: u8,
+}
+
+impl CrankReserve {
+    pub const SPACE: usize = 32 + // vault
+        8 + // bounty
+        8 + // inactivity_rewarded_id
+        8 + // execution_rewarded_id
+        8 + // expiry_rewarded_id
+        1; // bump
+
+    /// Marks `crank` as rewarded for `recovery_id`. Returns false if it already was.
+    pub fn claim(&mut self, crank: CrankKind, recovery_id: u64) -> bool {
+        let rewarded = match crank {
+            CrankKind::CheckInactivity => &mut self.inactivity_rewarded_id,
+            CrankKind::ExecuteRecovery => &mut self.execution_rewarded_id,
+            CrankKind::ExpireRecovery => &mut self.expiry_rewarded_id,
+        };
+        if recovery_id <= *rewarded {
+            return false;
+        }
+        *rewarded = recovery_id;
+        true
+    }
+
+    /// Lamports that can leave the reserve without dropping below rent exemption.
+    pub fn available_lamports(reserve: &AccountInfo) -> Result<u64> {
+        let rent_floor = Rent::get()?.minimum_balance(8 + CrankReserve::SPACE);
+        Ok(reserve.lamports().saturating_sub(rent_floor))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum CrankKind {
+    CheckInactivity,
+    ExecuteRecovery,
+    ExpireRecovery,
+}
+
+// One recovery attempt. The account outlives the recovery so a vault's full
+// history stays on-chain.
+#[account]
+#[derive(Default)]
+pub struct Recovery {
+    pub vault: Pubkey,
+    pub recovery_id: u64,
+    pub proposer: Pubkey,
+    pub new_owner: Pubkey,
+    pub inactivity_triggered: bool,
+    pub approvals: Vec<GuardianApproval>,
+    pub malicious_approvals: Vec<Pubkey>,
+    pub start_timestamp: i64,
+    // When the recovery was executed, cancelled or expired; 0 while pending
+    pub end_timestamp: i64,
+    pub status: RecoveryStatus,
+    pub bump: u8,
+    // Guardians voting to cancel a recovery that was not inactivity-triggered
+    pub cancel_votes: Vec<Pubkey>,
+}
+
+impl Recovery {
+    // Room for every Solana and EVM guardian to approve and every Solana guardian to vote
+    pub const SPACE: usize = 32 + // vault
+        8 + // recovery_id
+        32 + // proposer
+        32 + // new_owner
+        1 + // inactivity_triggered
+        4 + (GuardianApproval::SPACE * (10 + Vault::MAX_EVM_GUARDIANS)) + // approvals
+        4 + (32 * 10) + // malicious_approvals
+        8 + // start_timestamp
+        8 + // end_timestamp
+        1 + // status
+        1 + // bump
+        4 + (32 * 10); // cancel_votes
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RecoveryStatus {
+    #[default]
+    Pending,
+    Executed,
+    Cancelled,
+    Expired,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct GuardianApproval {
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+impl GuardianApproval {
+    pub const SPACE: usize = 32 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct SpendingLimit {
+    pub mint: Pubkey,
+    // Most the owner may withdraw alone per period
+    pub max_amount: u64,
+    // Period length in seconds
+    pub period: u64,
+    // Withdrawn so far in the current period
+    pub spent: u64,
+    pub period_start: i64,
+    // Withdrawals of more than this must be queued
+    pub queue_above: Option<u64>,
+}
+
+impl SpendingLimit {
+    pub const SPACE: usize = 32 + 8 + 8 + 8 + 8 + (1 + 8);
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PendingTiming {
+    pub timelock: u64,
+    pub inactivity_period: u64,
+    pub queued_at: i64,
+}
+
+impl PendingTiming {
+    pub const SPACE: usize = 8 + 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PendingOwnership {
+    pub new_owner: Pubkey,
+    pub proposed_at: i64,
+}
+
+impl PendingOwnership {
+    pub const SPACE: usize = 32 + 8;
+}
+
+// A large withdrawal waiting out the vault timelock. Kept after it resolves so
+// the history stays on-chain.
+#[account]
+#[derive(Default)]
+pub struct Withdrawal {
+    pub vault: Pubkey,
+    pub withdrawal_id: u64,
+    pub mint: Pubkey,
+    pub vault_token_account: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub queued_at: i64,
+    // When the withdrawal was executed, vetoed or cancelled; 0 while pending
+    pub end_timestamp: i64,
+    pub status: WithdrawalStatus,
+    pub vetoed_by: Option<Pubkey>,
+    pub bump: u8,
+}
+
+impl Withdrawal {
+    pub const SPACE: usize = 32 + // vault
+        8 + // withdrawal_id
+        32 + // mint
+        32 + // vault_token_account
+        32 + // destination
+        8 + // amount
+        8 + // queued_at
+        8 + // end_timestamp
+        1 + // status
+        1 + 32 + // vetoed_by
+        1; // bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WithdrawalStatus {
+    #[default]
+    Pending,
+    Executed,
+    Vetoed,
+    Cancelled,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -592,11 +2570,379 @@ pub struct LzReceiveParams {
     pub extra_data: Vec<u8>,
 }
 
+// Every event carries vault_id, recovery_id, actor and timestamp so indexers
+// can order a vault's history without parsing logs.
+
+#[event]
+pub struct VaultInitializedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub guardian_count: u8,
+    pub threshold: u8,
+    pub creator: Pubkey,
+    pub vault_seed: u64,
+}
+
+#[event]
+pub struct RecoveryInitiatedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub new_owner: Pubkey,
+    pub src_eid: u32,
+}
+
+#[event]
+pub struct RecoveryApprovedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct RecoveryCancelledEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryCancelVoteEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub votes: u8,
+}
+
+#[event]
+pub struct RecoveryExecutedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub new_owner: Pubkey,
+}
+
+#[event]
+pub struct RecoveryExpiredEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryDeadlinesSetEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub recovery_expiry: u64,
+    pub approval_window: u64,
+}
+
+#[event]
+pub struct TimingUpdateQueuedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub timelock: u64,
+    pub inactivity_period: u64,
+}
+
+#[event]
+pub struct TimingUpdatedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub timelock: u64,
+    pub inactivity_period: u64,
+}
+
+#[event]
+pub struct TimingUpdateCancelledEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OwnershipTransferProposedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub new_owner: Pubkey,
+}
+
+#[event]
+pub struct OwnershipTransferredEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub previous_owner: Pubkey,
+}
+
+#[event]
+pub struct OwnershipTransferCancelledEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InactivityTriggeredEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub backup_wallet: Pubkey,
+}
+
+#[event]
+pub struct ActivityRecordedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HeartbeatKeyAddedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub heartbeat_key: Pubkey,
+}
+
+#[event]
+pub struct HeartbeatKeyRemovedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub heartbeat_key: Pubkey,
+}
+
+#[event]
+pub struct EvmGuardianAddedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub evm_guardian: [u8; 20],
+}
+
+#[event]
+pub struct EvmGuardianRemovedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub evm_guardian: [u8; 20],
+}
+
+#[event]
+pub struct PeerSetEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub eid: u32,
+    pub peer: [u8; 32],
+}
+
+#[event]
+pub struct GuardianStakedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub amount: u64,
+    pub dst_eid: u32,
+}
+
+#[event]
+pub struct AssetTransferredEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub to: Pubkey,
+}
+
+#[event]
+pub struct MaliciousVoteEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub guardian: Pubkey,
+    pub votes: u8,
+}
+
+#[event]
+pub struct CrankBountySetEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub bounty: u64,
+}
+
+#[event]
+pub struct CrankRewardedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FreezeVoteEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    // false for a vote to unfreeze
+    pub freeze: bool,
+    pub votes: u8,
+}
+
+#[event]
+pub struct VaultFrozenEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub frozen_until: i64,
+    pub by_guardians: bool,
+}
+
+#[event]
+pub struct VaultUnfrozenEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetWithdrawnEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub to: Pubkey,
+    pub co_signer: Option<Pubkey>,
+}
+
+#[event]
+pub struct SpendingLimitSetEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub mint: Pubkey,
+    pub max_amount: u64,
+    pub period: u64,
+    pub queue_above: Option<u64>,
+}
+
+#[event]
+pub struct WithdrawalQueuedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub withdrawal_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub to: Pubkey,
+    pub co_signer: Option<Pubkey>,
+}
+
+#[event]
+pub struct WithdrawalExecutedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub withdrawal_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub to: Pubkey,
+}
+
+#[event]
+pub struct WithdrawalVetoedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub withdrawal_id: u64,
+}
+
+#[event]
+pub struct WithdrawalCancelledEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub withdrawal_id: u64,
+}
+
+#[event]
+pub struct SpendingLimitRemovedEvent {
+    pub vault_id: Pubkey,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub mint: Pubkey,
+}
+
 #[event]
 pub struct GuardianSlashedEvent {
     pub vault_id: Pubkey,
     pub guardian: Pubkey,
     pub amount: u64,
+    pub recovery_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+// Protocol config events belong to no vault, so they carry only actor and timestamp
+
+#[event]
+pub struct ConfigUpdatedEvent {
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub paused_scopes: u8,
+}
+
+#[event]
+pub struct AdminChangedEvent {
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub previous_admin: Pubkey,
 }
 
 #[error_code]
@@ -617,4 +2963,96 @@ pub enum ErrorCode {
     InsufficientStake,
     #[msg("Unauthorized source")]
     UnauthorizedSource,
+    #[msg("Heartbeat key already registered")]
+    DuplicateHeartbeatKey,
+    #[msg("Too many heartbeat keys")]
+    TooManyHeartbeatKeys,
+    #[msg("Heartbeat key not found")]
+    HeartbeatKeyNotFound,
+    #[msg("Recovery was not triggered by inactivity")]
+    NotInactivityRecovery,
+    #[msg("Recovery threshold or timelock not yet met")]
+    RecoveryNotReady,
+    #[msg("Token accounts are required to transfer vault assets")]
+    MissingTokenAccount,
+    #[msg("Crank reserve cannot cover this amount")]
+    InsufficientCrankReserve,
+    #[msg("Invalid or missing guardian attestation")]
+    InvalidAttestation,
+    #[msg("Guardian approval has expired")]
+    ApprovalExpired,
+    #[msg("EVM guardian already registered")]
+    DuplicateEvmGuardian,
+    #[msg("Too many EVM guardians")]
+    TooManyEvmGuardians,
+    #[msg("EVM guardian not found")]
+    EvmGuardianNotFound,
+    #[msg("Recovery has expired")]
+    RecoveryExpired,
+    #[msg("Recovery has not expired yet")]
+    RecoveryNotExpired,
+    #[msg("A recovery is in progress")]
+    RecoveryInProgress,
+    #[msg("Recovery expiry must exceed the timelock and the approval window must be non-zero")]
+    InvalidRecoveryDeadline,
+    #[msg("Owner has not been inactive for the inactivity period")]
+    InactivityNotElapsed,
+    #[msg("Recovery account is required to initiate a recovery")]
+    MissingRecoveryAccount,
+    #[msg("Vault is frozen")]
+    VaultFrozen,
+    #[msg("Vault is not frozen")]
+    VaultNotFrozen,
+    #[msg("Owner cannot freeze the vault again so soon after the last freeze")]
+    FreezeCooldown,
+    #[msg("Withdrawal exceeds the spending limit without a guardian co-signature")]
+    SpendingLimitExceeded,
+    #[msg("A guardian must co-sign this change")]
+    GuardianCoSignRequired,
+    #[msg("Spending limit period must be greater than zero")]
+    InvalidSpendingLimit,
+    #[msg("Maximum number of spending limits reached")]
+    TooManySpendingLimits,
+    #[msg("No spending limit for this mint")]
+    SpendingLimitNotFound,
+    #[msg("Withdrawal is above the queue threshold and must be queued")]
+    WithdrawalMustBeQueued,
+    #[msg("Withdrawal is not pending")]
+    WithdrawalNotPending,
+    #[msg("Withdrawal delay has not elapsed")]
+    WithdrawalDelayNotElapsed,
+    #[msg("Token account does not match the queued withdrawal")]
+    TokenAccountMismatch,
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+    #[msg("Endpoint does not match the protocol config")]
+    InvalidEndpoint,
+    #[msg("Destination EID is not allowed by the protocol config")]
+    UnsupportedEid,
+    #[msg("LayerZero messaging fee exceeds the configured maximum")]
+    MessagingFeeTooHigh,
+    #[msg("Too many allowed EIDs")]
+    TooManyAllowedEids,
+    #[msg("Minimum timelock exceeds the maximum")]
+    InvalidTimelockBounds,
+    #[msg("Minimum inactivity period exceeds the maximum")]
+    InvalidInactivityBounds,
+    #[msg("Timelock or inactivity period is outside the protocol bounds")]
+    TimingOutOfBounds,
+    #[msg("No timing change is queued")]
+    NoPendingTiming,
+    #[msg("Timing change delay has not elapsed")]
+    TimingDelayNotElapsed,
+    #[msg("New owner must differ from the current owner")]
+    InvalidNewOwner,
+    #[msg("No ownership transfer is pending")]
+    NoPendingOwnership,
+    #[msg("Ownership transfer delay has not elapsed")]
+    OwnershipDelayNotElapsed,
+    #[msg("Token accounts do not match the vault asset or its new owner")]
+    AssetAccountMismatch,
+    #[msg("Message sender is not the vault's peer for this chain")]
+    UnknownPeer,
+    #[msg("Inactivity recoveries are cancelled with cancel_inactivity_recovery")]
+    InactivityRecovery,
 }
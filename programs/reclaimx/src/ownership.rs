@@ -0,0 +1,66 @@
+// Pure transitions for an owner moving the vault to a new key without a
+// guardian recovery. The owner proposes the new key, which accepts once
+// OWNERSHIP_TRANSFER_DELAY has passed; until then the owner can cancel and
+// guardians can freeze the vault to hold it back.
+use anchor_lang::prelude::*;
+
+use crate::{freeze, recovery, ErrorCode, PendingOwnership, Vault};
+
+// Wait between proposing and accepting a new owner
+pub const OWNERSHIP_TRANSFER_DELAY: u64 = 24 * 60 * 60;
+
+/// Proposes `new_owner`, replacing any pending proposal. Not allowed while a
+/// recovery is pending, as that recovery decides who owns the vault.
+pub fn propose(vault: &mut Vault, new_owner: Pubkey, now: i64) -> Result<()> {
+    require!(
+        vault.active_recovery.is_none(),
+        ErrorCode::RecoveryInProgress
+    );
+    require!(new_owner != vault.owner, ErrorCode::InvalidNewOwner);
+    vault.pending_ownership = Some(PendingOwnership {
+        new_owner,
+        proposed_at: now,
+    });
+    Ok(())
+}
+
+/// Whether `signer` is the proposed owner and the delay has passed.
+pub fn ready_to_accept(vault: &Vault, signer: &Pubkey, now: i64) -> bool {
+    vault.pending_ownership.as_ref().is_some_and(|pending| {
+        pending.new_owner == *signer
+            && recovery::timelock_elapsed(pending.proposed_at, OWNERSHIP_TRANSFER_DELAY, now)
+    })
+}
+
+/// Hands the vault to `signer`, who must be the proposed owner, and returns
+/// the previous owner. The previous owner's heartbeat keys are dropped.
+pub fn accept(vault: &mut Vault, signer: Pubkey, now: i64) -> Result<Pubkey> {
+    let pending = vault
+        .pending_ownership
+        .clone()
+        .ok_or(ErrorCode::NoPendingOwnership)?;
+    require!(pending.new_owner == signer, ErrorCode::Unauthorized);
+    require!(
+        ready_to_accept(vault, &signer, now),
+        ErrorCode::OwnershipDelayNotElapsed
+    );
+    require!(
+        vault.active_recovery.is_none(),
+        ErrorCode::RecoveryInProgress
+    );
+    freeze::require_not_frozen(vault, now)?;
+
+    let previous = vault.owner;
+    vault.owner = signer;
+    vault.pending_ownership = None;
+    vault.heartbeat_keys.clear();
+    Ok(previous)
+}
+
+pub fn cancel(vault: &mut Vault) -> Result<()> {
+    require!(
+        vault.pending_ownership.take().is_some(),
+        ErrorCode::NoPendingOwnership
+    );
+    Ok(())
+}
@@ -0,0 +1,267 @@
+// Pure recovery state transitions. Handlers read the clock and perform CPIs;
+// everything that decides who may do what lives here so it can be tested off-chain.
+use anchor_lang::prelude::*;
+
+use crate::{freeze, ErrorCode, GuardianApproval, Recovery, RecoveryStatus, Vault};
+
+// Number of malicious votes after which a guardian is slashed
+pub const SLASH_VOTES: usize = 3;
+
+#[derive(Clone, Copy)]
+pub enum ApprovalOutcome {
+    Pending { approvals: usize },
+    Completed { new_owner: Pubkey, approvals: usize },
+}
+
+#[derive(Clone, Copy)]
+pub enum CancelOutcome {
+    Voted { votes: usize },
+    Cancelled,
+}
+
+pub fn timelock_elapsed(start_timestamp: i64, timelock: u64, now: i64) -> bool {
+    let timelock = i64::try_from(timelock).unwrap_or(i64::MAX);
+    now >= start_timestamp.saturating_add(timelock)
+}
+
+pub fn inactivity_elapsed(vault: &Vault, now: i64) -> bool {
+    let period = i64::try_from(vault.inactivity_period).unwrap_or(i64::MAX);
+    now >= vault.last_active_timestamp.saturating_add(period)
+}
+
+/// Whether a pending recovery has outlived `recovery_expiry` and can no
+/// longer be approved or executed.
+pub fn expired(vault: &Vault, recovery: &Recovery, now: i64) -> bool {
+    recovery.status == RecoveryStatus::Pending
+        && timelock_elapsed(recovery.start_timestamp, vault.recovery_expiry, now)
+}
+
+/// Number of approvals given less than `approval_window` seconds before `now`.
+pub fn live_approvals(approvals: &[GuardianApproval], approval_window: u64, now: i64) -> usize {
+    approvals
+        .iter()
+        .filter(|approval| !timelock_elapsed(approval.timestamp, approval_window, now))
+        .count()
+}
+
+/// Live approvals of `recovery` from keys that are still guardians of the
+/// vault, so approvals from a since-removed EVM guardian do not count.
+pub fn guardian_approvals(vault: &Vault, recovery: &Recovery, now: i64) -> usize {
+    recovery
+        .approvals
+        .iter()
+        .filter(|approval| vault.is_guardian(&approval.guardian))
+        .filter(|approval| !timelock_elapsed(approval.timestamp, vault.approval_window, now))
+        .count()
+}
+
+/// Opens `recovery`, the account at `address`, as the vault's active recovery
+/// towards `new_owner` and returns its recovery_id. A pending recovery must be
+/// executed, cancelled or expired first.
+pub fn start(
+    vault: &mut Vault,
+    recovery: &mut Recovery,
+    address: Pubkey,
+    proposer: Pubkey,
+    new_owner: Pubkey,
+    now: i64,
+    inactivity_triggered: bool,
+) -> Result<u64> {
+    require!(vault.active_recovery.is_none(), ErrorCode::RecoveryInProgress);
+    vault.recovery_id += 1;
+    vault.active_recovery = Some(address);
+
+    recovery.recovery_id = vault.recovery_id;
+    recovery.proposer = proposer;
+    recovery.new_owner = new_owner;
+    recovery.inactivity_triggered = inactivity_triggered;
+    recovery.approvals = vec![];
+    recovery.malicious_approvals = vec![];
+    recovery.cancel_votes = vec![];
+    recovery.start_timestamp = now;
+    recovery.end_timestamp = 0;
+    recovery.status = RecoveryStatus::Pending;
+    Ok(vault.recovery_id)
+}
+
+fn require_pending(recovery: &Recovery) -> Result<()> {
+    require!(
+        recovery.status == RecoveryStatus::Pending,
+        ErrorCode::NoActiveRecovery
+    );
+    Ok(())
+}
+
+// Records how the active recovery ended and detaches it from the vault
+fn finish(vault: &mut Vault, recovery: &mut Recovery, status: RecoveryStatus, now: i64) {
+    recovery.status = status;
+    recovery.end_timestamp = now;
+    vault.active_recovery = None;
+}
+
+/// Records a guardian approval and completes the recovery once the
+/// threshold is met and the timelock has passed.
+pub fn approve(
+    vault: &mut Vault,
+    recovery: &mut Recovery,
+    guardian: Pubkey,
+    now: i64,
+) -> Result<ApprovalOutcome> {
+    let approval_count = record_approval(vault, recovery, guardian, now)?;
+
+    if ready_to_execute(vault, recovery, now) {
+        let new_owner = execute(vault, recovery, now)?;
+        return Ok(ApprovalOutcome::Completed {
+            new_owner,
+            approvals: approval_count,
+        });
+    }
+
+    Ok(ApprovalOutcome::Pending {
+        approvals: approval_count,
+    })
+}
+
+/// Records a guardian approval without executing the recovery and returns
+/// the number of current guardians' approvals still inside the approval
+/// window. A guardian whose earlier approval has gone stale may approve again.
+pub fn record_approval(
+    vault: &Vault,
+    recovery: &mut Recovery,
+    guardian: Pubkey,
+    now: i64,
+) -> Result<usize> {
+    require!(vault.is_guardian(&guardian), ErrorCode::Unauthorized);
+    require_pending(recovery)?;
+    require!(!expired(vault, recovery, now), ErrorCode::RecoveryExpired);
+
+    let window = vault.approval_window;
+    match recovery
+        .approvals
+        .iter_mut()
+        .find(|approval| approval.guardian == guardian)
+    {
+        Some(approval) if !timelock_elapsed(approval.timestamp, window, now) => {
+            return err!(ErrorCode::DuplicateApproval);
+        }
+        Some(approval) => approval.timestamp = now,
+        None => recovery.approvals.push(GuardianApproval {
+            guardian,
+            timestamp: now,
+        }),
+    }
+    Ok(guardian_approvals(vault, recovery, now))
+}
+
+/// Whether the recovery is pending with enough live approvals, its timelock
+/// has passed, it has not expired and the vault is not frozen.
+pub fn ready_to_execute(vault: &Vault, recovery: &Recovery, now: i64) -> bool {
+    recovery.status == RecoveryStatus::Pending
+        && !freeze::is_frozen(vault, now)
+        && guardian_approvals(vault, recovery, now) >= vault.threshold as usize
+        && timelock_elapsed(recovery.start_timestamp, vault.timelock, now)
+        && !expired(vault, recovery, now)
+}
+
+/// Completes a recovery that is ready to execute and returns the new owner.
+/// Heartbeat keys belonged to the previous owner and are dropped.
+pub fn execute(vault: &mut Vault, recovery: &mut Recovery, now: i64) -> Result<Pubkey> {
+    require_pending(recovery)?;
+    require!(!expired(vault, recovery, now), ErrorCode::RecoveryExpired);
+    freeze::require_not_frozen(vault, now)?;
+    require!(
+        ready_to_execute(vault, recovery, now),
+        ErrorCode::RecoveryNotReady
+    );
+
+    vault.owner = recovery.new_owner;
+    vault.pending_ownership = None;
+    vault.heartbeat_keys.clear();
+    finish(vault, recovery, RecoveryStatus::Executed, now);
+    Ok(recovery.new_owner)
+}
+
+/// Closes a pending recovery whose deadline has passed and returns its recovery_id.
+pub fn expire(vault: &mut Vault, recovery: &mut Recovery, now: i64) -> Result<u64> {
+    require_pending(recovery)?;
+    require!(expired(vault, recovery, now), ErrorCode::RecoveryNotExpired);
+    finish(vault, recovery, RecoveryStatus::Expired, now);
+    Ok(recovery.recovery_id)
+}
+
+/// Records a malicious vote from `voter` and returns the number of votes cast.
+pub fn mark_malicious(vault: &Vault, recovery: &mut Recovery, voter: Pubkey) -> Result<usize> {
+    require!(vault.guardians.contains(&voter), ErrorCode::Unauthorized);
+    require_pending(recovery)?;
+    require!(
+        !recovery.malicious_approvals.contains(&voter),
+        ErrorCode::DuplicateApproval
+    );
+    recovery.malicious_approvals.push(voter);
+    Ok(recovery.malicious_approvals.len())
+}
+
+/// Opens a recovery to the backup wallet if the owner has been inactive for
+/// `inactivity_period` and no recovery is pending. Returns the new recovery_id.
+pub fn check_inactivity(
+    vault: &mut Vault,
+    recovery: &mut Recovery,
+    address: Pubkey,
+    cranker: Pubkey,
+    now: i64,
+) -> Result<u64> {
+    require!(
+        inactivity_elapsed(vault, now),
+        ErrorCode::InactivityNotElapsed
+    );
+    let backup_wallet = vault.backup_wallet;
+    start(vault, recovery, address, cranker, backup_wallet, now, true)
+}
+
+/// Cancels a pending recovery that was started by `check_inactivity`.
+pub fn cancel_inactivity_recovery(
+    vault: &mut Vault,
+    recovery: &mut Recovery,
+    now: i64,
+) -> Result<u64> {
+    require_pending(recovery)?;
+    require!(
+        recovery.inactivity_triggered,
+        ErrorCode::NotInactivityRecovery
+    );
+    finish(vault, recovery, RecoveryStatus::Cancelled, now);
+    Ok(recovery.recovery_id)
+}
+
+/// Cancels a pending recovery that was not started by `check_inactivity`.
+/// The owner cancels outright only a recovery they proposed, so a stolen
+/// owner key cannot stop a guardian recovery; guardians vote and cancel once
+/// a quorum of them agrees.
+pub fn cancel(
+    vault: &mut Vault,
+    recovery: &mut Recovery,
+    signer: Pubkey,
+    now: i64,
+) -> Result<CancelOutcome> {
+    require_pending(recovery)?;
+    require!(
+        !recovery.inactivity_triggered,
+        ErrorCode::InactivityRecovery
+    );
+
+    if signer != vault.owner || recovery.proposer != vault.owner {
+        require!(vault.guardians.contains(&signer), ErrorCode::Unauthorized);
+        require!(
+            !recovery.cancel_votes.contains(&signer),
+            ErrorCode::DuplicateApproval
+        );
+        recovery.cancel_votes.push(signer);
+        let votes = recovery.cancel_votes.len();
+        if votes < freeze::quorum(vault) {
+            return Ok(CancelOutcome::Voted { votes });
+        }
+    }
+
+    finish(vault, recovery, RecoveryStatus::Cancelled, now);
+    Ok(CancelOutcome::Cancelled)
+}
@@ -0,0 +1,146 @@
+// Pure per-mint spending limits for owner withdrawals. Within each period the
+// owner may withdraw up to `max_amount` alone; anything beyond that, and any
+// change that loosens a limit, needs a Solana guardian to co-sign. Amounts over
+// `queue_above` must go through the withdrawal queue instead.
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, SpendingLimit, Vault};
+
+impl SpendingLimit {
+    // Starts a fresh period once the current one has run out
+    fn roll(&mut self, now: i64) {
+        let period = i64::try_from(self.period).unwrap_or(i64::MAX);
+        if now >= self.period_start.saturating_add(period) {
+            self.period_start = now;
+            self.spent = 0;
+        }
+    }
+
+    /// Amount the owner may still withdraw alone in the period containing `now`.
+    pub fn remaining(&self, now: i64) -> u64 {
+        let mut limit = self.clone();
+        limit.roll(now);
+        limit.max_amount.saturating_sub(limit.spent)
+    }
+
+    // Whether moving from `self` to the new values lets more leave the vault
+    fn loosened_by(&self, max_amount: u64, period: u64, queue_above: Option<u64>) -> bool {
+        let queue_loosened = match (self.queue_above, queue_above) {
+            (Some(_), None) => true,
+            (Some(current), Some(new)) => new > current,
+            (None, _) => false,
+        };
+        max_amount > self.max_amount || period < self.period || queue_loosened
+    }
+}
+
+/// Whether withdrawing `amount` of `mint` has to wait in the withdrawal queue.
+pub fn must_queue(vault: &Vault, mint: &Pubkey, amount: u64) -> bool {
+    vault
+        .spending_limits
+        .iter()
+        .find(|limit| limit.mint == *mint)
+        .and_then(|limit| limit.queue_above)
+        .is_some_and(|queue_above| amount > queue_above)
+}
+
+fn require_guardian(vault: &Vault, co_signer: Option<Pubkey>) -> Result<()> {
+    if let Some(co_signer) = co_signer {
+        require!(
+            vault.guardians.contains(&co_signer),
+            ErrorCode::Unauthorized
+        );
+    }
+    Ok(())
+}
+
+/// Counts `amount` of `mint` against its spending limit, if there is one. A
+/// withdrawal over the remaining allowance needs a guardian `co_signer`; it
+/// still counts towards the period.
+pub fn record_withdrawal(
+    vault: &mut Vault,
+    mint: Pubkey,
+    amount: u64,
+    co_signer: Option<Pubkey>,
+    now: i64,
+) -> Result<()> {
+    require_guardian(vault, co_signer)?;
+    let Some(limit) = vault.spending_limits.iter_mut().find(|l| l.mint == mint) else {
+        return Ok(());
+    };
+    limit.roll(now);
+    require!(
+        co_signer.is_some() || limit.spent.saturating_add(amount) <= limit.max_amount,
+        ErrorCode::SpendingLimitExceeded
+    );
+    limit.spent = limit.spent.saturating_add(amount);
+    Ok(())
+}
+
+/// Gives back `amount` of `mint` that was counted at `counted_at` but never
+/// left the vault. Once the limit has moved on to a later period the amount
+/// no longer weighs on it, so nothing is given back.
+pub fn refund_withdrawal(vault: &mut Vault, mint: Pubkey, amount: u64, counted_at: i64, now: i64) {
+    let Some(limit) = vault.spending_limits.iter_mut().find(|l| l.mint == mint) else {
+        return;
+    };
+    limit.roll(now);
+    if limit.period_start <= counted_at {
+        limit.spent = limit.spent.saturating_sub(amount);
+    }
+}
+
+/// Adds or replaces the limit for `mint`. Tightening needs only the owner;
+/// raising the amount or queue threshold, shortening the period or no longer
+/// queueing needs a guardian `co_signer`.
+pub fn set_limit(
+    vault: &mut Vault,
+    mint: Pubkey,
+    max_amount: u64,
+    period: u64,
+    queue_above: Option<u64>,
+    co_signer: Option<Pubkey>,
+    now: i64,
+) -> Result<()> {
+    require!(period > 0, ErrorCode::InvalidSpendingLimit);
+    require_guardian(vault, co_signer)?;
+
+    if let Some(limit) = vault.spending_limits.iter_mut().find(|l| l.mint == mint) {
+        require!(
+            co_signer.is_some() || !limit.loosened_by(max_amount, period, queue_above),
+            ErrorCode::GuardianCoSignRequired
+        );
+        limit.roll(now);
+        limit.max_amount = max_amount;
+        limit.period = period;
+        limit.queue_above = queue_above;
+        return Ok(());
+    }
+
+    require!(
+        vault.spending_limits.len() < Vault::MAX_SPENDING_LIMITS,
+        ErrorCode::TooManySpendingLimits
+    );
+    vault.spending_limits.push(SpendingLimit {
+        mint,
+        max_amount,
+        period,
+        spent: 0,
+        period_start: now,
+        queue_above,
+    });
+    Ok(())
+}
+
+/// Drops the limit for `mint`, which always needs a guardian `co_signer`.
+pub fn remove_limit(vault: &mut Vault, mint: Pubkey, co_signer: Option<Pubkey>) -> Result<()> {
+    require_guardian(vault, co_signer)?;
+    require!(co_signer.is_some(), ErrorCode::GuardianCoSignRequired);
+    let len = vault.spending_limits.len();
+    vault.spending_limits.retain(|limit| limit.mint != mint);
+    require!(
+        vault.spending_limits.len() < len,
+        ErrorCode::SpendingLimitNotFound
+    );
+    Ok(())
+}
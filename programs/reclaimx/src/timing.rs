@@ -0,0 +1,73 @@
+// Pure transitions for changing a vault's timelock and inactivity period. New
+// values must sit within the ProtocolConfig bounds and only apply once the
+// current timelock has passed, so a stolen owner key cannot shorten the window
+// guardians have to react before it matters.
+use anchor_lang::prelude::*;
+
+use crate::{freeze, recovery, ErrorCode, PendingTiming, ProtocolConfig, Vault};
+
+fn require_valid(vault: &Vault, config: &ProtocolConfig, timing: &PendingTiming) -> Result<()> {
+    config.require_valid_timing(timing.timelock, timing.inactivity_period)?;
+    // A recovery must stay executable for some time after its timelock
+    require!(
+        vault.recovery_expiry > timing.timelock,
+        ErrorCode::InvalidRecoveryDeadline
+    );
+    Ok(())
+}
+
+/// Queues new timing values, replacing any already queued.
+pub fn propose(
+    vault: &mut Vault,
+    config: &ProtocolConfig,
+    timelock: u64,
+    inactivity_period: u64,
+    now: i64,
+) -> Result<()> {
+    let timing = PendingTiming {
+        timelock,
+        inactivity_period,
+        queued_at: now,
+    };
+    require_valid(vault, config, &timing)?;
+    vault.pending_timing = Some(timing);
+    Ok(())
+}
+
+/// Whether queued values exist and the current timelock has passed since.
+pub fn ready_to_apply(vault: &Vault, now: i64) -> bool {
+    vault
+        .pending_timing
+        .as_ref()
+        .is_some_and(|timing| recovery::timelock_elapsed(timing.queued_at, vault.timelock, now))
+}
+
+/// Applies the queued values and returns them. Bounds are checked again as the
+/// protocol config may have changed in the meantime; a pending recovery or a
+/// freeze holds the change back.
+pub fn apply(vault: &mut Vault, config: &ProtocolConfig, now: i64) -> Result<PendingTiming> {
+    let timing = vault
+        .pending_timing
+        .clone()
+        .ok_or(ErrorCode::NoPendingTiming)?;
+    require!(ready_to_apply(vault, now), ErrorCode::TimingDelayNotElapsed);
+    require!(
+        vault.active_recovery.is_none(),
+        ErrorCode::RecoveryInProgress
+    );
+    freeze::require_not_frozen(vault, now)?;
+    require_valid(vault, config, &timing)?;
+
+    vault.timelock = timing.timelock;
+    vault.inactivity_period = timing.inactivity_period;
+    vault.pending_timing = None;
+    Ok(timing)
+}
+
+pub fn cancel(vault: &mut Vault) -> Result<()> {
+    require!(
+        vault.pending_timing.take().is_some(),
+        ErrorCode::NoPendingTiming
+    );
+    Ok(())
+}
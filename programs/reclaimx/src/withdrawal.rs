@@ -0,0 +1,107 @@
+// Pure transitions for queued withdrawals. A withdrawal over its mint's
+// `queue_above` threshold waits out the vault timelock, during which any Solana
+// guardian can veto it and the owner can cancel it. Either way its amount goes
+// back to the spending limit it was counted against.
+use anchor_lang::prelude::*;
+
+use crate::{freeze, recovery, spending, ErrorCode, Vault, Withdrawal, WithdrawalStatus};
+
+/// Queues `withdrawal`, whose mint, accounts and amount are already set, and
+/// returns its withdrawal_id. The amount counts against the mint's spending
+/// limit straight away, so one over the remaining allowance needs a guardian
+/// `co_signer` here rather than at execution.
+pub fn queue(
+    vault: &mut Vault,
+    withdrawal: &mut Withdrawal,
+    co_signer: Option<Pubkey>,
+    now: i64,
+) -> Result<u64> {
+    freeze::require_not_frozen(vault, now)?;
+    spending::record_withdrawal(vault, withdrawal.mint, withdrawal.amount, co_signer, now)?;
+    vault.withdrawal_id += 1;
+
+    withdrawal.withdrawal_id = vault.withdrawal_id;
+    withdrawal.queued_at = now;
+    withdrawal.end_timestamp = 0;
+    withdrawal.status = WithdrawalStatus::Pending;
+    withdrawal.vetoed_by = None;
+    Ok(vault.withdrawal_id)
+}
+
+fn require_pending(withdrawal: &Withdrawal) -> Result<()> {
+    require!(
+        withdrawal.status == WithdrawalStatus::Pending,
+        ErrorCode::WithdrawalNotPending
+    );
+    Ok(())
+}
+
+/// Whether the withdrawal is pending, past its delay and the vault is not frozen.
+pub fn ready_to_execute(vault: &Vault, withdrawal: &Withdrawal, now: i64) -> bool {
+    withdrawal.status == WithdrawalStatus::Pending
+        && recovery::timelock_elapsed(withdrawal.queued_at, vault.timelock, now)
+        && !freeze::is_frozen(vault, now)
+}
+
+pub fn execute(vault: &Vault, withdrawal: &mut Withdrawal, now: i64) -> Result<()> {
+    require_pending(withdrawal)?;
+    require!(
+        recovery::timelock_elapsed(withdrawal.queued_at, vault.timelock, now),
+        ErrorCode::WithdrawalDelayNotElapsed
+    );
+    freeze::require_not_frozen(vault, now)?;
+    withdrawal.status = WithdrawalStatus::Executed;
+    withdrawal.end_timestamp = now;
+    Ok(())
+}
+
+// Returns a withdrawal that will never execute to the mint's spending limit
+fn refund(vault: &mut Vault, withdrawal: &Withdrawal, now: i64) {
+    spending::refund_withdrawal(
+        vault,
+        withdrawal.mint,
+        withdrawal.amount,
+        withdrawal.queued_at,
+        now,
+    );
+}
+
+/// Any Solana guardian may veto a pending withdrawal, even once its delay has
+/// passed, for as long as it has not executed.
+pub fn veto(
+    vault: &mut Vault,
+    withdrawal: &mut Withdrawal,
+    guardian: Pubkey,
+    now: i64,
+) -> Result<()> {
+    require!(vault.guardians.contains(&guardian), ErrorCode::Unauthorized);
+    require_pending(withdrawal)?;
+    withdrawal.status = WithdrawalStatus::Vetoed;
+    withdrawal.vetoed_by = Some(guardian);
+    withdrawal.end_timestamp = now;
+    refund(vault, withdrawal, now);
+    Ok(())
+}
+
+pub fn cancel(vault: &mut Vault, withdrawal: &mut Withdrawal, now: i64) -> Result<()> {
+    require_pending(withdrawal)?;
+    withdrawal.status = WithdrawalStatus::Cancelled;
+    withdrawal.end_timestamp = now;
+    refund(vault, withdrawal, now);
+    Ok(())
+}
+
+/// Lowers the tracked amount of assets held in `token_account` once `amount`
+/// has left it, dropping those that are gone, so recovery only moves what is left.
+pub fn deduct_assets(vault: &mut Vault, token_account: &Pubkey, amount: u64) {
+    for asset in vault
+        .assets
+        .iter_mut()
+        .filter(|asset| asset.token_account == *token_account)
+    {
+        asset.amount = asset.amount.saturating_sub(amount);
+    }
+    vault
+        .assets
+        .retain(|asset| asset.token_account != *token_account || asset.amount > 0);
+}
@@ -0,0 +1,1084 @@
+#![allow(dead_code)]
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use mock_endpoint::{EndpointState, ENDPOINT_SEED};
+use reclaimx::{
+    config::ConfigParams, Asset, AssetType, PauseScope, ProtocolConfig, Recovery, Vault,
+    Withdrawal, LAYERZERO_ENDPOINT,
+};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::{AccountSharedData, ReadableAccount, WritableAccount},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    clock::Clock,
+    hash::hashv,
+    instruction::{AccountMeta, Instruction},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    transaction::Transaction,
+};
+
+pub const TIMELOCK: u64 = 3_600;
+pub const INACTIVITY_PERIOD: u64 = 86_400;
+pub const STAKE_AMOUNT: u64 = 1_000;
+pub const MOCK_BASE_FEE: u64 = 5_000;
+pub const MOCK_FEE_PER_BYTE: u64 = 10;
+
+pub struct TestVault {
+    pub owner: Keypair,
+    pub guardians: Vec<Keypair>,
+    pub backup_wallet: Pubkey,
+    pub vault: Pubkey,
+    pub endpoint: Pubkey,
+}
+
+pub async fn program_test_context() -> ProgramTestContext {
+    let mut context = program_test_without_config().await;
+    let admin = context.payer.pubkey();
+    let ix = initialize_config_ix(&admin, &admin, admin, config_params());
+    process(&mut context, &[ix], &[]).await.unwrap();
+    context
+}
+
+/// Both programs deployed with the payer as upgrade authority and the mock
+/// endpoint initialized, but no protocol config yet.
+pub async fn program_test_without_config() -> ProgramTestContext {
+    // Both programs are loaded from the SBF build output (`cargo test-sbf`)
+    let mut program_test = ProgramTest::new("reclaimx", reclaimx::ID, None);
+    program_test.add_program("mock_endpoint", LAYERZERO_ENDPOINT, None);
+    let mut context = program_test.start_with_context().await;
+
+    // ProgramTest deploys with the non-upgradeable loader, so write the
+    // ProgramData account the upgradeable loader would keep
+    let data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(context.payer.pubkey()),
+    })
+    .unwrap();
+    let mut program_data = AccountSharedData::new(
+        Rent::default().minimum_balance(data.len()),
+        data.len(),
+        &bpf_loader_upgradeable::ID,
+    );
+    program_data.set_data_from_slice(&data);
+    context.set_account(&program_data_pda(), &program_data);
+
+    let ix = Instruction {
+        program_id: LAYERZERO_ENDPOINT,
+        accounts: mock_endpoint::accounts::Initialize {
+            endpoint: endpoint_pda(),
+            payer: context.payer.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: mock_endpoint::instruction::Initialize {
+            base_fee: MOCK_BASE_FEE,
+            fee_per_byte: MOCK_FEE_PER_BYTE,
+        }
+        .data(),
+    };
+    process(&mut context, &[ix], &[]).await.unwrap();
+    context
+}
+
+// Defaults with room for the hour- and day-scale timing tests run with
+pub fn config_params() -> ConfigParams {
+    ConfigParams {
+        min_timelock: 60,
+        min_inactivity_period: 60,
+        ..ConfigParams::default()
+    }
+}
+
+pub fn endpoint_pda() -> Pubkey {
+    Pubkey::find_program_address(&[ENDPOINT_SEED], &LAYERZERO_ENDPOINT).0
+}
+
+pub fn event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &reclaimx::ID).0
+}
+
+pub fn program_data_pda() -> Pubkey {
+    Pubkey::find_program_address(&[reclaimx::ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+pub fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &reclaimx::ID).0
+}
+
+pub fn vault_pda(creator: &Pubkey, vault_seed: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"vault", creator.as_ref(), &vault_seed.to_le_bytes()],
+        &reclaimx::ID,
+    )
+    .0
+}
+
+pub fn peer_pda(vault: &Pubkey, eid: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"peer", vault.as_ref(), &eid.to_le_bytes()],
+        &reclaimx::ID,
+    )
+    .0
+}
+
+pub fn recovery_pda(vault: &Pubkey, recovery_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"recovery", vault.as_ref(), &recovery_id.to_le_bytes()],
+        &reclaimx::ID,
+    )
+    .0
+}
+
+pub fn withdrawal_pda(vault: &Pubkey, withdrawal_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"withdrawal", vault.as_ref(), &withdrawal_id.to_le_bytes()],
+        &reclaimx::ID,
+    )
+    .0
+}
+
+pub fn crank_reserve_pda(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"crank_reserve", vault.as_ref()], &reclaimx::ID).0
+}
+
+pub async fn process(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+pub async fn fund(context: &mut ProgramTestContext, to: &Pubkey, lamports: u64) {
+    let ix = system_instruction::transfer(&context.payer.pubkey(), to, lamports);
+    process(context, &[ix], &[]).await.unwrap();
+}
+
+pub async fn fetch_vault(context: &mut ProgramTestContext, vault: &Pubkey) -> Vault {
+    let account = context
+        .banks_client
+        .get_account(*vault)
+        .await
+        .unwrap()
+        .unwrap();
+    Vault::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+pub async fn fetch_recovery(
+    context: &mut ProgramTestContext,
+    vault: &Pubkey,
+    recovery_id: u64,
+) -> Recovery {
+    let account = context
+        .banks_client
+        .get_account(recovery_pda(vault, recovery_id))
+        .await
+        .unwrap()
+        .unwrap();
+    Recovery::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+pub async fn fetch_withdrawal(
+    context: &mut ProgramTestContext,
+    vault: &Pubkey,
+    withdrawal_id: u64,
+) -> Withdrawal {
+    let account = context
+        .banks_client
+        .get_account(withdrawal_pda(vault, withdrawal_id))
+        .await
+        .unwrap()
+        .unwrap();
+    Withdrawal::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+pub async fn fetch_config(context: &mut ProgramTestContext) -> ProtocolConfig {
+    let account = context
+        .banks_client
+        .get_account(config_pda())
+        .await
+        .unwrap()
+        .unwrap();
+    ProtocolConfig::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+pub async fn fetch_endpoint(context: &mut ProgramTestContext) -> EndpointState {
+    let account = context
+        .banks_client
+        .get_account(endpoint_pda())
+        .await
+        .unwrap()
+        .unwrap();
+    EndpointState::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+/// An inbound packet from `sender` on `src_eid` to `vault`.
+#[derive(Clone)]
+pub struct InboundPacket {
+    pub vault: Pubkey,
+    pub src_eid: u32,
+    pub sender: [u8; 32],
+    pub nonce: u64,
+    pub message: Vec<u8>,
+}
+
+impl InboundPacket {
+    /// Gives each packet a fresh nonce so repeated deliveries stay distinct.
+    pub fn new(vault: &Pubkey, src_eid: u32, sender: [u8; 32], message: Vec<u8>) -> Self {
+        static NONCE: AtomicU64 = AtomicU64::new(1);
+        InboundPacket {
+            vault: *vault,
+            src_eid,
+            sender,
+            nonce: NONCE.fetch_add(1, Ordering::Relaxed),
+            message,
+        }
+    }
+
+    fn guid(&self) -> [u8; 32] {
+        hashv(&[
+            &self.nonce.to_le_bytes(),
+            &self.src_eid.to_le_bytes(),
+            &self.sender,
+            self.vault.as_ref(),
+        ])
+        .to_bytes()
+    }
+}
+
+/// The mock endpoint's stand-in for DVN verification of `packet`.
+pub fn verify_ix(verifier: &Pubkey, packet: &InboundPacket) -> Instruction {
+    Instruction {
+        program_id: LAYERZERO_ENDPOINT,
+        accounts: mock_endpoint::accounts::Verify {
+            verifier: *verifier,
+            endpoint: endpoint_pda(),
+        }
+        .to_account_metas(None),
+        data: mock_endpoint::instruction::Verify {
+            params: mock_endpoint::VerifyParams {
+                receiver: packet.vault,
+                src_eid: packet.src_eid,
+                sender: packet.sender,
+                nonce: packet.nonce,
+                guid: packet.guid(),
+                message: packet.message.clone(),
+            },
+        }
+        .data(),
+    }
+}
+
+/// `lz_receive` as the executor submits it. `recovery` is the account an
+/// Initiate message opens; `payer` funds it.
+pub fn lz_receive_ix(
+    packet: &InboundPacket,
+    payer: &Pubkey,
+    recovery: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::LzReceive {
+            vault: packet.vault,
+            peer: peer_pda(&packet.vault, packet.src_eid),
+            endpoint: endpoint_pda(),
+            endpoint_program: LAYERZERO_ENDPOINT,
+            config: config_pda(),
+            payer: *payer,
+            recovery,
+            system_program: system_program::ID,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::LzReceive {
+            params: reclaimx::LzReceiveParams {
+                src_eid: packet.src_eid,
+                sender: packet.sender,
+                nonce: packet.nonce,
+                guid: packet.guid(),
+                message: packet.message.clone(),
+                extra_data: vec![],
+            },
+        }
+        .data(),
+    }
+}
+
+/// Verifies a packet and has `payer` execute it, as a DVN and the executor would.
+pub fn deliver_ixs(
+    vault: &Pubkey,
+    payer: &Pubkey,
+    recovery: Option<Pubkey>,
+    src_eid: u32,
+    sender: [u8; 32],
+    message: Vec<u8>,
+) -> Vec<Instruction> {
+    let packet = InboundPacket::new(vault, src_eid, sender, message);
+    vec![
+        verify_ix(payer, &packet),
+        lz_receive_ix(&packet, payer, recovery),
+    ]
+}
+
+pub fn set_peer_ix(
+    vault: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    eid: u32,
+    peer: [u8; 32],
+) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::SetPeer {
+            vault: *vault,
+            config: config_pda(),
+            owner: *owner,
+            peer_info: peer_pda(vault, eid),
+            payer: *payer,
+            system_program: system_program::ID,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::SetPeer { dst_eid: eid, peer }.data(),
+    }
+}
+
+/// Has the owner trust `peer` as the vault's sender on `eid`.
+pub async fn set_peer(
+    context: &mut ProgramTestContext,
+    vault: &TestVault,
+    eid: u32,
+    peer: [u8; 32],
+) {
+    let payer = context.payer.pubkey();
+    let ix = set_peer_ix(&vault.vault, &vault.owner.pubkey(), &payer, eid, peer);
+    process(context, &[ix], &[&vault.owner]).await.unwrap();
+}
+
+pub async fn now(context: &mut ProgramTestContext) -> i64 {
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp
+}
+
+pub async fn warp_forward(context: &mut ProgramTestContext, seconds: i64) {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+pub async fn initialize_vault(
+    context: &mut ProgramTestContext,
+    guardian_count: usize,
+    threshold: u8,
+) -> TestVault {
+    initialize_vault_holding(context, guardian_count, threshold, &[])
+        .await
+        .0
+}
+
+/// Like `initialize_vault`, but the vault tracks one asset per entry of
+/// `amounts`: a fresh mint with that many tokens in a vault-owned account.
+pub async fn initialize_vault_holding(
+    context: &mut ProgramTestContext,
+    guardian_count: usize,
+    threshold: u8,
+    amounts: &[u64],
+) -> (TestVault, Vec<Asset>) {
+    let owner = Keypair::new();
+    let guardians: Vec<Keypair> = (0..guardian_count).map(|_| Keypair::new()).collect();
+    let backup_wallet = Keypair::new().pubkey();
+    let vault = vault_pda(&owner.pubkey(), 0);
+    let endpoint = endpoint_pda();
+    fund(context, &owner.pubkey(), 1_000_000_000).await;
+
+    let mut assets = vec![];
+    for &amount in amounts {
+        let mint_authority = context.payer.pubkey();
+        let mint = create_mint(context, &mint_authority).await;
+        let token_account = create_token_account(context, &mint, &vault).await;
+        mint_to(context, &mint, &token_account, amount).await;
+        assets.push(Asset {
+            asset_type: AssetType::Token,
+            amount,
+            token_account,
+            mint,
+        });
+    }
+
+    let guardian_keys: Vec<Pubkey> = guardians.iter().map(|g| g.pubkey()).collect();
+    let ix = initialize_vault_with_assets_ix(
+        &owner.pubkey(),
+        &context.payer.pubkey(),
+        guardian_keys,
+        threshold,
+        backup_wallet,
+        &assets,
+    );
+    process(context, &[ix], &[&owner]).await.unwrap();
+
+    let vault = TestVault {
+        owner,
+        guardians,
+        backup_wallet,
+        vault,
+        endpoint,
+    };
+    (vault, assets)
+}
+
+pub fn initialize_vault_ix(
+    owner: &Pubkey,
+    payer: &Pubkey,
+    vault_seed: u64,
+    guardians: Vec<Pubkey>,
+    threshold: u8,
+    timelock: u64,
+    inactivity_period: u64,
+    backup_wallet: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::InitializeVault {
+            vault: vault_pda(owner, vault_seed),
+            owner: *owner,
+            payer: *payer,
+            config: config_pda(),
+            endpoint: endpoint_pda(),
+            endpoint_program: LAYERZERO_ENDPOINT,
+            system_program: system_program::ID,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::InitializeVault {
+            vault_seed,
+            guardians,
+            threshold,
+            timelock,
+            inactivity_period,
+            backup_wallet,
+            stake_amount: STAKE_AMOUNT,
+            assets: Vec::<Asset>::new(),
+        }
+        .data(),
+    }
+}
+
+/// `initialize_vault` with `assets`, passing each asset's token account.
+pub fn initialize_vault_with_assets_ix(
+    owner: &Pubkey,
+    payer: &Pubkey,
+    guardians: Vec<Pubkey>,
+    threshold: u8,
+    backup_wallet: Pubkey,
+    assets: &[Asset],
+) -> Instruction {
+    let mut ix = initialize_vault_ix(
+        owner,
+        payer,
+        0,
+        guardians.clone(),
+        threshold,
+        TIMELOCK,
+        INACTIVITY_PERIOD,
+        backup_wallet,
+    );
+    ix.data = reclaimx::instruction::InitializeVault {
+        vault_seed: 0,
+        guardians,
+        threshold,
+        timelock: TIMELOCK,
+        inactivity_period: INACTIVITY_PERIOD,
+        backup_wallet,
+        stake_amount: STAKE_AMOUNT,
+        assets: assets.to_vec(),
+    }
+    .data();
+    ix.accounts.extend(
+        assets
+            .iter()
+            .map(|asset| AccountMeta::new_readonly(asset.token_account, false)),
+    );
+    ix
+}
+
+/// `recovery_id` is the id the new recovery will get, i.e. the vault's current one plus one.
+pub fn initiate_recovery_ix(
+    vault: &TestVault,
+    recovery_id: u64,
+    initiator: &Pubkey,
+    payer: &Pubkey,
+    new_owner: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::InitiateRecovery {
+            vault: vault.vault,
+            initiator: *initiator,
+            payer: *payer,
+            recovery: recovery_pda(&vault.vault, recovery_id),
+            config: config_pda(),
+            endpoint: vault.endpoint,
+            endpoint_program: LAYERZERO_ENDPOINT,
+            peer: Pubkey::new_unique(),
+            system_program: system_program::ID,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::InitiateRecovery {
+            new_owner,
+            dst_eid: reclaimx::ETHEREUM_EID,
+            options: vec![],
+        }
+        .data(),
+    }
+}
+
+/// Remaining accounts for a completed recovery: (vault token account, new
+/// owner token account) per vault asset.
+fn asset_account_metas(asset_accounts: &[(Pubkey, Pubkey)]) -> Vec<AccountMeta> {
+    asset_accounts
+        .iter()
+        .flat_map(|(from, to)| [AccountMeta::new(*from, false), AccountMeta::new(*to, false)])
+        .collect()
+}
+
+pub fn approve_recovery_ix(
+    vault: &Pubkey,
+    recovery_id: u64,
+    guardian: &Pubkey,
+    asset_accounts: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let mut accounts = reclaimx::accounts::ApproveRecovery {
+        vault: *vault,
+        config: config_pda(),
+        recovery: recovery_pda(vault, recovery_id),
+        guardian: *guardian,
+        token_program: spl_token::ID,
+        event_authority: event_authority(),
+        program: reclaimx::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(asset_account_metas(asset_accounts));
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts,
+        data: reclaimx::instruction::ApproveRecovery {}.data(),
+    }
+}
+
+pub fn execute_recovery_ix(
+    vault: &Pubkey,
+    recovery_id: u64,
+    cranker: &Pubkey,
+    crank_reserve: Option<Pubkey>,
+    asset_accounts: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let mut accounts = reclaimx::accounts::ExecuteRecovery {
+        vault: *vault,
+        config: config_pda(),
+        recovery: recovery_pda(vault, recovery_id),
+        cranker: *cranker,
+        crank_reserve,
+        token_program: spl_token::ID,
+        event_authority: event_authority(),
+        program: reclaimx::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(asset_account_metas(asset_accounts));
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts,
+        data: reclaimx::instruction::ExecuteRecovery {}.data(),
+    }
+}
+
+pub fn add_evm_guardian_ix(vault: &Pubkey, owner: &Pubkey, evm_guardian: [u8; 20]) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::AddEvmGuardian {
+            vault: *vault,
+            config: config_pda(),
+            owner: *owner,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::AddEvmGuardian { evm_guardian }.data(),
+    }
+}
+
+pub fn add_heartbeat_key_ix(vault: &Pubkey, owner: &Pubkey, heartbeat_key: Pubkey) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::AddHeartbeatKey {
+            vault: *vault,
+            config: config_pda(),
+            owner: *owner,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::AddHeartbeatKey { heartbeat_key }.data(),
+    }
+}
+
+pub fn remove_heartbeat_key_ix(
+    vault: &Pubkey,
+    owner: &Pubkey,
+    heartbeat_key: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::RemoveHeartbeatKey {
+            vault: *vault,
+            config: config_pda(),
+            owner: *owner,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::RemoveHeartbeatKey { heartbeat_key }.data(),
+    }
+}
+
+pub fn cancel_inactivity_recovery_ix(
+    vault: &Pubkey,
+    recovery_id: u64,
+    signer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::CancelRecovery {
+            vault: *vault,
+            config: config_pda(),
+            recovery: recovery_pda(vault, recovery_id),
+            signer: *signer,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::CancelInactivityRecovery {}.data(),
+    }
+}
+
+pub fn cancel_recovery_ix(vault: &Pubkey, recovery_id: u64, signer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::CancelRecovery {
+            vault: *vault,
+            config: config_pda(),
+            recovery: recovery_pda(vault, recovery_id),
+            signer: *signer,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::CancelRecovery {}.data(),
+    }
+}
+
+pub fn freeze_vault_ix(vault: &Pubkey, signer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::FreezeVault {
+            vault: *vault,
+            config: config_pda(),
+            signer: *signer,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::FreezeVault {}.data(),
+    }
+}
+
+pub fn unfreeze_vault_ix(vault: &Pubkey, signer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::FreezeVault {
+            vault: *vault,
+            config: config_pda(),
+            signer: *signer,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::UnfreezeVault {}.data(),
+    }
+}
+
+pub fn update_timing_ix(
+    vault: &Pubkey,
+    owner: &Pubkey,
+    timelock: u64,
+    inactivity_period: u64,
+) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::UpdateTiming {
+            vault: *vault,
+            config: config_pda(),
+            owner: *owner,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::UpdateTiming {
+            timelock,
+            inactivity_period,
+        }
+        .data(),
+    }
+}
+
+pub fn transfer_ownership_ix(vault: &Pubkey, owner: &Pubkey, new_owner: Pubkey) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::TransferOwnership {
+            vault: *vault,
+            config: config_pda(),
+            owner: *owner,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::TransferOwnership { new_owner }.data(),
+    }
+}
+
+pub fn accept_ownership_ix(vault: &Pubkey, new_owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::AcceptOwnership {
+            vault: *vault,
+            config: config_pda(),
+            new_owner: *new_owner,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::AcceptOwnership {}.data(),
+    }
+}
+
+pub fn cancel_ownership_transfer_ix(vault: &Pubkey, owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::TransferOwnership {
+            vault: *vault,
+            config: config_pda(),
+            owner: *owner,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::CancelOwnershipTransfer {}.data(),
+    }
+}
+
+pub fn apply_timing_ix(vault: &Pubkey, cranker: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::ApplyTiming {
+            vault: *vault,
+            config: config_pda(),
+            cranker: *cranker,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::ApplyTiming {}.data(),
+    }
+}
+
+pub fn cancel_timing_update_ix(vault: &Pubkey, owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::UpdateTiming {
+            vault: *vault,
+            config: config_pda(),
+            owner: *owner,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::CancelTimingUpdate {}.data(),
+    }
+}
+
+pub async fn create_mint(context: &mut ProgramTestContext, authority: &Pubkey) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &context.payer.pubkey(),
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint(&spl_token::ID, &mint.pubkey(), authority, None, 0)
+            .unwrap(),
+    ];
+    process(context, &instructions, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+pub async fn create_token_account(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Pubkey {
+    let account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &context.payer.pubkey(),
+            &account.pubkey(),
+            rent.minimum_balance(spl_token::state::Account::LEN),
+            spl_token::state::Account::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_account(&spl_token::ID, &account.pubkey(), mint, owner)
+            .unwrap(),
+    ];
+    process(context, &instructions, &[&account]).await.unwrap();
+    account.pubkey()
+}
+
+pub async fn mint_to(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    account: &Pubkey,
+    amount: u64,
+) {
+    let authority = context.payer.pubkey();
+    let ix =
+        spl_token::instruction::mint_to(&spl_token::ID, mint, account, &authority, &[], amount)
+            .unwrap();
+    process(context, &[ix], &[]).await.unwrap();
+}
+
+pub async fn token_balance(context: &mut ProgramTestContext, account: &Pubkey) -> u64 {
+    let account = context
+        .banks_client
+        .get_account(*account)
+        .await
+        .unwrap()
+        .unwrap();
+    spl_token::state::Account::unpack(&account.data)
+        .unwrap()
+        .amount
+}
+
+/// Overwrites a token account's balance, as if it had drained outside the vault.
+pub async fn set_token_balance(context: &mut ProgramTestContext, account: &Pubkey, amount: u64) {
+    let mut shared: AccountSharedData = context
+        .banks_client
+        .get_account(*account)
+        .await
+        .unwrap()
+        .unwrap()
+        .into();
+    let mut state = spl_token::state::Account::unpack(shared.data()).unwrap();
+    state.amount = amount;
+    spl_token::state::Account::pack(state, shared.data_as_mut_slice()).unwrap();
+    context.set_account(account, &shared);
+}
+
+pub fn withdraw_asset_ix(
+    vault: &Pubkey,
+    owner: &Pubkey,
+    guardian: Option<Pubkey>,
+    vault_token_account: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::WithdrawAsset {
+            vault: *vault,
+            config: config_pda(),
+            owner: *owner,
+            guardian,
+            vault_token_account: *vault_token_account,
+            destination: *destination,
+            token_program: spl_token::ID,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::WithdrawAsset { amount }.data(),
+    }
+}
+
+pub fn set_spending_limit_ix(
+    vault: &Pubkey,
+    owner: &Pubkey,
+    guardian: Option<Pubkey>,
+    mint: Pubkey,
+    max_amount: u64,
+    period: u64,
+    queue_above: Option<u64>,
+) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::SetSpendingLimit {
+            vault: *vault,
+            config: config_pda(),
+            owner: *owner,
+            guardian,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::SetSpendingLimit {
+            mint,
+            max_amount,
+            period,
+            queue_above,
+        }
+        .data(),
+    }
+}
+
+/// `withdrawal_id` is the id the queued withdrawal will get.
+pub fn queue_withdrawal_ix(
+    vault: &Pubkey,
+    withdrawal_id: u64,
+    owner: &Pubkey,
+    vault_token_account: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::QueueWithdrawal {
+            vault: *vault,
+            config: config_pda(),
+            owner: *owner,
+            guardian: None,
+            withdrawal: withdrawal_pda(vault, withdrawal_id),
+            vault_token_account: *vault_token_account,
+            destination: *destination,
+            system_program: system_program::ID,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::QueueWithdrawal { amount }.data(),
+    }
+}
+
+pub fn execute_withdrawal_ix(
+    vault: &Pubkey,
+    withdrawal_id: u64,
+    cranker: &Pubkey,
+    vault_token_account: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::ExecuteWithdrawal {
+            vault: *vault,
+            config: config_pda(),
+            withdrawal: withdrawal_pda(vault, withdrawal_id),
+            cranker: *cranker,
+            vault_token_account: *vault_token_account,
+            destination: *destination,
+            token_program: spl_token::ID,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::ExecuteWithdrawal {}.data(),
+    }
+}
+
+pub fn veto_withdrawal_ix(vault: &Pubkey, withdrawal_id: u64, guardian: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::ResolveWithdrawal {
+            vault: *vault,
+            config: config_pda(),
+            withdrawal: withdrawal_pda(vault, withdrawal_id),
+            signer: *guardian,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::VetoWithdrawal {}.data(),
+    }
+}
+
+pub fn initialize_config_ix(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    admin: Pubkey,
+    params: ConfigParams,
+) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::InitializeConfig {
+            config: config_pda(),
+            payer: *payer,
+            authority: *authority,
+            program_data: program_data_pda(),
+            system_program: system_program::ID,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::InitializeConfig { admin, params }.data(),
+    }
+}
+
+fn manage_config_ix(signer: &Pubkey, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::ManageConfig {
+            config: config_pda(),
+            signer: *signer,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn update_config_ix(signer: &Pubkey, params: ConfigParams) -> Instruction {
+    manage_config_ix(signer, reclaimx::instruction::UpdateConfig { params })
+}
+
+pub fn set_paused_ix(signer: &Pubkey, scope: PauseScope, paused: bool) -> Instruction {
+    manage_config_ix(signer, reclaimx::instruction::SetPaused { scope, paused })
+}
+
+pub fn propose_admin_ix(signer: &Pubkey, new_admin: Option<Pubkey>) -> Instruction {
+    manage_config_ix(signer, reclaimx::instruction::ProposeAdmin { new_admin })
+}
+
+pub fn accept_admin_ix(signer: &Pubkey) -> Instruction {
+    manage_config_ix(signer, reclaimx::instruction::AcceptAdmin {})
+}
@@ -0,0 +1,230 @@
+mod common;
+
+use anchor_lang::AnchorSerialize;
+use common::*;
+use reclaimx::{config::ConfigParams, PauseScope, RecoveryAction, RecoveryMessage, RecoveryStatus};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+fn initiate_message(vault: &Pubkey) -> Vec<u8> {
+    RecoveryMessage {
+        vault_id: *vault,
+        new_owner: Pubkey::new_unique(),
+        recovery_id: 0,
+        action: RecoveryAction::Initiate,
+    }
+    .try_to_vec()
+    .unwrap()
+}
+
+fn deliver_from(vault: &TestVault, payer: &Pubkey, src_eid: u32) -> Vec<Instruction> {
+    deliver_ixs(
+        &vault.vault,
+        payer,
+        Some(recovery_pda(&vault.vault, 1)),
+        src_eid,
+        [7u8; 32],
+        initiate_message(&vault.vault),
+    )
+}
+
+#[tokio::test]
+async fn only_upgrade_authority_initializes_config() {
+    let mut context = program_test_without_config().await;
+    let payer = context.payer.pubkey();
+    let stranger = Keypair::new();
+
+    // Paying for the account is not enough to pick the admin
+    let ix = initialize_config_ix(
+        &payer,
+        &stranger.pubkey(),
+        stranger.pubkey(),
+        config_params(),
+    );
+    assert!(process(&mut context, &[ix], &[&stranger]).await.is_err());
+
+    let admin = Keypair::new().pubkey();
+    let ix = initialize_config_ix(&payer, &payer, admin, config_params());
+    process(&mut context, &[ix], &[]).await.unwrap();
+    assert_eq!(fetch_config(&mut context).await.admin, admin);
+}
+
+#[tokio::test]
+async fn admin_updates_config_and_hands_over_role() {
+    let mut context = program_test_context().await;
+    let admin = context.payer.pubkey();
+    let stranger = Keypair::new();
+    let params = ConfigParams {
+        allowed_eids: vec![reclaimx::ETHEREUM_EID, reclaimx::SEPOLIA_EID],
+        ..config_params()
+    };
+
+    let ix = update_config_ix(&stranger.pubkey(), params.clone());
+    assert!(process(&mut context, &[ix], &[&stranger]).await.is_err());
+    let ix = update_config_ix(&admin, params.clone());
+    process(&mut context, &[ix], &[]).await.unwrap();
+    assert_eq!(
+        fetch_config(&mut context).await.allowed_eids,
+        params.allowed_eids
+    );
+
+    // Timelock bounds must stay ordered
+    let ix = update_config_ix(
+        &admin,
+        ConfigParams {
+            min_timelock: 2,
+            max_timelock: 1,
+            ..config_params()
+        },
+    );
+    assert!(process(&mut context, &[ix], &[]).await.is_err());
+
+    let new_admin = Keypair::new();
+    let ix = propose_admin_ix(&admin, Some(new_admin.pubkey()));
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let ix = accept_admin_ix(&stranger.pubkey());
+    assert!(process(&mut context, &[ix], &[&stranger]).await.is_err());
+    let ix = accept_admin_ix(&new_admin.pubkey());
+    process(&mut context, &[ix], &[&new_admin]).await.unwrap();
+
+    let config = fetch_config(&mut context).await;
+    assert_eq!(config.admin, new_admin.pubkey());
+    assert_eq!(config.pending_admin, None);
+    let ix = set_paused_ix(&admin, PauseScope::All, true);
+    assert!(process(&mut context, &[ix], &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn allowed_eids_come_from_config() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    for eid in [reclaimx::ETHEREUM_EID, reclaimx::SEPOLIA_EID] {
+        set_peer(&mut context, &vault, eid, [7u8; 32]).await;
+    }
+    let admin = context.payer.pubkey();
+    let ix = update_config_ix(
+        &admin,
+        ConfigParams {
+            allowed_eids: vec![reclaimx::SEPOLIA_EID],
+            ..config_params()
+        },
+    );
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    // Ethereum is no longer a trusted source or destination
+    let ixs = deliver_from(&vault, &admin, reclaimx::ETHEREUM_EID);
+    assert!(process(&mut context, &ixs, &[]).await.is_err());
+    let guardian = &vault.guardians[0];
+    let ix = initiate_recovery_ix(&vault, 1, &guardian.pubkey(), &admin, Pubkey::new_unique());
+    assert!(process(&mut context, &[ix], &[guardian]).await.is_err());
+
+    let ixs = deliver_from(&vault, &admin, reclaimx::SEPOLIA_EID);
+    process(&mut context, &ixs, &[]).await.unwrap();
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.recovery_id, 1);
+}
+
+#[tokio::test]
+async fn pause_scopes_block_their_instructions() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    set_peer(&mut context, &vault, reclaimx::ETHEREUM_EID, [7u8; 32]).await;
+    let admin = context.payer.pubkey();
+    let council = Keypair::new();
+    let ix = update_config_ix(
+        &admin,
+        ConfigParams {
+            security_council: Some(council.pubkey()),
+            ..config_params()
+        },
+    );
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let stranger = Keypair::new();
+    let ix = set_paused_ix(&stranger.pubkey(), PauseScope::All, true);
+    assert!(process(&mut context, &[ix], &[&stranger]).await.is_err());
+
+    // An inbound pause leaves outbound messages flowing
+    let ix = set_paused_ix(&council.pubkey(), PauseScope::InboundLayerZero, true);
+    process(&mut context, &[ix], &[&council]).await.unwrap();
+    let ixs = deliver_from(&vault, &admin, reclaimx::ETHEREUM_EID);
+    assert!(process(&mut context, &ixs, &[]).await.is_err());
+    let guardian = &vault.guardians[0];
+    let ix = initiate_recovery_ix(&vault, 1, &guardian.pubkey(), &admin, Pubkey::new_unique());
+    process(&mut context, &[ix], &[guardian]).await.unwrap();
+
+    // Pausing everything halts ordinary vault instructions too
+    let freeze = freeze_vault_ix(&vault.vault, &vault.owner.pubkey());
+    let ix = set_paused_ix(&council.pubkey(), PauseScope::All, true);
+    process(&mut context, &[ix], &[&council]).await.unwrap();
+    assert!(process(&mut context, &[freeze.clone()], &[&vault.owner])
+        .await
+        .is_err());
+
+    let ix = set_paused_ix(&admin, PauseScope::All, false);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    process(&mut context, &[freeze], &[&vault.owner])
+        .await
+        .unwrap();
+    assert!(fetch_config(&mut context)
+        .await
+        .is_paused(PauseScope::InboundLayerZero));
+}
+
+#[tokio::test]
+async fn execution_pause_records_approvals_without_executing() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let admin = context.payer.pubkey();
+    let new_owner = Pubkey::new_unique();
+
+    let guardian = &vault.guardians[0];
+    let ix = initiate_recovery_ix(&vault, 1, &guardian.pubkey(), &admin, new_owner);
+    process(&mut context, &[ix], &[guardian]).await.unwrap();
+    warp_forward(&mut context, TIMELOCK as i64).await;
+
+    let ix = set_paused_ix(&admin, PauseScope::RecoveryExecution, true);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    for guardian in &vault.guardians[..2] {
+        let ix = approve_recovery_ix(&vault.vault, 1, &guardian.pubkey(), &[]);
+        process(&mut context, &[ix], &[guardian]).await.unwrap();
+    }
+    let recovery = fetch_recovery(&mut context, &vault.vault, 1).await;
+    assert_eq!(recovery.approvals.len(), 2);
+    assert_eq!(recovery.status, RecoveryStatus::Pending);
+    assert_eq!(
+        fetch_vault(&mut context, &vault.vault).await.owner,
+        vault.owner.pubkey()
+    );
+    let ix = execute_recovery_ix(&vault.vault, 1, &admin, None, &[]);
+    assert!(process(&mut context, &[ix.clone()], &[]).await.is_err());
+
+    let unpause = set_paused_ix(&admin, PauseScope::RecoveryExecution, false);
+    process(&mut context, &[unpause, ix], &[]).await.unwrap();
+    assert_eq!(
+        fetch_vault(&mut context, &vault.vault).await.owner,
+        new_owner
+    );
+}
+
+#[tokio::test]
+async fn messaging_fee_is_capped_by_config() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let admin = context.payer.pubkey();
+    let ix = update_config_ix(
+        &admin,
+        ConfigParams {
+            max_native_fee: MOCK_BASE_FEE,
+            ..config_params()
+        },
+    );
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let guardian = &vault.guardians[0];
+    let ix = initiate_recovery_ix(&vault, 1, &guardian.pubkey(), &admin, Pubkey::new_unique());
+    assert!(process(&mut context, &[ix], &[guardian]).await.is_err());
+}
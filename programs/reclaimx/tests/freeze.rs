@@ -0,0 +1,97 @@
+mod common;
+
+use common::*;
+use reclaimx::{freeze::MAX_FREEZE_DURATION, RecoveryStatus};
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn owner_freezes_and_unfreezes() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let owner = vault.owner.pubkey();
+
+    let ix = freeze_vault_ix(&vault.vault, &owner);
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(
+        state.frozen_until,
+        now(&mut context).await + MAX_FREEZE_DURATION
+    );
+    assert!(!state.frozen_by_guardians);
+
+    // Guardian changes are blocked while frozen
+    let ix = add_evm_guardian_ix(&vault.vault, &owner, [7u8; 20]);
+    assert!(process(&mut context, &[ix], &[&vault.owner]).await.is_err());
+
+    let ix = unfreeze_vault_ix(&vault.vault, &owner);
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+    let ix = add_evm_guardian_ix(&vault.vault, &owner, [7u8; 20]);
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+
+    // The owner cannot freeze again straight away
+    let ix = freeze_vault_ix(&vault.vault, &owner);
+    assert!(process(&mut context, &[ix], &[&vault.owner]).await.is_err());
+}
+
+#[tokio::test]
+async fn guardian_freeze_needs_quorum_and_lapses() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+
+    let guardian = &vault.guardians[0];
+    let ix = freeze_vault_ix(&vault.vault, &guardian.pubkey());
+    process(&mut context, &[ix], &[guardian]).await.unwrap();
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.frozen_until, 0);
+    assert_eq!(state.freeze_votes.len(), 1);
+
+    let guardian = &vault.guardians[1];
+    let ix = freeze_vault_ix(&vault.vault, &guardian.pubkey());
+    process(&mut context, &[ix], &[guardian]).await.unwrap();
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert!(state.frozen_by_guardians);
+    assert!(state.freeze_votes.is_empty());
+
+    // Only guardians may lift a freeze they imposed
+    let ix = unfreeze_vault_ix(&vault.vault, &vault.owner.pubkey());
+    assert!(process(&mut context, &[ix], &[&vault.owner]).await.is_err());
+
+    warp_forward(&mut context, MAX_FREEZE_DURATION).await;
+    let ix = add_evm_guardian_ix(&vault.vault, &vault.owner.pubkey(), [7u8; 20]);
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+}
+
+#[tokio::test]
+async fn frozen_vault_blocks_recovery_execution() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let new_owner = Keypair::new().pubkey();
+    let cranker = Keypair::new();
+    fund(&mut context, &cranker.pubkey(), 1_000_000_000).await;
+
+    let initiator = vault.guardians[0].pubkey();
+    let ix = initiate_recovery_ix(&vault, 1, &initiator, &context.payer.pubkey(), new_owner);
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+    for guardian in &vault.guardians[..2] {
+        let ix = approve_recovery_ix(&vault.vault, 1, &guardian.pubkey(), &[]);
+        process(&mut context, &[ix], &[guardian]).await.unwrap();
+    }
+
+    let ix = freeze_vault_ix(&vault.vault, &vault.owner.pubkey());
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+    warp_forward(&mut context, TIMELOCK as i64).await;
+    let ix = execute_recovery_ix(&vault.vault, 1, &cranker.pubkey(), None, &[]);
+    assert!(process(&mut context, &[ix], &[&cranker]).await.is_err());
+
+    // A guardian quorum can lift the owner's freeze
+    for guardian in &vault.guardians[..2] {
+        let ix = unfreeze_vault_ix(&vault.vault, &guardian.pubkey());
+        process(&mut context, &[ix], &[guardian]).await.unwrap();
+    }
+    let ix = execute_recovery_ix(&vault.vault, 1, &cranker.pubkey(), None, &[]);
+    process(&mut context, &[ix], &[&cranker]).await.unwrap();
+    let recovery = fetch_recovery(&mut context, &vault.vault, 1).await;
+    assert_eq!(recovery.status, RecoveryStatus::Executed);
+}
@@ -0,0 +1,244 @@
+mod common;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use reclaimx::RecoveryStatus;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+};
+
+const BOUNTY: u64 = 10_000;
+
+fn check_inactivity_ix(
+    vault: &Pubkey,
+    recovery_id: u64,
+    cranker: &Pubkey,
+    crank_reserve: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::CheckInactivity {
+            vault: *vault,
+            config: config_pda(),
+            cranker: *cranker,
+            recovery: recovery_pda(vault, recovery_id),
+            crank_reserve,
+            system_program: system_program::ID,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::CheckInactivity {}.data(),
+    }
+}
+
+fn initialize_crank_reserve_ix(vault: &Pubkey, owner: &Pubkey, bounty: u64) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::InitializeCrankReserve {
+            vault: *vault,
+            config: config_pda(),
+            owner: *owner,
+            crank_reserve: crank_reserve_pda(vault),
+            system_program: system_program::ID,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::InitializeCrankReserve { bounty }.data(),
+    }
+}
+
+fn proof_of_life_ix(vault: &Pubkey, signer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::ProofOfLife {
+            vault: *vault,
+            config: config_pda(),
+            signer: *signer,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::ProofOfLife {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn active_vault_is_not_recovered() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+
+    warp_forward(&mut context, INACTIVITY_PERIOD as i64 - 1).await;
+    let cranker = context.payer.pubkey();
+    let ix = check_inactivity_ix(&vault.vault, 1, &cranker, None);
+    assert!(process(&mut context, &[ix], &[]).await.is_err());
+
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.active_recovery, None);
+    assert_eq!(state.recovery_id, 0);
+}
+
+#[tokio::test]
+async fn inactive_vault_starts_recovery_to_backup_wallet() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+
+    warp_forward(&mut context, INACTIVITY_PERIOD as i64).await;
+    let cranker = context.payer.pubkey();
+    let ix = check_inactivity_ix(&vault.vault, 1, &cranker, None);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.active_recovery, Some(recovery_pda(&vault.vault, 1)));
+    let recovery = fetch_recovery(&mut context, &vault.vault, 1).await;
+    assert_eq!(recovery.new_owner, vault.backup_wallet);
+    assert_eq!(recovery.proposer, cranker);
+    assert!(recovery.inactivity_triggered);
+    assert_eq!(recovery.status, RecoveryStatus::Pending);
+}
+
+#[tokio::test]
+async fn proof_of_life_resets_inactivity_clock() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+
+    warp_forward(&mut context, INACTIVITY_PERIOD as i64 - 10).await;
+    let ix = proof_of_life_ix(&vault.vault, &vault.owner.pubkey());
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+    let checked_in_at = now(&mut context).await;
+
+    warp_forward(&mut context, 20).await;
+    let cranker = context.payer.pubkey();
+    let ix = check_inactivity_ix(&vault.vault, 1, &cranker, None);
+    assert!(process(&mut context, &[ix], &[]).await.is_err());
+
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.last_active_timestamp, checked_in_at);
+    assert_eq!(state.active_recovery, None);
+}
+
+#[tokio::test]
+async fn unregistered_key_cannot_prove_life() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let stranger = Keypair::new();
+
+    let ix = proof_of_life_ix(&vault.vault, &stranger.pubkey());
+    assert!(process(&mut context, &[ix], &[&stranger]).await.is_err());
+}
+
+#[tokio::test]
+async fn owner_adds_and_removes_heartbeat_keys() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let owner = vault.owner.pubkey();
+    let heartbeat = Keypair::new();
+    let stranger = Keypair::new();
+
+    let ix = add_heartbeat_key_ix(&vault.vault, &stranger.pubkey(), heartbeat.pubkey());
+    assert!(process(&mut context, &[ix], &[&stranger]).await.is_err());
+    let add = add_heartbeat_key_ix(&vault.vault, &owner, heartbeat.pubkey());
+    process(&mut context, &[add.clone()], &[&vault.owner])
+        .await
+        .unwrap();
+    assert!(process(&mut context, &[add], &[&vault.owner])
+        .await
+        .is_err());
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.heartbeat_keys, vec![heartbeat.pubkey()]);
+
+    // A registered key proves life on the owner's behalf
+    warp_forward(&mut context, 10).await;
+    let ix = proof_of_life_ix(&vault.vault, &heartbeat.pubkey());
+    process(&mut context, &[ix.clone()], &[&heartbeat])
+        .await
+        .unwrap();
+    assert_eq!(
+        fetch_vault(&mut context, &vault.vault)
+            .await
+            .last_active_timestamp,
+        now(&mut context).await
+    );
+
+    let remove = remove_heartbeat_key_ix(&vault.vault, &owner, heartbeat.pubkey());
+    process(&mut context, &[remove.clone()], &[&vault.owner])
+        .await
+        .unwrap();
+    assert!(process(&mut context, &[remove], &[&vault.owner])
+        .await
+        .is_err());
+    assert!(fetch_vault(&mut context, &vault.vault)
+        .await
+        .heartbeat_keys
+        .is_empty());
+    assert!(process(&mut context, &[ix], &[&heartbeat]).await.is_err());
+}
+
+#[tokio::test]
+async fn heartbeat_key_cancels_inactivity_recovery() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let heartbeat = Keypair::new();
+    let ix = add_heartbeat_key_ix(&vault.vault, &vault.owner.pubkey(), heartbeat.pubkey());
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+
+    warp_forward(&mut context, INACTIVITY_PERIOD as i64).await;
+    let cranker = context.payer.pubkey();
+    let ix = check_inactivity_ix(&vault.vault, 1, &cranker, None);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let stranger = Keypair::new();
+    let ix = cancel_inactivity_recovery_ix(&vault.vault, 1, &stranger.pubkey());
+    assert!(process(&mut context, &[ix], &[&stranger]).await.is_err());
+    let ix = cancel_inactivity_recovery_ix(&vault.vault, 1, &heartbeat.pubkey());
+    process(&mut context, &[ix], &[&heartbeat]).await.unwrap();
+
+    let recovery = fetch_recovery(&mut context, &vault.vault, 1).await;
+    assert_eq!(recovery.status, RecoveryStatus::Cancelled);
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.active_recovery, None);
+    assert_eq!(state.last_active_timestamp, now(&mut context).await);
+}
+
+#[tokio::test]
+async fn cranker_is_paid_from_reserve_once() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let reserve = crank_reserve_pda(&vault.vault);
+    let cranker = Keypair::new();
+    fund(&mut context, &cranker.pubkey(), 1_000_000_000).await;
+
+    let ix = initialize_crank_reserve_ix(&vault.vault, &vault.owner.pubkey(), BOUNTY);
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+    fund(&mut context, &reserve, 10 * BOUNTY).await;
+
+    warp_forward(&mut context, INACTIVITY_PERIOD as i64).await;
+    let before = context
+        .banks_client
+        .get_balance(cranker.pubkey())
+        .await
+        .unwrap();
+    let ix = check_inactivity_ix(&vault.vault, 1, &cranker.pubkey(), Some(reserve));
+    process(&mut context, &[ix], &[&cranker]).await.unwrap();
+    // A repeated crank finds the recovery already pending and fails
+    let ix = check_inactivity_ix(&vault.vault, 2, &cranker.pubkey(), Some(reserve));
+    assert!(process(&mut context, &[ix], &[&cranker]).await.is_err());
+
+    let after = context
+        .banks_client
+        .get_balance(cranker.pubkey())
+        .await
+        .unwrap();
+    // Less the rent for the Recovery account the cranker paid for
+    let rent = context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(8 + reclaimx::Recovery::SPACE);
+    assert_eq!(after, before + BOUNTY - rent);
+}
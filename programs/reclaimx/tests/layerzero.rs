@@ -0,0 +1,282 @@
+mod common;
+
+use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+use common::*;
+use reclaimx::{PeerInfo, RecoveryAction, RecoveryMessage, RecoveryStatus};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+#[tokio::test]
+async fn owner_sets_peer() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let peer_info = peer_pda(&vault.vault, reclaimx::ETHEREUM_EID);
+
+    let payer = context.payer.pubkey();
+    let ix = set_peer_ix(
+        &vault.vault,
+        &vault.owner.pubkey(),
+        &payer,
+        reclaimx::ETHEREUM_EID,
+        [1u8; 32],
+    );
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(peer_info)
+        .await
+        .unwrap()
+        .unwrap();
+    let peer = <PeerInfo as anchor_lang::AccountDeserialize>::try_deserialize(
+        &mut account.data.as_slice(),
+    )
+    .unwrap();
+    assert_eq!(peer.vault, vault.vault);
+    assert_eq!(peer.eid, reclaimx::ETHEREUM_EID);
+    assert_eq!(peer.peer, [1u8; 32]);
+}
+
+#[tokio::test]
+async fn initiate_recovery_sends_packet_through_endpoint() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let new_owner = Keypair::new().pubkey();
+
+    let initiator = vault.guardians[0].pubkey();
+    let ix = initiate_recovery_ix(&vault, 1, &initiator, &context.payer.pubkey(), new_owner);
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+
+    let endpoint = fetch_endpoint(&mut context).await;
+    assert_eq!(endpoint.registered_oapps, 1);
+    assert_eq!(endpoint.outbound_nonce, 1);
+    let packet = endpoint.packets.last().unwrap();
+    assert_eq!(packet.sender, vault.vault);
+    assert_eq!(packet.dst_eid, reclaimx::ETHEREUM_EID);
+    assert_eq!(
+        packet.native_fee,
+        MOCK_BASE_FEE + MOCK_FEE_PER_BYTE * packet.message.len() as u64
+    );
+
+    let message = RecoveryMessage::try_from_slice(&packet.message).unwrap();
+    assert_eq!(message.vault_id, vault.vault);
+    assert_eq!(message.new_owner, new_owner);
+    assert_eq!(message.recovery_id, 1);
+    assert!(matches!(message.action, RecoveryAction::Initiate));
+}
+
+#[tokio::test]
+async fn lz_receive_initiates_recovery() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    set_peer(&mut context, &vault, reclaimx::ETHEREUM_EID, [7u8; 32]).await;
+    let new_owner = Keypair::new().pubkey();
+
+    let message = RecoveryMessage {
+        vault_id: vault.vault,
+        new_owner,
+        recovery_id: 0,
+        action: RecoveryAction::Initiate,
+    };
+    let payer = context.payer.pubkey();
+    let ixs = deliver_ixs(
+        &vault.vault,
+        &payer,
+        Some(recovery_pda(&vault.vault, 1)),
+        reclaimx::ETHEREUM_EID,
+        [7u8; 32],
+        message.try_to_vec().unwrap(),
+    );
+    process(&mut context, &ixs, &[]).await.unwrap();
+
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.active_recovery, Some(recovery_pda(&vault.vault, 1)));
+    let recovery = fetch_recovery(&mut context, &vault.vault, 1).await;
+    assert_eq!(recovery.recovery_id, 1);
+    assert_eq!(recovery.new_owner, new_owner);
+    assert_eq!(recovery.proposer, Pubkey::new_from_array([7u8; 32]));
+    assert_eq!(recovery.status, RecoveryStatus::Pending);
+}
+
+#[tokio::test]
+async fn lz_receive_clears_only_verified_packets() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    set_peer(&mut context, &vault, reclaimx::ETHEREUM_EID, [7u8; 32]).await;
+    let payer = context.payer.pubkey();
+    let recovery = Some(recovery_pda(&vault.vault, 1));
+    let initiate = |new_owner| {
+        RecoveryMessage {
+            vault_id: vault.vault,
+            new_owner,
+            recovery_id: 0,
+            action: RecoveryAction::Initiate,
+        }
+        .try_to_vec()
+        .unwrap()
+    };
+    let packet = InboundPacket::new(
+        &vault.vault,
+        reclaimx::ETHEREUM_EID,
+        [7u8; 32],
+        initiate(Pubkey::new_unique()),
+    );
+
+    // Calling lz_receive directly does not deliver an unverified packet
+    let ix = lz_receive_ix(&packet, &payer, recovery);
+    assert!(process(&mut context, &[ix], &[]).await.is_err());
+
+    // Nor a different message under a verified packet's nonce
+    process(&mut context, &[verify_ix(&payer, &packet)], &[])
+        .await
+        .unwrap();
+    let forged = InboundPacket {
+        message: initiate(Pubkey::new_unique()),
+        ..packet.clone()
+    };
+    let ix = lz_receive_ix(&forged, &payer, recovery);
+    assert!(process(&mut context, &[ix], &[]).await.is_err());
+
+    let ix = lz_receive_ix(&packet, &payer, recovery);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let endpoint = fetch_endpoint(&mut context).await;
+    assert!(endpoint.verified.is_empty());
+    assert_eq!(endpoint.inbound_nonce, 1);
+    assert_eq!(fetch_vault(&mut context, &vault.vault).await.recovery_id, 1);
+}
+
+#[tokio::test]
+async fn lz_receive_initiate_requires_recovery_account() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    set_peer(&mut context, &vault, reclaimx::ETHEREUM_EID, [7u8; 32]).await;
+
+    let message = RecoveryMessage {
+        vault_id: vault.vault,
+        new_owner: Pubkey::new_unique(),
+        recovery_id: 0,
+        action: RecoveryAction::Initiate,
+    };
+    let payer = context.payer.pubkey();
+    let ixs = deliver_ixs(
+        &vault.vault,
+        &payer,
+        None,
+        reclaimx::ETHEREUM_EID,
+        [7u8; 32],
+        message.try_to_vec().unwrap(),
+    );
+    assert!(process(&mut context, &ixs, &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn lz_receive_cannot_overwrite_pending_recovery() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    set_peer(&mut context, &vault, reclaimx::ETHEREUM_EID, [7u8; 32]).await;
+    let new_owner = Keypair::new().pubkey();
+
+    let payer = context.payer.pubkey();
+    for (recovery_id, target, succeeds) in
+        [(1, new_owner, true), (2, Keypair::new().pubkey(), false)]
+    {
+        let message = RecoveryMessage {
+            vault_id: vault.vault,
+            new_owner: target,
+            recovery_id: 0,
+            action: RecoveryAction::Initiate,
+        };
+        let ixs = deliver_ixs(
+            &vault.vault,
+            &payer,
+            Some(recovery_pda(&vault.vault, recovery_id)),
+            reclaimx::ETHEREUM_EID,
+            [7u8; 32],
+            message.try_to_vec().unwrap(),
+        );
+        assert_eq!(process(&mut context, &ixs, &[]).await.is_ok(), succeeds);
+    }
+
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.recovery_id, 1);
+    let recovery = fetch_recovery(&mut context, &vault.vault, 1).await;
+    assert_eq!(recovery.new_owner, new_owner);
+    assert_eq!(recovery.status, RecoveryStatus::Pending);
+}
+
+#[tokio::test]
+async fn lz_receive_rejects_unknown_source() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    set_peer(&mut context, &vault, reclaimx::SEPOLIA_EID, [7u8; 32]).await;
+
+    let message = RecoveryMessage {
+        vault_id: vault.vault,
+        new_owner: Pubkey::new_unique(),
+        recovery_id: 0,
+        action: RecoveryAction::Initiate,
+    };
+    let payer = context.payer.pubkey();
+    let ixs = deliver_ixs(
+        &vault.vault,
+        &payer,
+        Some(recovery_pda(&vault.vault, 1)),
+        reclaimx::SEPOLIA_EID,
+        [7u8; 32],
+        message.try_to_vec().unwrap(),
+    );
+    assert!(process(&mut context, &ixs, &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn lz_receive_rejects_message_for_other_vault() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    set_peer(&mut context, &vault, reclaimx::ETHEREUM_EID, [7u8; 32]).await;
+
+    let message = RecoveryMessage {
+        vault_id: Pubkey::new_unique(),
+        new_owner: Pubkey::new_unique(),
+        recovery_id: 0,
+        action: RecoveryAction::Initiate,
+    };
+    let payer = context.payer.pubkey();
+    let ixs = deliver_ixs(
+        &vault.vault,
+        &payer,
+        Some(recovery_pda(&vault.vault, 1)),
+        reclaimx::ETHEREUM_EID,
+        [7u8; 32],
+        message.try_to_vec().unwrap(),
+    );
+    assert!(process(&mut context, &ixs, &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn lz_receive_rejects_sender_other_than_peer() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    set_peer(&mut context, &vault, reclaimx::ETHEREUM_EID, [7u8; 32]).await;
+
+    let message = RecoveryMessage {
+        vault_id: vault.vault,
+        new_owner: Pubkey::new_unique(),
+        recovery_id: 0,
+        action: RecoveryAction::Initiate,
+    };
+    let payer = context.payer.pubkey();
+    let ixs = deliver_ixs(
+        &vault.vault,
+        &payer,
+        Some(recovery_pda(&vault.vault, 1)),
+        reclaimx::ETHEREUM_EID,
+        [8u8; 32],
+        message.try_to_vec().unwrap(),
+    );
+    assert!(process(&mut context, &ixs, &[]).await.is_err());
+    assert_eq!(fetch_vault(&mut context, &vault.vault).await.recovery_id, 0);
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::*;
+use reclaimx::ownership::OWNERSHIP_TRANSFER_DELAY;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+#[tokio::test]
+async fn new_owner_accepts_after_delay_and_vault_keeps_signing() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let owner = vault.owner.pubkey();
+    let new_owner = Keypair::new();
+    let stranger = Keypair::new();
+    let ix = add_heartbeat_key_ix(&vault.vault, &owner, Pubkey::new_unique());
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+
+    let ix = transfer_ownership_ix(&vault.vault, &stranger.pubkey(), stranger.pubkey());
+    assert!(process(&mut context, &[ix], &[&stranger]).await.is_err());
+    let ix = transfer_ownership_ix(&vault.vault, &owner, new_owner.pubkey());
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+
+    let ix = accept_ownership_ix(&vault.vault, &stranger.pubkey());
+    assert!(process(&mut context, &[ix], &[&stranger]).await.is_err());
+    let accept = accept_ownership_ix(&vault.vault, &new_owner.pubkey());
+    assert!(process(&mut context, &[accept.clone()], &[&new_owner])
+        .await
+        .is_err());
+    warp_forward(&mut context, OWNERSHIP_TRANSFER_DELAY as i64).await;
+    process(&mut context, &[accept], &[&new_owner])
+        .await
+        .unwrap();
+
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.owner, new_owner.pubkey());
+    assert_eq!(state.creator, owner);
+    assert_eq!(state.pending_ownership, None);
+    // The previous owner's heartbeat keys no longer keep the vault alive
+    assert!(state.heartbeat_keys.is_empty());
+
+    // The vault still signs for its token accounts under the new owner
+    let mint_authority = context.payer.pubkey();
+    let mint = create_mint(&mut context, &mint_authority).await;
+    let vault_token_account = create_token_account(&mut context, &mint, &vault.vault).await;
+    let destination = create_token_account(&mut context, &mint, &new_owner.pubkey()).await;
+    mint_to(&mut context, &mint, &vault_token_account, 100).await;
+    let withdraw = |owner: &Pubkey| {
+        withdraw_asset_ix(
+            &vault.vault,
+            owner,
+            None,
+            &vault_token_account,
+            &destination,
+            100,
+        )
+    };
+    assert!(process(&mut context, &[withdraw(&owner)], &[&vault.owner])
+        .await
+        .is_err());
+    process(
+        &mut context,
+        &[withdraw(&new_owner.pubkey())],
+        &[&new_owner],
+    )
+    .await
+    .unwrap();
+    assert_eq!(token_balance(&mut context, &destination).await, 100);
+}
+
+#[tokio::test]
+async fn owner_cancels_ownership_transfer() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let owner = vault.owner.pubkey();
+    let new_owner = Keypair::new();
+
+    let ix = transfer_ownership_ix(&vault.vault, &owner, owner);
+    assert!(process(&mut context, &[ix], &[&vault.owner]).await.is_err());
+    let ix = transfer_ownership_ix(&vault.vault, &owner, new_owner.pubkey());
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+    let ix = cancel_ownership_transfer_ix(&vault.vault, &owner);
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+
+    warp_forward(&mut context, OWNERSHIP_TRANSFER_DELAY as i64).await;
+    let ix = accept_ownership_ix(&vault.vault, &new_owner.pubkey());
+    assert!(process(&mut context, &[ix], &[&new_owner]).await.is_err());
+    assert_eq!(fetch_vault(&mut context, &vault.vault).await.owner, owner);
+}
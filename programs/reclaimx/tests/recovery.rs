@@ -0,0 +1,703 @@
+mod common;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use reclaimx::{
+    attestation::{self, ApprovalMessage},
+    Asset, AssetType, RecoveryStatus, Vault,
+};
+use solana_sdk::{
+    ed25519_instruction::new_ed25519_instruction,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    secp256k1_instruction::{construct_eth_pubkey, new_secp256k1_instruction},
+    signature::{Keypair, Signer},
+    sysvar,
+};
+
+fn signed_approval_ix(guardian: &Keypair, message: &ApprovalMessage) -> Instruction {
+    let keypair = ed25519_dalek::Keypair::from_bytes(&guardian.to_bytes()).unwrap();
+    new_ed25519_instruction(&keypair, &message.to_bytes())
+}
+
+fn submit_evm_approvals_ix(vault: &Pubkey, relayer: &Pubkey, expiries: Vec<i64>) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::SubmitApprovals {
+            vault: *vault,
+            config: config_pda(),
+            recovery: recovery_pda(vault, 1),
+            relayer: *relayer,
+            instructions: sysvar::instructions::ID,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::SubmitEvmApprovals { expiries }.data(),
+    }
+}
+
+fn submit_approvals_ix(vault: &Pubkey, relayer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::SubmitApprovals {
+            vault: *vault,
+            config: config_pda(),
+            recovery: recovery_pda(vault, 1),
+            relayer: *relayer,
+            instructions: sysvar::instructions::ID,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::SubmitApprovals {}.data(),
+    }
+}
+
+fn expire_recovery_ix(vault: &Pubkey, recovery_id: u64, cranker: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::ExpireRecovery {
+            vault: *vault,
+            config: config_pda(),
+            recovery: recovery_pda(vault, recovery_id),
+            cranker: *cranker,
+            crank_reserve: None,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::ExpireRecovery {}.data(),
+    }
+}
+
+fn set_recovery_deadlines_ix(
+    vault: &Pubkey,
+    owner: &Pubkey,
+    recovery_expiry: u64,
+    approval_window: u64,
+) -> Instruction {
+    Instruction {
+        program_id: reclaimx::ID,
+        accounts: reclaimx::accounts::SetRecoveryDeadlines {
+            vault: *vault,
+            config: config_pda(),
+            owner: *owner,
+            event_authority: event_authority(),
+            program: reclaimx::ID,
+        }
+        .to_account_metas(None),
+        data: reclaimx::instruction::SetRecoveryDeadlines {
+            recovery_expiry,
+            approval_window,
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn initialize_vault_stores_configuration() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.owner, vault.owner.pubkey());
+    assert_eq!(state.guardians.len(), 3);
+    assert_eq!(state.threshold, 2);
+    assert_eq!(state.timelock, TIMELOCK);
+    assert_eq!(state.backup_wallet, vault.backup_wallet);
+    assert_eq!(state.recovery_id, 0);
+    assert_eq!(
+        state.recovery_expiry,
+        TIMELOCK + Vault::DEFAULT_RECOVERY_EXPIRY
+    );
+    assert_eq!(state.active_recovery, None);
+}
+
+#[tokio::test]
+async fn one_key_creates_several_vaults() {
+    let mut context = program_test_context().await;
+    let owner = Keypair::new();
+    fund(&mut context, &owner.pubkey(), 1_000_000_000).await;
+    let payer = context.payer.pubkey();
+
+    for vault_seed in [0, 1] {
+        let ix = initialize_vault_ix(
+            &owner.pubkey(),
+            &payer,
+            vault_seed,
+            vec![Pubkey::new_unique()],
+            1,
+            TIMELOCK,
+            INACTIVITY_PERIOD,
+            Pubkey::new_unique(),
+        );
+        process(&mut context, &[ix], &[&owner]).await.unwrap();
+        let state = fetch_vault(&mut context, &vault_pda(&owner.pubkey(), vault_seed)).await;
+        assert_eq!(state.creator, owner.pubkey());
+        assert_eq!(state.vault_seed, vault_seed);
+    }
+
+    // A seed can only be used once per creator
+    let ix = initialize_vault_ix(
+        &owner.pubkey(),
+        &payer,
+        1,
+        vec![Pubkey::new_unique()],
+        1,
+        TIMELOCK,
+        INACTIVITY_PERIOD,
+        Pubkey::new_unique(),
+    );
+    assert!(process(&mut context, &[ix], &[&owner]).await.is_err());
+}
+
+#[tokio::test]
+async fn guardian_initiates_recovery() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let new_owner = Keypair::new().pubkey();
+
+    let initiator = vault.guardians[0].pubkey();
+    let ix = initiate_recovery_ix(&vault, 1, &initiator, &context.payer.pubkey(), new_owner);
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.recovery_id, 1);
+    assert_eq!(state.active_recovery, Some(recovery_pda(&vault.vault, 1)));
+    let recovery = fetch_recovery(&mut context, &vault.vault, 1).await;
+    assert_eq!(recovery.vault, vault.vault);
+    assert_eq!(recovery.recovery_id, 1);
+    assert_eq!(recovery.proposer, initiator);
+    assert_eq!(recovery.new_owner, new_owner);
+    assert!(recovery.approvals.is_empty());
+    assert_eq!(recovery.status, RecoveryStatus::Pending);
+}
+
+#[tokio::test]
+async fn second_initiate_cannot_overwrite_pending_recovery() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let new_owner = Keypair::new().pubkey();
+
+    let initiator = vault.guardians[0].pubkey();
+    let ix = initiate_recovery_ix(&vault, 1, &initiator, &context.payer.pubkey(), new_owner);
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+
+    let rogue = vault.guardians[1].pubkey();
+    let ix = initiate_recovery_ix(&vault, 2, &rogue, &context.payer.pubkey(), rogue);
+    assert!(process(&mut context, &[ix], &[&vault.guardians[1]])
+        .await
+        .is_err());
+
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.recovery_id, 1);
+    let recovery = fetch_recovery(&mut context, &vault.vault, 1).await;
+    assert_eq!(recovery.new_owner, new_owner);
+}
+
+#[tokio::test]
+async fn stranger_cannot_initiate_recovery() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let stranger = Keypair::new();
+
+    let ix = initiate_recovery_ix(
+        &vault,
+        1,
+        &stranger.pubkey(),
+        &context.payer.pubkey(),
+        stranger.pubkey(),
+    );
+    assert!(process(&mut context, &[ix], &[&stranger]).await.is_err());
+}
+
+#[tokio::test]
+async fn duplicate_approval_is_rejected() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let new_owner = Keypair::new().pubkey();
+
+    let initiator = vault.guardians[0].pubkey();
+    let ix = initiate_recovery_ix(&vault, 1, &initiator, &context.payer.pubkey(), new_owner);
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+
+    let guardian = &vault.guardians[1];
+    let ix = approve_recovery_ix(&vault.vault, 1, &guardian.pubkey(), &[]);
+    process(&mut context, &[ix.clone()], &[guardian])
+        .await
+        .unwrap();
+    assert!(process(&mut context, &[ix], &[guardian]).await.is_err());
+}
+
+#[tokio::test]
+async fn owner_or_guardian_quorum_cancels_recovery() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let payer = context.payer.pubkey();
+    let owner = &vault.owner;
+
+    for (recovery_id, initiator) in [(1, owner), (2, &vault.guardians[0])] {
+        let ix = initiate_recovery_ix(
+            &vault,
+            recovery_id,
+            &initiator.pubkey(),
+            &payer,
+            Pubkey::new_unique(),
+        );
+        process(&mut context, &[ix], &[initiator]).await.unwrap();
+
+        if recovery_id == 1 {
+            // The owner withdraws a recovery they started themselves
+            let ix = cancel_recovery_ix(&vault.vault, recovery_id, &owner.pubkey());
+            process(&mut context, &[ix], &[owner]).await.unwrap();
+        } else {
+            // but cannot stop one the guardians started
+            let ix = cancel_recovery_ix(&vault.vault, recovery_id, &owner.pubkey());
+            assert!(process(&mut context, &[ix], &[owner]).await.is_err());
+            let stranger = Keypair::new();
+            let ix = cancel_recovery_ix(&vault.vault, recovery_id, &stranger.pubkey());
+            assert!(process(&mut context, &[ix], &[&stranger]).await.is_err());
+            for guardian in &vault.guardians[1..] {
+                let ix = cancel_recovery_ix(&vault.vault, recovery_id, &guardian.pubkey());
+                process(&mut context, &[ix], &[guardian]).await.unwrap();
+            }
+        }
+
+        let recovery = fetch_recovery(&mut context, &vault.vault, recovery_id).await;
+        assert_eq!(recovery.status, RecoveryStatus::Cancelled);
+        let state = fetch_vault(&mut context, &vault.vault).await;
+        assert_eq!(state.active_recovery, None);
+    }
+}
+
+#[tokio::test]
+async fn recovery_completes_only_after_timelock() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let new_owner = Keypair::new().pubkey();
+
+    let initiator = vault.guardians[0].pubkey();
+    let ix = initiate_recovery_ix(&vault, 1, &initiator, &context.payer.pubkey(), new_owner);
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+
+    // Threshold reached inside the timelock: still pending
+    for guardian in &vault.guardians[..2] {
+        let ix = approve_recovery_ix(&vault.vault, 1, &guardian.pubkey(), &[]);
+        process(&mut context, &[ix], &[guardian]).await.unwrap();
+    }
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.owner, vault.owner.pubkey());
+    let recovery = fetch_recovery(&mut context, &vault.vault, 1).await;
+    assert_eq!(recovery.status, RecoveryStatus::Pending);
+
+    warp_forward(&mut context, TIMELOCK as i64).await;
+
+    let guardian = &vault.guardians[2];
+    let ix = approve_recovery_ix(&vault.vault, 1, &guardian.pubkey(), &[]);
+    process(&mut context, &[ix], &[guardian]).await.unwrap();
+
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.owner, new_owner);
+    assert_eq!(state.active_recovery, None);
+    let recovery = fetch_recovery(&mut context, &vault.vault, 1).await;
+    assert_eq!(recovery.status, RecoveryStatus::Executed);
+    assert_eq!(recovery.end_timestamp, now(&mut context).await);
+}
+
+#[tokio::test]
+async fn anyone_executes_recovery_after_timelock() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let new_owner = Keypair::new().pubkey();
+    let cranker = Keypair::new();
+
+    let initiator = vault.guardians[0].pubkey();
+    let ix = initiate_recovery_ix(&vault, 1, &initiator, &context.payer.pubkey(), new_owner);
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+    for guardian in &vault.guardians[..2] {
+        let ix = approve_recovery_ix(&vault.vault, 1, &guardian.pubkey(), &[]);
+        process(&mut context, &[ix], &[guardian]).await.unwrap();
+    }
+
+    let ix = execute_recovery_ix(&vault.vault, 1, &cranker.pubkey(), None, &[]);
+    assert!(process(&mut context, &[ix], &[&cranker]).await.is_err());
+
+    warp_forward(&mut context, TIMELOCK as i64).await;
+    let ix = execute_recovery_ix(&vault.vault, 1, &cranker.pubkey(), None, &[]);
+    process(&mut context, &[ix], &[&cranker]).await.unwrap();
+
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.owner, new_owner);
+    assert_eq!(state.active_recovery, None);
+    let recovery = fetch_recovery(&mut context, &vault.vault, 1).await;
+    assert_eq!(recovery.status, RecoveryStatus::Executed);
+    assert_eq!(recovery.end_timestamp, now(&mut context).await);
+}
+
+#[tokio::test]
+async fn recovered_vault_still_signs_for_its_tokens() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let new_owner = Keypair::new();
+    let mint_authority = context.payer.pubkey();
+    let mint = create_mint(&mut context, &mint_authority).await;
+    let vault_token_account = create_token_account(&mut context, &mint, &vault.vault).await;
+    let new_owner_token_account =
+        create_token_account(&mut context, &mint, &new_owner.pubkey()).await;
+    mint_to(&mut context, &mint, &vault_token_account, 100).await;
+
+    let initiator = vault.guardians[0].pubkey();
+    let ix = initiate_recovery_ix(
+        &vault,
+        1,
+        &initiator,
+        &context.payer.pubkey(),
+        new_owner.pubkey(),
+    );
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+    for guardian in &vault.guardians[..2] {
+        let ix = approve_recovery_ix(&vault.vault, 1, &guardian.pubkey(), &[]);
+        process(&mut context, &[ix], &[guardian]).await.unwrap();
+    }
+    warp_forward(&mut context, TIMELOCK as i64).await;
+    let cranker = context.payer.pubkey();
+    let ix = execute_recovery_ix(&vault.vault, 1, &cranker, None, &[]);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    // The vault address no longer matches the owner, yet it signs as before
+    let ix = withdraw_asset_ix(
+        &vault.vault,
+        &new_owner.pubkey(),
+        None,
+        &vault_token_account,
+        &new_owner_token_account,
+        100,
+    );
+    process(&mut context, &[ix], &[&new_owner]).await.unwrap();
+    assert_eq!(
+        token_balance(&mut context, &new_owner_token_account).await,
+        100
+    );
+}
+
+#[tokio::test]
+async fn recovered_assets_only_move_to_new_owner_accounts() {
+    let mut context = program_test_context().await;
+    let (vault, assets) = initialize_vault_holding(&mut context, 3, 2, &[100, 50]).await;
+    let new_owner = Keypair::new().pubkey();
+    let stranger = Keypair::new().pubkey();
+    let mut pairs = vec![];
+    let mut stranger_accounts = vec![];
+    for asset in &assets {
+        let to = create_token_account(&mut context, &asset.mint, &new_owner).await;
+        pairs.push((asset.token_account, to));
+        stranger_accounts.push(create_token_account(&mut context, &asset.mint, &stranger).await);
+    }
+
+    let initiator = vault.guardians[0].pubkey();
+    let ix = initiate_recovery_ix(&vault, 1, &initiator, &context.payer.pubkey(), new_owner);
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+    for guardian in &vault.guardians[..2] {
+        let ix = approve_recovery_ix(&vault.vault, 1, &guardian.pubkey(), &[]);
+        process(&mut context, &[ix], &[guardian]).await.unwrap();
+    }
+    warp_forward(&mut context, TIMELOCK as i64).await;
+
+    // A destination the new owner does not hold
+    let foreign = vec![pairs[0], (assets[1].token_account, stranger_accounts[1])];
+    let guardian = &vault.guardians[2];
+    let ix = approve_recovery_ix(&vault.vault, 1, &guardian.pubkey(), &foreign);
+    assert!(process(&mut context, &[ix], &[guardian]).await.is_err());
+
+    let cranker = context.payer.pubkey();
+    let swapped = vec![pairs[1], pairs[0]];
+    for asset_accounts in [foreign, swapped, pairs[..1].to_vec()] {
+        let ix = execute_recovery_ix(&vault.vault, 1, &cranker, None, &asset_accounts);
+        assert!(process(&mut context, &[ix], &[]).await.is_err());
+    }
+    let ix = execute_recovery_ix(&vault.vault, 1, &cranker, None, &pairs);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    for (asset, (_, to)) in assets.iter().zip(&pairs) {
+        assert_eq!(token_balance(&mut context, to).await, asset.amount);
+    }
+}
+
+#[tokio::test]
+async fn recovery_goes_through_with_drained_or_closed_asset_accounts() {
+    let mut context = program_test_context().await;
+    let (vault, assets) = initialize_vault_holding(&mut context, 3, 2, &[100, 50, 30]).await;
+    let new_owner = Keypair::new().pubkey();
+    let mut pairs = vec![];
+    for asset in &assets {
+        let to = create_token_account(&mut context, &asset.mint, &new_owner).await;
+        pairs.push((asset.token_account, to));
+    }
+
+    let initiator = vault.guardians[0].pubkey();
+    let ix = initiate_recovery_ix(&vault, 1, &initiator, &context.payer.pubkey(), new_owner);
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+    for guardian in &vault.guardians[..2] {
+        let ix = approve_recovery_ix(&vault.vault, 1, &guardian.pubkey(), &[]);
+        process(&mut context, &[ix], &[guardian]).await.unwrap();
+    }
+    warp_forward(&mut context, TIMELOCK as i64).await;
+
+    // One asset account emptied, one partly drained and one closed
+    set_token_balance(&mut context, &assets[0].token_account, 0).await;
+    set_token_balance(&mut context, &assets[1].token_account, 20).await;
+    context.set_account(&assets[2].token_account, &Default::default());
+
+    let cranker = context.payer.pubkey();
+    let ix = execute_recovery_ix(&vault.vault, 1, &cranker, None, &pairs);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    assert_eq!(
+        fetch_vault(&mut context, &vault.vault).await.owner,
+        new_owner
+    );
+    assert_eq!(token_balance(&mut context, &pairs[0].1).await, 0);
+    assert_eq!(token_balance(&mut context, &pairs[1].1).await, 20);
+    assert_eq!(token_balance(&mut context, &pairs[2].1).await, 0);
+}
+
+#[tokio::test]
+async fn initialize_vault_checks_asset_accounts() {
+    let mut context = program_test_context().await;
+    let owner = Keypair::new();
+    fund(&mut context, &owner.pubkey(), 1_000_000_000).await;
+    let payer = context.payer.pubkey();
+    let vault = vault_pda(&owner.pubkey(), 0);
+    let mint = create_mint(&mut context, &payer).await;
+    let held = create_token_account(&mut context, &mint, &vault).await;
+    let foreign = create_token_account(&mut context, &mint, &owner.pubkey()).await;
+    mint_to(&mut context, &mint, &held, 10).await;
+    mint_to(&mut context, &mint, &foreign, 10).await;
+    let asset = |token_account, amount| Asset {
+        asset_type: AssetType::Token,
+        amount,
+        token_account,
+        mint,
+    };
+    let guardians = vec![Pubkey::new_unique()];
+    let ix = |assets: &[Asset]| {
+        initialize_vault_with_assets_ix(
+            &owner.pubkey(),
+            &payer,
+            guardians.clone(),
+            1,
+            Pubkey::new_unique(),
+            assets,
+        )
+    };
+
+    // More than the account holds, or an account the vault does not own
+    for assets in [[asset(held, 11)], [asset(foreign, 10)]] {
+        assert!(process(&mut context, &[ix(&assets)], &[&owner])
+            .await
+            .is_err());
+    }
+    let mut missing = ix(&[asset(held, 10)]);
+    missing.accounts.pop();
+    assert!(process(&mut context, &[missing], &[&owner]).await.is_err());
+
+    process(&mut context, &[ix(&[asset(held, 10)])], &[&owner])
+        .await
+        .unwrap();
+    assert_eq!(fetch_vault(&mut context, &vault).await.assets.len(), 1);
+}
+
+#[tokio::test]
+async fn relayer_submits_signed_guardian_approvals() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let new_owner = Keypair::new().pubkey();
+
+    let initiator = vault.guardians[0].pubkey();
+    let ix = initiate_recovery_ix(&vault, 1, &initiator, &context.payer.pubkey(), new_owner);
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+
+    let message = ApprovalMessage {
+        vault: vault.vault,
+        recovery_id: 1,
+        new_owner,
+        expiry: now(&mut context).await + 600,
+    };
+    // Guardians sign off-chain; the payer relays both approvals at once
+    let relayer = context.payer.pubkey();
+    let ixs = [
+        signed_approval_ix(&vault.guardians[0], &message),
+        signed_approval_ix(&vault.guardians[1], &message),
+        submit_approvals_ix(&vault.vault, &relayer),
+    ];
+    process(&mut context, &ixs, &[]).await.unwrap();
+
+    let recovery = fetch_recovery(&mut context, &vault.vault, 1).await;
+    assert_eq!(recovery.approvals.len(), 2);
+
+    warp_forward(&mut context, TIMELOCK as i64).await;
+    let ix = execute_recovery_ix(&vault.vault, 1, &relayer, None, &[]);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    assert_eq!(
+        fetch_vault(&mut context, &vault.vault).await.owner,
+        new_owner
+    );
+}
+
+#[tokio::test]
+async fn expired_or_foreign_attestations_are_rejected() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let new_owner = Keypair::new().pubkey();
+
+    let initiator = vault.guardians[0].pubkey();
+    let ix = initiate_recovery_ix(&vault, 1, &initiator, &context.payer.pubkey(), new_owner);
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+
+    let relayer = context.payer.pubkey();
+    let valid = ApprovalMessage {
+        vault: vault.vault,
+        recovery_id: 1,
+        new_owner,
+        expiry: now(&mut context).await + 600,
+    };
+    let expired = ApprovalMessage {
+        expiry: now(&mut context).await - 1,
+        ..valid.clone()
+    };
+    let wrong_owner = ApprovalMessage {
+        new_owner: Pubkey::new_unique(),
+        ..valid.clone()
+    };
+    let stranger = Keypair::new();
+
+    for (signer, message) in [
+        (&vault.guardians[1], &expired),
+        (&vault.guardians[1], &wrong_owner),
+        (&stranger, &valid),
+    ] {
+        let ixs = [
+            signed_approval_ix(signer, message),
+            submit_approvals_ix(&vault.vault, &relayer),
+        ];
+        assert!(process(&mut context, &ixs, &[]).await.is_err());
+    }
+}
+
+#[tokio::test]
+async fn evm_guardian_approves_with_eip712_signature() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let new_owner = Keypair::new().pubkey();
+    let evm_key = libsecp256k1::SecretKey::parse(&[7u8; 32]).unwrap();
+    let evm_address = construct_eth_pubkey(&libsecp256k1::PublicKey::from_secret_key(&evm_key));
+
+    let ix = add_evm_guardian_ix(&vault.vault, &vault.owner.pubkey(), evm_address);
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+    let initiator = vault.guardians[0].pubkey();
+    let ix = initiate_recovery_ix(&vault, 1, &initiator, &context.payer.pubkey(), new_owner);
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+
+    let expiry = now(&mut context).await + 600;
+    let message = ApprovalMessage {
+        vault: vault.vault,
+        recovery_id: 1,
+        new_owner,
+        expiry,
+    };
+    let preimage = attestation::eip712_preimage(&message).unwrap();
+    let relayer = context.payer.pubkey();
+
+    // The precompile must sit at index 0, where new_secp256k1_instruction points
+    let ixs = [
+        new_secp256k1_instruction(&evm_key, &preimage),
+        submit_evm_approvals_ix(&vault.vault, &relayer, vec![expiry + 1]),
+    ];
+    assert!(process(&mut context, &ixs, &[]).await.is_err());
+
+    let ixs = [
+        new_secp256k1_instruction(&evm_key, &preimage),
+        submit_evm_approvals_ix(&vault.vault, &relayer, vec![expiry]),
+    ];
+    process(&mut context, &ixs, &[]).await.unwrap();
+
+    let recovery = fetch_recovery(&mut context, &vault.vault, 1).await;
+    assert_eq!(recovery.approvals.len(), 1);
+    assert_eq!(
+        recovery.approvals[0].guardian,
+        attestation::evm_guardian_key(&evm_address)
+    );
+}
+
+#[tokio::test]
+async fn stale_recovery_expires_and_deadlines_are_locked_while_pending() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let owner = vault.owner.pubkey();
+    let cranker = Keypair::new();
+    fund(&mut context, &cranker.pubkey(), 1_000_000_000).await;
+
+    let recovery_expiry = TIMELOCK * 2;
+    let ix = set_recovery_deadlines_ix(&vault.vault, &owner, recovery_expiry, TIMELOCK);
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+
+    let initiator = vault.guardians[0].pubkey();
+    let ix = initiate_recovery_ix(
+        &vault,
+        1,
+        &initiator,
+        &context.payer.pubkey(),
+        Keypair::new().pubkey(),
+    );
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+
+    // The owner cannot shorten the deadline of a recovery already underway
+    let ix = set_recovery_deadlines_ix(&vault.vault, &owner, TIMELOCK + 1, 1);
+    assert!(process(&mut context, &[ix], &[&vault.owner]).await.is_err());
+
+    let ix = expire_recovery_ix(&vault.vault, 1, &cranker.pubkey());
+    assert!(process(&mut context, &[ix], &[&cranker]).await.is_err());
+
+    warp_forward(&mut context, recovery_expiry as i64).await;
+    let ix = expire_recovery_ix(&vault.vault, 1, &cranker.pubkey());
+    process(&mut context, &[ix], &[&cranker]).await.unwrap();
+
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.owner, owner);
+    assert_eq!(state.active_recovery, None);
+
+    // The expired recovery stays on-chain next to the one that replaces it
+    let ix = initiate_recovery_ix(&vault, 2, &initiator, &context.payer.pubkey(), owner);
+    process(&mut context, &[ix], &[&vault.guardians[0]])
+        .await
+        .unwrap();
+    let expired = fetch_recovery(&mut context, &vault.vault, 1).await;
+    assert_eq!(expired.status, RecoveryStatus::Expired);
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.active_recovery, Some(recovery_pda(&vault.vault, 2)));
+}
@@ -0,0 +1,487 @@
+use anchor_lang::prelude::{AnchorSerialize, Pubkey};
+use proptest::prelude::*;
+use reclaimx::recovery::{self, ApprovalOutcome, CancelOutcome};
+use reclaimx::{attestation, freeze};
+use reclaimx::{CrankKind, CrankReserve, Recovery, RecoveryStatus, Vault};
+
+const START: i64 = 1_700_000_000;
+
+fn vault(guardian_count: usize, threshold: u8, timelock: u64) -> Vault {
+    Vault {
+        owner: Pubkey::new_unique(),
+        guardians: (0..guardian_count).map(|_| Pubkey::new_unique()).collect(),
+        threshold,
+        timelock,
+        inactivity_period: 86_400,
+        backup_wallet: Pubkey::new_unique(),
+        last_active_timestamp: START,
+        bump: 255,
+        recovery_expiry: timelock + Vault::DEFAULT_RECOVERY_EXPIRY,
+        approval_window: timelock + Vault::DEFAULT_RECOVERY_EXPIRY,
+        creator: Pubkey::new_unique(),
+        ..Vault::default()
+    }
+}
+
+// Opens a recovery in a fresh account, as `initiate_recovery` would
+fn start(vault: &mut Vault, new_owner: Pubkey, now: i64) -> Recovery {
+    let mut recovery = Recovery::default();
+    recovery::start(
+        vault,
+        &mut recovery,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        new_owner,
+        now,
+        false,
+    )
+    .unwrap();
+    recovery
+}
+
+#[derive(Clone, Debug)]
+enum Op {
+    Initiate,
+    Approve(usize),
+    ApproveStranger,
+    MarkMalicious(usize),
+    Warp(u32),
+    CheckInactivity,
+    CancelInactivity,
+    // Index past the last guardian cancels as the owner
+    Cancel(usize),
+    Expire,
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        Just(Op::Initiate),
+        (0usize..10).prop_map(Op::Approve),
+        Just(Op::ApproveStranger),
+        (0usize..10).prop_map(Op::MarkMalicious),
+        (0u32..200_000).prop_map(Op::Warp),
+        (0u32..5_000_000).prop_map(Op::Warp),
+        Just(Op::CheckInactivity),
+        Just(Op::CancelInactivity),
+        (0usize..11).prop_map(Op::Cancel),
+        Just(Op::Expire),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn owner_changes_only_after_threshold_and_timelock(
+        guardian_count in 1usize..10,
+        threshold_seed in 0u8..10,
+        timelock in 0u64..100_000,
+        ops in prop::collection::vec(op(), 1..64),
+    ) {
+        let threshold = threshold_seed % guardian_count as u8 + 1;
+        let mut vault = vault(guardian_count, threshold, timelock);
+        let original_owner = vault.owner;
+        let mut now = START;
+        // Latest recovery account; earlier ones are never touched again
+        let mut current: Option<Recovery> = None;
+
+        for op in ops {
+            let before = vault.clone();
+            let before_recovery = current.clone();
+            match (op, current.as_mut()) {
+                (Op::Initiate, _) => {
+                    let mut recovery = Recovery::default();
+                    match recovery::start(&mut vault, &mut recovery, Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), now, false) {
+                        Ok(id) => {
+                            prop_assert!(before.active_recovery.is_none());
+                            prop_assert_eq!(id, before.recovery_id + 1);
+                            current = Some(recovery);
+                        }
+                        Err(_) => prop_assert!(before.active_recovery.is_some()),
+                    }
+                }
+                (Op::CheckInactivity, _) => {
+                    let mut recovery = Recovery::default();
+                    if let Ok(id) = recovery::check_inactivity(&mut vault, &mut recovery, Pubkey::new_unique(), Pubkey::new_unique(), now) {
+                        prop_assert!(before.active_recovery.is_none());
+                        prop_assert_eq!(id, before.recovery_id + 1);
+                        current = Some(recovery);
+                    }
+                }
+                (Op::Warp(seconds), _) => now += seconds as i64,
+                // Every other instruction needs an existing recovery account
+                (_, None) => {}
+                (Op::Approve(index), Some(recovery)) => {
+                    let guardian = vault.guardians[index % guardian_count];
+                    if let Ok(ApprovalOutcome::Completed { new_owner, .. }) =
+                        recovery::approve(&mut vault, recovery, guardian, now)
+                    {
+                        let before_recovery = before_recovery.as_ref().unwrap();
+                        prop_assert_eq!(before_recovery.status, RecoveryStatus::Pending);
+                        prop_assert_eq!(before_recovery.new_owner, new_owner);
+                        prop_assert!(before_recovery.approvals.len() + 1 >= threshold as usize);
+                        prop_assert!(now >= before_recovery.start_timestamp + timelock as i64);
+                        prop_assert!(now < before_recovery.start_timestamp + before.recovery_expiry as i64);
+                        prop_assert_eq!(vault.owner, new_owner);
+                    }
+                }
+                (Op::ApproveStranger, Some(recovery)) => {
+                    prop_assert!(recovery::approve(&mut vault, recovery, Pubkey::new_unique(), now).is_err());
+                }
+                (Op::MarkMalicious(index), Some(recovery)) => {
+                    let guardian = vault.guardians[index % guardian_count];
+                    let _ = recovery::mark_malicious(&vault, recovery, guardian);
+                }
+                (Op::CancelInactivity, Some(recovery)) => {
+                    let _ = recovery::cancel_inactivity_recovery(&mut vault, recovery, now);
+                }
+                (Op::Cancel(index), Some(recovery)) => {
+                    let signer = vault.guardians.get(index).copied().unwrap_or(vault.owner);
+                    if let Ok(CancelOutcome::Cancelled) = recovery::cancel(&mut vault, recovery, signer, now) {
+                        prop_assert!(!recovery.inactivity_triggered);
+                        prop_assert!(
+                            (signer == vault.owner && recovery.proposer == vault.owner)
+                                || recovery.cancel_votes.len() >= threshold as usize
+                        );
+                        prop_assert_eq!(recovery.status, RecoveryStatus::Cancelled);
+                    }
+                }
+                (Op::Expire, Some(recovery)) => {
+                    if recovery::expire(&mut vault, recovery, now).is_ok() {
+                        prop_assert!(recovery::expired(&before, before_recovery.as_ref().unwrap(), now));
+                        prop_assert_eq!(recovery.status, RecoveryStatus::Expired);
+                    }
+                }
+            }
+
+            let Some(current) = &current else {
+                prop_assert_eq!(vault.active_recovery, None);
+                continue;
+            };
+            if vault.owner != before.owner {
+                prop_assert_eq!(current.status, RecoveryStatus::Executed);
+            }
+            // The vault points at a recovery exactly while it is pending
+            prop_assert_eq!(vault.active_recovery.is_some(), current.status == RecoveryStatus::Pending);
+            // A pending recovery is never replaced by another one
+            if before.active_recovery.is_some() && vault.active_recovery.is_some() {
+                prop_assert_eq!(before.active_recovery, vault.active_recovery);
+                prop_assert_eq!(before.recovery_id, vault.recovery_id);
+            }
+            // A finished recovery keeps its outcome
+            if let Some(previous) = before_recovery.filter(|r| r.recovery_id == current.recovery_id) {
+                if previous.status != RecoveryStatus::Pending {
+                    prop_assert_eq!(current.status, previous.status);
+                }
+            }
+            if current.status == RecoveryStatus::Pending {
+                let (approvals, malicious_approvals) = (&current.approvals, &current.malicious_approvals);
+                for (i, approval) in approvals.iter().enumerate() {
+                    prop_assert!(vault.guardians.contains(&approval.guardian));
+                    prop_assert!(approval.timestamp <= now);
+                    prop_assert!(approvals[..i].iter().all(|a| a.guardian != approval.guardian));
+                }
+                for (i, voter) in malicious_approvals.iter().enumerate() {
+                    prop_assert!(vault.guardians.contains(voter));
+                    prop_assert!(!malicious_approvals[..i].contains(voter));
+                }
+                for (i, voter) in current.cancel_votes.iter().enumerate() {
+                    prop_assert!(vault.guardians.contains(voter));
+                    prop_assert!(!current.cancel_votes[..i].contains(voter));
+                }
+            }
+        }
+
+        if vault.owner != original_owner {
+            prop_assert!(vault.recovery_id > 0);
+        }
+    }
+
+    #[test]
+    fn rejected_approval_leaves_state_unchanged(
+        guardian_count in 1usize..10,
+        index in 0usize..10,
+        now_offset in 0i64..1_000_000,
+    ) {
+        let mut vault = vault(guardian_count, 1, 0);
+        let guardian = vault.guardians[index % guardian_count];
+
+        // The recovery has already been executed
+        let mut recovery = start(&mut vault, Pubkey::new_unique(), START);
+        recovery::approve(&mut vault, &mut recovery, vault.guardians[0], START).unwrap();
+        let before = (vault.try_to_vec().unwrap(), recovery.try_to_vec().unwrap());
+        prop_assert!(recovery::approve(&mut vault, &mut recovery, guardian, START + now_offset).is_err());
+        prop_assert_eq!(&before, &(vault.try_to_vec().unwrap(), recovery.try_to_vec().unwrap()));
+
+        // Stranger cannot approve
+        let mut recovery = start(&mut vault, Pubkey::new_unique(), START);
+        let before = (vault.try_to_vec().unwrap(), recovery.try_to_vec().unwrap());
+        prop_assert!(recovery::approve(&mut vault, &mut recovery, Pubkey::new_unique(), START + now_offset).is_err());
+        prop_assert_eq!(&before, &(vault.try_to_vec().unwrap(), recovery.try_to_vec().unwrap()));
+    }
+
+    #[test]
+    fn inactivity_triggers_exactly_at_period(
+        period in 1u64..10_000_000,
+        elapsed in 0i64..20_000_000,
+    ) {
+        let mut vault = vault(3, 2, 0);
+        vault.inactivity_period = period;
+        let mut recovery = Recovery::default();
+        let cranker = Pubkey::new_unique();
+        let started = recovery::check_inactivity(&mut vault, &mut recovery, Pubkey::new_unique(), cranker, START + elapsed);
+        prop_assert_eq!(started.is_ok(), elapsed >= period as i64);
+        if started.is_ok() {
+            prop_assert_eq!(recovery.new_owner, vault.backup_wallet);
+            prop_assert_eq!(recovery.proposer, cranker);
+            prop_assert!(recovery.inactivity_triggered);
+        } else {
+            prop_assert_eq!(vault.active_recovery, None);
+        }
+    }
+}
+
+#[test]
+fn duplicate_approval_is_rejected() {
+    let mut vault = vault(3, 2, 0);
+    let guardian = vault.guardians[0];
+    let mut recovery = start(&mut vault, Pubkey::new_unique(), START);
+
+    assert!(recovery::approve(&mut vault, &mut recovery, guardian, START).is_ok());
+    assert!(recovery::approve(&mut vault, &mut recovery, guardian, START).is_err());
+}
+
+#[test]
+fn pending_recovery_cannot_be_overwritten() {
+    let mut vault = vault(3, 2, 0);
+    vault.inactivity_period = 0;
+    let mut recovery = start(&mut vault, Pubkey::new_unique(), START);
+    let active = vault.active_recovery;
+    let guardian = vault.guardians[0];
+    recovery::approve(&mut vault, &mut recovery, guardian, START).unwrap();
+
+    let mut other = Recovery::default();
+    assert!(recovery::start(
+        &mut vault,
+        &mut other,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        START,
+        false
+    )
+    .is_err());
+    assert!(recovery::check_inactivity(
+        &mut vault,
+        &mut other,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        START
+    )
+    .is_err());
+    assert_eq!(vault.active_recovery, active);
+    assert_eq!(vault.recovery_id, 1);
+    assert_eq!(recovery.approvals.len(), 1);
+}
+
+#[test]
+fn new_recovery_after_expiry_starts_without_approvals() {
+    let mut vault = vault(3, 2, 0);
+    let mut first = start(&mut vault, Pubkey::new_unique(), START);
+    let guardian = vault.guardians[0];
+    recovery::approve(&mut vault, &mut first, guardian, START).unwrap();
+
+    let deadline = START + vault.recovery_expiry as i64;
+    recovery::expire(&mut vault, &mut first, deadline).unwrap();
+    assert_eq!(first.status, RecoveryStatus::Expired);
+    assert_eq!(first.end_timestamp, deadline);
+    assert_eq!(vault.active_recovery, None);
+
+    let second = start(&mut vault, Pubkey::new_unique(), deadline);
+    assert_eq!(second.recovery_id, 2);
+    assert!(second.approvals.is_empty());
+    // The first recovery keeps its history
+    assert_eq!(first.approvals.len(), 1);
+}
+
+#[test]
+fn stale_approvals_do_not_count() {
+    let mut vault = vault(3, 2, 0);
+    vault.approval_window = 1_000;
+    let mut recovery = start(&mut vault, Pubkey::new_unique(), START);
+    let (first, second) = (vault.guardians[0], vault.guardians[1]);
+
+    recovery::record_approval(&vault, &mut recovery, first, START).unwrap();
+    assert_eq!(
+        recovery::record_approval(&vault, &mut recovery, second, START + 1_000).unwrap(),
+        1
+    );
+    assert!(!recovery::ready_to_execute(
+        &vault,
+        &recovery,
+        START + 1_000
+    ));
+
+    // The first guardian's approval lapsed, so they may give it again
+    let outcome = recovery::approve(&mut vault, &mut recovery, first, START + 1_001).unwrap();
+    assert!(matches!(
+        outcome,
+        ApprovalOutcome::Completed { approvals: 2, .. }
+    ));
+}
+
+#[test]
+fn removed_evm_guardian_approvals_do_not_count() {
+    let mut vault = vault(2, 2, 0);
+    let evm_guardian = [7u8; 20];
+    vault.evm_guardians.push(evm_guardian);
+    let mut recovery = start(&mut vault, Pubkey::new_unique(), START);
+
+    let evm_key = attestation::evm_guardian_key(&evm_guardian);
+    recovery::record_approval(&vault, &mut recovery, evm_key, START).unwrap();
+    recovery::record_approval(&vault, &mut recovery, vault.guardians[0], START).unwrap();
+    assert!(recovery::ready_to_execute(&vault, &recovery, START));
+
+    // The owner drops the EVM guardian before the recovery executes
+    vault.evm_guardians.clear();
+    assert_eq!(recovery::guardian_approvals(&vault, &recovery, START), 1);
+    assert!(!recovery::ready_to_execute(&vault, &recovery, START));
+    assert!(recovery::execute(&mut vault, &mut recovery, START).is_err());
+
+    assert_eq!(
+        recovery::record_approval(&vault, &mut recovery, vault.guardians[1], START).unwrap(),
+        2
+    );
+    assert!(recovery::ready_to_execute(&vault, &recovery, START));
+}
+
+#[test]
+fn recovery_expires_at_deadline() {
+    let mut vault = vault(3, 2, 3_600);
+    vault.recovery_expiry = 10_000;
+    let mut recovery = start(&mut vault, Pubkey::new_unique(), START);
+    for guardian in vault.guardians.clone().into_iter().take(2) {
+        recovery::record_approval(&vault, &mut recovery, guardian, START).unwrap();
+    }
+
+    assert!(recovery::expire(&mut vault, &mut recovery, START + 9_999).is_err());
+    assert!(recovery::ready_to_execute(&vault, &recovery, START + 9_999));
+    assert!(!recovery::ready_to_execute(
+        &vault,
+        &recovery,
+        START + 10_000
+    ));
+    assert!(recovery::execute(&mut vault.clone(), &mut recovery.clone(), START + 10_000).is_err());
+    let late = vault.guardians[2];
+    assert!(recovery::record_approval(&vault, &mut recovery, late, START + 10_000).is_err());
+
+    assert_eq!(
+        recovery::expire(&mut vault, &mut recovery, START + 10_000).unwrap(),
+        1
+    );
+    assert_eq!(recovery.status, RecoveryStatus::Expired);
+    assert_eq!(vault.active_recovery, None);
+    assert!(recovery::expire(&mut vault, &mut recovery, START + 10_000).is_err());
+}
+
+#[test]
+fn guardian_quorum_cancels_recovery() {
+    let mut vault = vault(3, 2, 0);
+    let mut recovery = start(&mut vault, Pubkey::new_unique(), START);
+    let active = vault.active_recovery;
+    let (owner, first, second) = (vault.owner, vault.guardians[0], vault.guardians[1]);
+
+    assert!(recovery::cancel(&mut vault, &mut recovery, Pubkey::new_unique(), START).is_err());
+    // A recovery the owner did not start is beyond the owner's reach
+    assert!(recovery::cancel(&mut vault, &mut recovery, owner, START).is_err());
+    let outcome = recovery::cancel(&mut vault, &mut recovery, first, START).unwrap();
+    assert!(matches!(outcome, CancelOutcome::Voted { votes: 1 }));
+    assert!(recovery::cancel(&mut vault, &mut recovery, first, START).is_err());
+    assert_eq!(vault.active_recovery, active);
+
+    let outcome = recovery::cancel(&mut vault, &mut recovery, second, START + 1).unwrap();
+    assert!(matches!(outcome, CancelOutcome::Cancelled));
+    assert_eq!(recovery.status, RecoveryStatus::Cancelled);
+    assert_eq!(recovery.end_timestamp, START + 1);
+    assert_eq!(vault.active_recovery, None);
+}
+
+#[test]
+fn inactivity_recovery_is_not_cancelled_by_vote() {
+    let mut vault = vault(3, 1, 0);
+    vault.inactivity_period = 0;
+    let mut recovery = Recovery::default();
+    recovery::check_inactivity(
+        &mut vault,
+        &mut recovery,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        START,
+    )
+    .unwrap();
+
+    let (owner, guardian) = (vault.owner, vault.guardians[0]);
+    assert!(recovery::cancel(&mut vault, &mut recovery, guardian, START).is_err());
+    assert!(recovery::cancel(&mut vault, &mut recovery, owner, START).is_err());
+    assert!(recovery.cancel_votes.is_empty());
+    recovery::cancel_inactivity_recovery(&mut vault, &mut recovery, START).unwrap();
+    assert_eq!(recovery.status, RecoveryStatus::Cancelled);
+}
+
+#[test]
+fn crank_is_rewarded_once_per_recovery() {
+    let mut reserve = CrankReserve {
+        vault: Pubkey::new_unique(),
+        bounty: 10_000,
+        inactivity_rewarded_id: 0,
+        execution_rewarded_id: 0,
+        expiry_rewarded_id: 0,
+        bump: 255,
+    };
+
+    assert!(reserve.claim(CrankKind::CheckInactivity, 1));
+    assert!(!reserve.claim(CrankKind::CheckInactivity, 1));
+    // Each crank is tracked separately
+    assert!(reserve.claim(CrankKind::ExecuteRecovery, 1));
+    assert!(!reserve.claim(CrankKind::ExecuteRecovery, 1));
+    assert!(reserve.claim(CrankKind::ExpireRecovery, 1));
+    assert!(reserve.claim(CrankKind::CheckInactivity, 2));
+}
+
+#[test]
+fn frozen_vault_does_not_execute_recovery() {
+    let mut vault = vault(3, 2, 0);
+    let mut recovery = start(&mut vault, Pubkey::new_unique(), START);
+    let owner = vault.owner;
+    freeze::freeze(&mut vault, owner, START).unwrap();
+
+    for guardian in vault.guardians.clone().into_iter().take(2) {
+        let outcome = recovery::approve(&mut vault, &mut recovery, guardian, START).unwrap();
+        assert!(matches!(outcome, ApprovalOutcome::Pending { .. }));
+    }
+    assert!(!recovery::ready_to_execute(&vault, &recovery, START));
+    assert!(recovery::execute(&mut vault.clone(), &mut recovery.clone(), START).is_err());
+
+    let thawed = START + freeze::MAX_FREEZE_DURATION;
+    assert!(recovery::ready_to_execute(&vault, &recovery, thawed));
+}
+
+#[test]
+fn owner_cannot_refreeze_during_cooldown() {
+    let mut vault = vault(3, 2, 0);
+    let owner = vault.owner;
+    freeze::freeze(&mut vault, owner, START).unwrap();
+    freeze::unfreeze(&mut vault, owner, START + 10).unwrap();
+
+    let cooldown_end = START + 10 + freeze::OWNER_FREEZE_COOLDOWN;
+    assert!(freeze::freeze(&mut vault, owner, cooldown_end - 1).is_err());
+
+    // Guardians are not subject to the cooldown
+    let mut by_guardians = vault.clone();
+    let guardians = vault.guardians.clone();
+    freeze::freeze(&mut by_guardians, guardians[0], START + 11).unwrap();
+    assert!(matches!(
+        freeze::freeze(&mut by_guardians, guardians[1], START + 11).unwrap(),
+        freeze::FreezeOutcome::Frozen { .. }
+    ));
+
+    assert!(freeze::freeze(&mut vault, owner, cooldown_end).is_ok());
+}
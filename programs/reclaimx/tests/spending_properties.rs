@@ -0,0 +1,197 @@
+use anchor_lang::prelude::Pubkey;
+use proptest::prelude::*;
+use reclaimx::{freeze, spending, withdrawal, Vault, Withdrawal, WithdrawalStatus};
+
+const START: i64 = 1_700_000_000;
+const DAY: u64 = 86_400;
+
+fn vault() -> Vault {
+    Vault {
+        owner: Pubkey::new_unique(),
+        guardians: (0..3).map(|_| Pubkey::new_unique()).collect(),
+        threshold: 2,
+        timelock: 3_600,
+        inactivity_period: DAY,
+        backup_wallet: Pubkey::new_unique(),
+        last_active_timestamp: START,
+        bump: 255,
+        recovery_expiry: 3_600 + Vault::DEFAULT_RECOVERY_EXPIRY,
+        approval_window: 3_600 + Vault::DEFAULT_RECOVERY_EXPIRY,
+        creator: Pubkey::new_unique(),
+        ..Vault::default()
+    }
+}
+
+proptest! {
+    #[test]
+    fn owner_alone_never_exceeds_limit_within_a_period(
+        max_amount in 1u64..1_000,
+        withdrawals in prop::collection::vec((0u64..400, 0u32..20_000), 1..40),
+    ) {
+        let mut vault = vault();
+        let mint = Pubkey::new_unique();
+        spending::set_limit(&mut vault, mint, max_amount, DAY, None, None, START).unwrap();
+
+        let mut now = START;
+        let mut period_start = START;
+        let mut spent = 0u64;
+        for (amount, wait) in withdrawals {
+            now += wait as i64;
+            if now >= period_start + DAY as i64 {
+                period_start = now;
+                spent = 0;
+            }
+            let allowed = spending::record_withdrawal(&mut vault, mint, amount, None, now).is_ok();
+            prop_assert_eq!(allowed, spent + amount <= max_amount);
+            if allowed {
+                spent += amount;
+            }
+            prop_assert!(spent <= max_amount);
+            prop_assert_eq!(vault.spending_limits[0].remaining(now), max_amount - spent);
+        }
+    }
+}
+
+#[test]
+fn guardian_co_signs_over_limit() {
+    let mut vault = vault();
+    let mint = Pubkey::new_unique();
+    let guardian = vault.guardians[0];
+    spending::set_limit(&mut vault, mint, 100, DAY, None, None, START).unwrap();
+
+    assert!(spending::record_withdrawal(&mut vault, mint, 150, None, START).is_err());
+    assert!(
+        spending::record_withdrawal(&mut vault, mint, 150, Some(Pubkey::new_unique()), START)
+            .is_err()
+    );
+    spending::record_withdrawal(&mut vault, mint, 150, Some(guardian), START).unwrap();
+    // Co-signed withdrawals still use up the period's allowance
+    assert_eq!(vault.spending_limits[0].remaining(START), 0);
+    // Mints without a limit are unrestricted
+    spending::record_withdrawal(&mut vault, Pubkey::new_unique(), u64::MAX, None, START).unwrap();
+}
+
+#[test]
+fn loosening_a_limit_needs_guardian() {
+    let mut vault = vault();
+    let mint = Pubkey::new_unique();
+    let guardian = vault.guardians[0];
+    spending::set_limit(&mut vault, mint, 100, DAY, None, None, START).unwrap();
+
+    // Tightening is the owner's call alone
+    spending::set_limit(&mut vault, mint, 50, DAY, None, None, START).unwrap();
+    spending::set_limit(&mut vault, mint, 50, 2 * DAY, None, None, START).unwrap();
+    assert!(spending::set_limit(&mut vault, mint, 80, 2 * DAY, None, None, START).is_err());
+    assert!(spending::set_limit(&mut vault, mint, 50, DAY, None, None, START).is_err());
+    assert!(spending::remove_limit(&mut vault, mint, None).is_err());
+
+    spending::set_limit(&mut vault, mint, 80, DAY, None, Some(guardian), START).unwrap();
+    assert_eq!(vault.spending_limits[0].max_amount, 80);
+    spending::remove_limit(&mut vault, mint, Some(guardian)).unwrap();
+    assert!(vault.spending_limits.is_empty());
+    assert!(spending::set_limit(&mut vault, mint, 80, 0, None, None, START).is_err());
+}
+
+#[test]
+fn queue_threshold_only_tightens_without_guardian() {
+    let mut vault = vault();
+    let mint = Pubkey::new_unique();
+    let guardian = vault.guardians[0];
+    spending::set_limit(&mut vault, mint, 1_000, DAY, Some(500), None, START).unwrap();
+    assert!(spending::must_queue(&vault, &mint, 501));
+    assert!(!spending::must_queue(&vault, &mint, 500));
+    assert!(!spending::must_queue(
+        &vault,
+        &Pubkey::new_unique(),
+        u64::MAX
+    ));
+
+    spending::set_limit(&mut vault, mint, 1_000, DAY, Some(100), None, START).unwrap();
+    assert!(spending::set_limit(&mut vault, mint, 1_000, DAY, Some(200), None, START).is_err());
+    assert!(spending::set_limit(&mut vault, mint, 1_000, DAY, None, None, START).is_err());
+    spending::set_limit(&mut vault, mint, 1_000, DAY, None, Some(guardian), START).unwrap();
+    assert!(!spending::must_queue(&vault, &mint, u64::MAX));
+}
+
+// Queues `amount` of `mint` as `queue_withdrawal` would
+fn queue_withdrawal(vault: &mut Vault, mint: Pubkey, amount: u64, now: i64) -> Withdrawal {
+    let mut queued = Withdrawal {
+        mint,
+        amount,
+        ..Withdrawal::default()
+    };
+    withdrawal::queue(vault, &mut queued, None, now).unwrap();
+    queued
+}
+
+#[test]
+fn queued_withdrawal_waits_for_timelock() {
+    let mut vault = vault();
+    let mint = Pubkey::new_unique();
+    let mut queued = queue_withdrawal(&mut vault, mint, 1_000, START);
+    assert_eq!(queued.withdrawal_id, 1);
+    assert_eq!(vault.withdrawal_id, 1);
+
+    let matured = START + vault.timelock as i64;
+    assert!(!withdrawal::ready_to_execute(&vault, &queued, matured - 1));
+    assert!(withdrawal::execute(&vault, &mut queued.clone(), matured - 1).is_err());
+
+    // A freeze holds the withdrawal back even after its delay
+    let owner = vault.owner;
+    let mut frozen = vault.clone();
+    freeze::freeze(&mut frozen, owner, START).unwrap();
+    assert!(withdrawal::execute(&frozen, &mut queued.clone(), matured).is_err());
+
+    withdrawal::execute(&vault, &mut queued, matured).unwrap();
+    assert_eq!(queued.status, WithdrawalStatus::Executed);
+    assert_eq!(queued.end_timestamp, matured);
+    assert!(withdrawal::execute(&vault, &mut queued, matured).is_err());
+}
+
+#[test]
+fn any_guardian_vetoes_pending_withdrawal() {
+    let mut vault = vault();
+    let mint = Pubkey::new_unique();
+    let mut queued = queue_withdrawal(&mut vault, mint, 1_000, START);
+    let matured = START + vault.timelock as i64;
+
+    let owner = vault.owner;
+    assert!(withdrawal::veto(&mut vault, &mut queued, owner, START).is_err());
+    assert!(withdrawal::veto(&mut vault, &mut queued, Pubkey::new_unique(), START).is_err());
+    let guardian = vault.guardians[2];
+    withdrawal::veto(&mut vault, &mut queued, guardian, matured).unwrap();
+    assert_eq!(queued.status, WithdrawalStatus::Vetoed);
+    assert_eq!(queued.vetoed_by, Some(guardian));
+    assert!(withdrawal::execute(&vault, &mut queued, matured).is_err());
+
+    let mut second = queue_withdrawal(&mut vault, mint, 1_000, START);
+    assert_eq!(second.withdrawal_id, 2);
+    withdrawal::cancel(&mut vault, &mut second, START).unwrap();
+    assert!(withdrawal::veto(&mut vault, &mut second, guardian, START).is_err());
+}
+
+#[test]
+fn vetoed_or_cancelled_withdrawal_frees_its_allowance() {
+    let mut vault = vault();
+    let mint = Pubkey::new_unique();
+    let guardian = vault.guardians[0];
+    spending::set_limit(&mut vault, mint, 1_000, DAY, Some(100), None, START).unwrap();
+
+    let mut vetoed = queue_withdrawal(&mut vault, mint, 800, START);
+    assert_eq!(vault.spending_limits[0].remaining(START), 200);
+    withdrawal::veto(&mut vault, &mut vetoed, guardian, START + 10).unwrap();
+    assert_eq!(vault.spending_limits[0].remaining(START + 10), 1_000);
+    // The full allowance is there for the owner again
+    spending::record_withdrawal(&mut vault, mint, 900, None, START + 20).unwrap();
+
+    let mut cancelled = queue_withdrawal(&mut vault, mint, 100, START + 30);
+    withdrawal::cancel(&mut vault, &mut cancelled, START + 40).unwrap();
+    assert_eq!(vault.spending_limits[0].remaining(START + 40), 100);
+
+    // A withdrawal from an earlier period gives nothing back to the current one
+    let mut stale = queue_withdrawal(&mut vault, mint, 100, START + 50);
+    let next_period = START + DAY as i64;
+    spending::record_withdrawal(&mut vault, mint, 300, None, next_period).unwrap();
+    withdrawal::cancel(&mut vault, &mut stale, next_period + 1).unwrap();
+    assert_eq!(vault.spending_limits[0].remaining(next_period + 1), 700);
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::*;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+#[tokio::test]
+async fn initialize_rejects_timing_outside_config_bounds() {
+    let mut context = program_test_context().await;
+    let owner = Keypair::new();
+    fund(&mut context, &owner.pubkey(), 1_000_000_000).await;
+    let config = fetch_config(&mut context).await;
+    let payer = context.payer.pubkey();
+    let guardians = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+
+    for (timelock, inactivity_period) in [
+        (0, INACTIVITY_PERIOD),
+        (config.max_timelock + 1, INACTIVITY_PERIOD),
+        (TIMELOCK, 0),
+        (TIMELOCK, config.max_inactivity_period + 1),
+    ] {
+        let ix = initialize_vault_ix(
+            &owner.pubkey(),
+            &payer,
+            0,
+            guardians.clone(),
+            1,
+            timelock,
+            inactivity_period,
+            Pubkey::new_unique(),
+        );
+        assert!(process(&mut context, &[ix], &[&owner]).await.is_err());
+    }
+}
+
+#[tokio::test]
+async fn timing_change_waits_out_current_timelock() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let owner = vault.owner.pubkey();
+    let cranker = context.payer.pubkey();
+    let (timelock, inactivity_period) = (2 * TIMELOCK, 2 * INACTIVITY_PERIOD);
+
+    let stranger = Keypair::new();
+    let ix = update_timing_ix(
+        &vault.vault,
+        &stranger.pubkey(),
+        timelock,
+        inactivity_period,
+    );
+    assert!(process(&mut context, &[ix], &[&stranger]).await.is_err());
+    let ix = update_timing_ix(&vault.vault, &owner, 0, inactivity_period);
+    assert!(process(&mut context, &[ix], &[&vault.owner]).await.is_err());
+
+    let ix = update_timing_ix(&vault.vault, &owner, timelock, inactivity_period);
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.timelock, TIMELOCK);
+    assert!(state.pending_timing.is_some());
+
+    let ix = apply_timing_ix(&vault.vault, &cranker);
+    assert!(process(&mut context, &[ix.clone()], &[]).await.is_err());
+    warp_forward(&mut context, TIMELOCK as i64).await;
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let state = fetch_vault(&mut context, &vault.vault).await;
+    assert_eq!(state.timelock, timelock);
+    assert_eq!(state.inactivity_period, inactivity_period);
+    assert_eq!(state.pending_timing, None);
+}
+
+#[tokio::test]
+async fn owner_cancels_queued_timing_change() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let owner = vault.owner.pubkey();
+    let cranker = context.payer.pubkey();
+
+    let ix = cancel_timing_update_ix(&vault.vault, &owner);
+    assert!(process(&mut context, &[ix], &[&vault.owner]).await.is_err());
+    let ix = update_timing_ix(&vault.vault, &owner, TIMELOCK / 2, INACTIVITY_PERIOD);
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+    let ix = cancel_timing_update_ix(&vault.vault, &owner);
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+
+    warp_forward(&mut context, TIMELOCK as i64).await;
+    let ix = apply_timing_ix(&vault.vault, &cranker);
+    assert!(process(&mut context, &[ix], &[]).await.is_err());
+    assert_eq!(
+        fetch_vault(&mut context, &vault.vault).await.timelock,
+        TIMELOCK
+    );
+}
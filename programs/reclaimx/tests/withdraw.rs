@@ -0,0 +1,244 @@
+mod common;
+
+use common::*;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const DAY: u64 = 86_400;
+
+struct Funded {
+    mint: Pubkey,
+    vault_token_account: Pubkey,
+    destination: Pubkey,
+}
+
+// A vault-owned token account holding `amount`, and an owner account to withdraw into
+async fn fund_vault(
+    context: &mut solana_program_test::ProgramTestContext,
+    vault: &TestVault,
+    amount: u64,
+) -> Funded {
+    let mint_authority = context.payer.pubkey();
+    let mint = create_mint(context, &mint_authority).await;
+    let vault_token_account = create_token_account(context, &mint, &vault.vault).await;
+    let destination = create_token_account(context, &mint, &vault.owner.pubkey()).await;
+    mint_to(context, &mint, &vault_token_account, amount).await;
+    Funded {
+        mint,
+        vault_token_account,
+        destination,
+    }
+}
+
+#[tokio::test]
+async fn owner_withdraws_without_limit() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let funded = fund_vault(&mut context, &vault, 1_000).await;
+
+    let stranger = Keypair::new();
+    let ix = withdraw_asset_ix(
+        &vault.vault,
+        &stranger.pubkey(),
+        None,
+        &funded.vault_token_account,
+        &funded.destination,
+        400,
+    );
+    assert!(process(&mut context, &[ix], &[&stranger]).await.is_err());
+
+    let ix = withdraw_asset_ix(
+        &vault.vault,
+        &vault.owner.pubkey(),
+        None,
+        &funded.vault_token_account,
+        &funded.destination,
+        400,
+    );
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &funded.destination).await, 400);
+    assert_eq!(
+        token_balance(&mut context, &funded.vault_token_account).await,
+        600
+    );
+}
+
+#[tokio::test]
+async fn withdrawal_over_limit_needs_guardian() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let funded = fund_vault(&mut context, &vault, 1_000).await;
+    let owner = vault.owner.pubkey();
+    let guardian = &vault.guardians[0];
+
+    let ix = set_spending_limit_ix(&vault.vault, &owner, None, funded.mint, 100, DAY, None);
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+
+    let withdraw = |guardian: Option<Pubkey>, amount: u64| {
+        withdraw_asset_ix(
+            &vault.vault,
+            &owner,
+            guardian,
+            &funded.vault_token_account,
+            &funded.destination,
+            amount,
+        )
+    };
+    process(&mut context, &[withdraw(None, 60)], &[&vault.owner])
+        .await
+        .unwrap();
+    assert!(
+        process(&mut context, &[withdraw(None, 50)], &[&vault.owner])
+            .await
+            .is_err()
+    );
+    process(
+        &mut context,
+        &[withdraw(Some(guardian.pubkey()), 50)],
+        &[&vault.owner, guardian],
+    )
+    .await
+    .unwrap();
+    assert_eq!(token_balance(&mut context, &funded.destination).await, 110);
+
+    // Raising the limit needs a guardian as well
+    let ix = set_spending_limit_ix(&vault.vault, &owner, None, funded.mint, 500, DAY, None);
+    assert!(process(&mut context, &[ix], &[&vault.owner]).await.is_err());
+
+    // The allowance resets with the next period
+    warp_forward(&mut context, DAY as i64).await;
+    process(&mut context, &[withdraw(None, 100)], &[&vault.owner])
+        .await
+        .unwrap();
+    assert_eq!(token_balance(&mut context, &funded.destination).await, 210);
+}
+
+#[tokio::test]
+async fn frozen_vault_blocks_withdrawal() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let funded = fund_vault(&mut context, &vault, 1_000).await;
+    let owner = vault.owner.pubkey();
+
+    let ix = freeze_vault_ix(&vault.vault, &owner);
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+    let ix = withdraw_asset_ix(
+        &vault.vault,
+        &owner,
+        None,
+        &funded.vault_token_account,
+        &funded.destination,
+        1,
+    );
+    assert!(process(&mut context, &[ix], &[&vault.owner]).await.is_err());
+}
+
+#[tokio::test]
+async fn large_withdrawal_is_queued_and_vetoable() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let funded = fund_vault(&mut context, &vault, 1_000).await;
+    let owner = vault.owner.pubkey();
+    let cranker = context.payer.pubkey();
+
+    let ix = set_spending_limit_ix(
+        &vault.vault,
+        &owner,
+        None,
+        funded.mint,
+        1_000,
+        DAY,
+        Some(100),
+    );
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+    let ix = withdraw_asset_ix(
+        &vault.vault,
+        &owner,
+        None,
+        &funded.vault_token_account,
+        &funded.destination,
+        200,
+    );
+    assert!(process(&mut context, &[ix], &[&vault.owner]).await.is_err());
+
+    let execute = |withdrawal_id| {
+        execute_withdrawal_ix(
+            &vault.vault,
+            withdrawal_id,
+            &cranker,
+            &funded.vault_token_account,
+            &funded.destination,
+        )
+    };
+    for (withdrawal_id, amount) in [(1, 200), (2, 300)] {
+        let ix = queue_withdrawal_ix(
+            &vault.vault,
+            withdrawal_id,
+            &owner,
+            &funded.vault_token_account,
+            &funded.destination,
+            amount,
+        );
+        process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+    }
+    assert!(process(&mut context, &[execute(1)], &[]).await.is_err());
+
+    let guardian = &vault.guardians[1];
+    let ix = veto_withdrawal_ix(&vault.vault, 2, &guardian.pubkey());
+    process(&mut context, &[ix], &[guardian]).await.unwrap();
+
+    warp_forward(&mut context, TIMELOCK as i64).await;
+    process(&mut context, &[execute(1)], &[]).await.unwrap();
+    assert!(process(&mut context, &[execute(2)], &[]).await.is_err());
+    assert_eq!(token_balance(&mut context, &funded.destination).await, 200);
+
+    let vetoed = fetch_withdrawal(&mut context, &vault.vault, 2).await;
+    assert_eq!(vetoed.status, reclaimx::WithdrawalStatus::Vetoed);
+    assert_eq!(vetoed.vetoed_by, Some(guardian.pubkey()));
+}
+
+#[tokio::test]
+async fn vetoed_withdrawal_returns_its_allowance() {
+    let mut context = program_test_context().await;
+    let vault = initialize_vault(&mut context, 3, 2).await;
+    let funded = fund_vault(&mut context, &vault, 1_000).await;
+    let owner = vault.owner.pubkey();
+    let cranker = context.payer.pubkey();
+
+    let ix = set_spending_limit_ix(&vault.vault, &owner, None, funded.mint, 500, DAY, Some(100));
+    process(&mut context, &[ix], &[&vault.owner]).await.unwrap();
+    let queue = |withdrawal_id, amount| {
+        queue_withdrawal_ix(
+            &vault.vault,
+            withdrawal_id,
+            &owner,
+            &funded.vault_token_account,
+            &funded.destination,
+            amount,
+        )
+    };
+    process(&mut context, &[queue(1, 400)], &[&vault.owner])
+        .await
+        .unwrap();
+
+    let guardian = &vault.guardians[0];
+    let ix = veto_withdrawal_ix(&vault.vault, 1, &guardian.pubkey());
+    process(&mut context, &[ix], &[guardian]).await.unwrap();
+
+    // Without the refund this would take the period past its 500 limit
+    process(&mut context, &[queue(2, 450)], &[&vault.owner])
+        .await
+        .unwrap();
+    warp_forward(&mut context, TIMELOCK as i64).await;
+    let ix = execute_withdrawal_ix(
+        &vault.vault,
+        2,
+        &cranker,
+        &funded.vault_token_account,
+        &funded.destination,
+    );
+    process(&mut context, &[ix], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &funded.destination).await, 450);
+}